@@ -10,18 +10,33 @@ pub enum Span {
     Range(Range<usize>),
 }
 
+/// Spans covering more lines are shortened to their first and last lines.
+const MAX_SPAN_LINES: usize = 5;
+
 #[derive(Debug)]
 pub struct Error<'a> {
     message: &'a str,
     error_code: Option<&'a str>,
     source: Option<(&'a str, Span)>,
     file_name: Option<&'a str>,
+    labels: Vec<Label<'a>>,
+    notes: Vec<&'a str>,
+    help: Vec<&'a str>,
     ansi_colors: bool,
 }
 
 impl<'a> Error<'a> {
     pub fn new(message: &'a str) -> Self {
-        Self { message, error_code: None, source: None, file_name: None, ansi_colors: false }
+        Self {
+            message,
+            error_code: None,
+            source: None,
+            file_name: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+            ansi_colors: false,
+        }
     }
 
     pub fn with_error_code(mut self, error_code: &'a str) -> Self {
@@ -39,68 +54,82 @@ impl<'a> Error<'a> {
         self
     }
 
+    /// Adds a secondary span, e.g. pointing at a previous declaration.
+    pub fn with_label(mut self, label: Label<'a>) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Adds a `note: ...` line below the message.
+    pub fn with_note(mut self, note: &'a str) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Adds a `help: ...` line below the message and the notes.
+    pub fn with_help(mut self, help: &'a str) -> Self {
+        self.help.push(help);
+        self
+    }
+
     pub fn with_ansi_colors(mut self, ansi_colors: bool) -> Self {
         self.ansi_colors = ansi_colors;
         self
     }
 }
 
+/// A secondary span with a message. Labels in the same source as the primary span are rendered in
+/// the same snippet, others get their own snippet.
+#[derive(Debug)]
+pub struct Label<'a> {
+    message: &'a str,
+    source: &'a str,
+    span: Span,
+    file_name: Option<&'a str>,
+}
+
+impl<'a> Label<'a> {
+    pub fn new(message: &'a str, source: &'a str, span: Span) -> Self {
+        Self { message, source, span, file_name: None }
+    }
+
+    pub fn with_file_name(mut self, file_name: &'a str) -> Self {
+        self.file_name = Some(file_name);
+        self
+    }
+}
+
 impl fmt::Display for Error<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (locations, indent) = match &self.source {
-            None => (None, 2),
-            Some((source, span)) => {
-                let locations = locations(source, span.clone());
-                let indent = locations.iter().map(|loc| digits(loc.line) + 1).max().unwrap();
-                (Some(locations), indent)
-            }
-        };
+        // Group the spans by source
+        let mut snippets: Vec<Snippet<'_>> = Vec::new();
+        if let Some((source, span)) = &self.source {
+            let mut snippet = Snippet::new(source, self.file_name, true);
+            snippet.add(source, span.clone(), None);
+            snippets.push(snippet);
+        }
+        for label in &self.labels {
+            let idx = match snippets.iter().position(|snippet| {
+                std::ptr::eq(snippet.source, label.source) && snippet.file_name == label.file_name
+            }) {
+                Some(idx) => idx,
+                None => {
+                    snippets.push(Snippet::new(label.source, label.file_name, false));
+                    snippets.len() - 1
+                }
+            };
+            snippets[idx].add(label.source, label.span.clone(), Some(label.message));
+        }
 
-        if self.source.is_some() {
-            for _ in 0..indent - 1 {
-                write!(f, " ")?;
-            }
-            if self.ansi_colors {
-                write!(f, "{}", Blue.paint("--> "))?;
-            } else {
-                write!(f, "--> ")?;
-            }
-            if let Some(file_name) = self.file_name {
-                write!(f, "{}", file_name)?;
-                write!(f, ":")?;
-            }
+        let indent = snippets
+            .iter()
+            .flat_map(|snippet| snippet.lines.iter())
+            .map(|line| digits(line.line) + 1)
+            .max()
+            .unwrap_or(2);
 
-            match locations {
-                None => write!(f, "EOI\n")?,
-                Some(locations) => {
-                    write!(f, "{}:{}\n", locations[0].line, locations[0].column)?;
-
-                    pre(f, "", indent, self.ansi_colors)?;
-                    write!(f, "\n")?;
-
-                    for loc in locations {
-                        pre(f, &loc.line.to_string(), indent, self.ansi_colors)?;
-                        write!(f, "    {}\n", loc.line_slice)?;
-
-                        pre(f, "", indent, self.ansi_colors)?;
-                        write!(f, "    ")?;
-                        for _ in 0..loc.column - 1 {
-                            write!(f, " ")?;
-                        }
-                        let circumflexes =
-                            (loc.column - 1..loc.column_last).map(|_| '^').collect::<String>();
-                        if self.ansi_colors {
-                            write!(f, "{}", Red.paint(circumflexes))?;
-                        } else {
-                            write!(f, "{}", circumflexes)?;
-                        }
-                        write!(f, "\n")?;
-                    }
-
-                    pre(f, "", indent, self.ansi_colors)?;
-                    write!(f, "\n")?;
-                }
-            }
+        for snippet in &snippets {
+            snippet.fmt(f, indent, self.ansi_colors)?;
         }
 
         for _ in 0..indent {
@@ -116,6 +145,156 @@ impl fmt::Display for Error<'_> {
             write!(f, " {}", error_code)?;
         }
 
+        for (kind, text) in self
+            .notes
+            .iter()
+            .map(|note| ("note", note))
+            .chain(self.help.iter().map(|help| ("help", help)))
+        {
+            writeln!(f)?;
+            for _ in 0..indent {
+                write!(f, " ")?;
+            }
+            let kind = format!("{}:", kind);
+            if self.ansi_colors {
+                write!(f, "= {} ", ansi_term::Style::new().bold().paint(kind))?;
+            } else {
+                write!(f, "= {} ", kind)?;
+            }
+            write!(f, "{}", indent_str(text, indent + 2))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The annotated lines of one source.
+#[derive(Debug)]
+struct Snippet<'a> {
+    source: &'a str,
+    file_name: Option<&'a str>,
+    is_primary: bool,
+    /// The first location, shown in the header.
+    location: Option<(usize, usize)>,
+    /// Sorted by line number.
+    lines: Vec<Line<'a>>,
+}
+
+#[derive(Debug)]
+struct Line<'a> {
+    line: usize,
+    line_slice: &'a str,
+    markers: Vec<Marker<'a>>,
+}
+
+#[derive(Debug)]
+struct Marker<'a> {
+    column: usize,
+    column_last: usize,
+    is_primary: bool,
+    message: Option<&'a str>,
+}
+
+impl<'a> Snippet<'a> {
+    fn new(source: &'a str, file_name: Option<&'a str>, is_primary: bool) -> Self {
+        Self { source, file_name, is_primary, location: None, lines: Vec::new() }
+    }
+
+    fn add(&mut self, source: &'a str, span: Span, message: Option<&'a str>) {
+        let mut locations = locations(source, span);
+        if self.location.is_none() {
+            self.location = locations.first().map(|loc| (loc.line, loc.column));
+        }
+
+        // Shorten long spans
+        if locations.len() > MAX_SPAN_LINES {
+            locations.drain(2..locations.len() - 2);
+        }
+
+        let is_primary = message.is_none();
+        let last = locations.len().saturating_sub(1);
+        for (idx, loc) in locations.into_iter().enumerate() {
+            let marker = Marker {
+                column: loc.column,
+                column_last: loc.column_last,
+                is_primary,
+                message: if idx == last { message } else { None },
+            };
+            match self.lines.binary_search_by_key(&loc.line, |line| line.line) {
+                Ok(pos) => self.lines[pos].markers.push(marker),
+                Err(pos) => self.lines.insert(
+                    pos,
+                    Line { line: loc.line, line_slice: loc.line_slice, markers: vec![marker] },
+                ),
+            }
+        }
+    }
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, indent: usize, ansi_colors: bool) -> fmt::Result {
+        // Header
+        for _ in 0..indent - 1 {
+            write!(f, " ")?;
+        }
+        let arrow = if self.is_primary { "--> " } else { "::: " };
+        if ansi_colors {
+            write!(f, "{}", Blue.paint(arrow))?;
+        } else {
+            write!(f, "{}", arrow)?;
+        }
+        if let Some(file_name) = self.file_name {
+            write!(f, "{}", file_name)?;
+            write!(f, ":")?;
+        }
+        match self.location {
+            None => writeln!(f, "EOI")?,
+            Some((line, column)) => writeln!(f, "{}:{}", line, column)?,
+        }
+
+        pre(f, "", indent, ansi_colors)?;
+        writeln!(f)?;
+
+        // Lines
+        let mut prev_line = None;
+        for line in &self.lines {
+            if matches!(prev_line, Some(prev) if line.line > prev + 1) {
+                if ansi_colors {
+                    writeln!(f, "{}", Blue.paint("..."))?;
+                } else {
+                    writeln!(f, "...")?;
+                }
+            }
+            prev_line = Some(line.line);
+
+            pre(f, &line.line.to_string(), indent, ansi_colors)?;
+            writeln!(f, "    {}", line.line_slice)?;
+
+            let mut markers = line.markers.iter().collect::<Vec<_>>();
+            markers.sort_by_key(|marker| (!marker.is_primary, marker.column));
+            for marker in markers {
+                pre(f, "", indent, ansi_colors)?;
+                write!(f, "    ")?;
+                for _ in 0..marker.column - 1 {
+                    write!(f, " ")?;
+                }
+                let c = if marker.is_primary { '^' } else { '-' };
+                let mut underline =
+                    (marker.column - 1..marker.column_last).map(|_| c).collect::<String>();
+                if let Some(message) = marker.message {
+                    underline.push(' ');
+                    underline += message;
+                }
+                match (ansi_colors, marker.is_primary) {
+                    (true, true) => write!(f, "{}", Red.paint(underline))?,
+                    (true, false) => write!(f, "{}", Blue.paint(underline))?,
+                    (false, _) => write!(f, "{}", underline)?,
+                }
+                writeln!(f)?;
+            }
+        }
+
+        pre(f, "", indent, ansi_colors)?;
+        writeln!(f)?;
+
         Ok(())
     }
 }
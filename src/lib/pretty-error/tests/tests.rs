@@ -0,0 +1,158 @@
+use pretty_error::{Error, Label, Span};
+
+const SOURCE: &str = "declare register A\ndeclare register B, A\n\nA <- 1;\n";
+
+#[test]
+fn single_span() {
+    let error = Error::new("duplicate symbol \"A\"")
+        .with_error_code("[E001]")
+        .with_source(SOURCE, Span::Range(39..40))
+        .with_file_name("test.rt");
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> test.rt:2:21
+  |
+2 |    declare register B, A
+  |                        ^
+  |
+  = duplicate symbol "A" [E001]"#
+    );
+}
+
+#[test]
+fn label_same_source() {
+    let error = Error::new("duplicate symbol \"A\"")
+        .with_error_code("[E001]")
+        .with_source(SOURCE, Span::Range(39..40))
+        .with_label(Label::new("first declared here", SOURCE, Span::Range(17..18)));
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> 2:21
+  |
+1 |    declare register A
+  |                     - first declared here
+2 |    declare register B, A
+  |                        ^
+  |
+  = duplicate symbol "A" [E001]"#
+    );
+}
+
+#[test]
+fn label_other_source() {
+    const OTHER: &str = "declare register A\n";
+
+    let error = Error::new("duplicate symbol \"A\"")
+        .with_source(SOURCE, Span::Range(39..40))
+        .with_file_name("main.rt")
+        .with_label(
+            Label::new("first declared here", OTHER, Span::Range(17..18))
+                .with_file_name("other.rt"),
+        );
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> main.rt:2:21
+  |
+2 |    declare register B, A
+  |                        ^
+  |
+ ::: other.rt:1:18
+  |
+1 |    declare register A
+  |                     - first declared here
+  |
+  = duplicate symbol "A""#
+    );
+}
+
+#[test]
+fn labels_same_line() {
+    const SOURCE: &str = "A <- 1, A <- 2;";
+
+    let error = Error::new("register \"A\" is assigned more than once")
+        .with_source(SOURCE, Span::Range(0..14))
+        .with_label(Label::new("second assignment", SOURCE, Span::Range(8..14)))
+        .with_label(Label::new("first assignment", SOURCE, Span::Range(0..6)));
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> 1:1
+  |
+1 |    A <- 1, A <- 2;
+  |    ^^^^^^^^^^^^^^
+  |    ------ first assignment
+  |            ------ second assignment
+  |
+  = register "A" is assigned more than once"#
+    );
+}
+
+#[test]
+fn notes_and_help() {
+    let error = Error::new("statement has a feedback loop")
+        .with_source(SOURCE, Span::Range(42..48))
+        .with_note("buses are assigned in the same cycle\nthey must not depend on themselves")
+        .with_help("break the loop with a register");
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> 4:1
+  |
+4 |    A <- 1;
+  |    ^^^^^^
+  |
+  = statement has a feedback loop
+  = note: buses are assigned in the same cycle
+    they must not depend on themselves
+  = help: break the loop with a register"#
+    );
+}
+
+#[test]
+fn long_span() {
+    const SOURCE: &str = "a\nb\nc\nd\ne\nf\ng\n";
+
+    let error = Error::new("long").with_source(SOURCE, Span::Range(0..13));
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> 1:1
+  |
+1 |    a
+  |    ^
+2 |    b
+  |    ^
+...
+6 |    f
+  |    ^
+7 |    g
+  |    ^
+  |
+  = long"#
+    );
+}
+
+#[test]
+fn separated_lines() {
+    const SOURCE: &str = "a\nb\nc\nd\n";
+
+    let error = Error::new("separated")
+        .with_source(SOURCE, Span::Range(6..7))
+        .with_label(Label::new("here", SOURCE, Span::Range(0..1)));
+
+    assert_eq!(
+        error.to_string(),
+        r#" --> 4:1
+  |
+1 |    a
+  |    - here
+...
+4 |    d
+  |    ^
+  |
+  = separated"#
+    );
+}
@@ -334,6 +334,34 @@ where
     }
 }
 
+/// Error recovery: If `f` fails, `sync` is applied to the same input to skip the erroneous tokens
+/// and the error of `f` is returned as output. If `sync` fails too, the error of `f` is returned.
+/// Errors of [`fail`] are not recovered from.
+pub fn recover<'s, 't, T, O, O2, F, S>(
+    f: F,
+    sync: S,
+) -> impl Fn(State<'s, 't, T>) -> PResult<'s, 't, T, Result<O, Error<T>>>
+where
+    F: Parser<'s, 't, T, O>,
+    S: Parser<'s, 't, T, O2>,
+    's: 't,
+{
+    move |state| {
+        let input = state.input();
+        match f.parse(state) {
+            Ok((rest, out)) => Ok((rest, Ok(out))),
+            Err(e) => {
+                let (input, err) = e.recover(input)?.into_parts();
+                let err = err.unwrap();
+                match sync.parse(State::from_parts(input, StateError::none())) {
+                    Ok((rest, _)) => Ok((rest, Err(err.into()))),
+                    Err(_) => Err(err),
+                }
+            }
+        }
+    }
+}
+
 pub fn exact<'s, 't, T>(token: T) -> impl Fn(State<'s, 't, T>) -> PResult<'s, 't, T, &'s str>
 where
     T: Clone + PartialEq,
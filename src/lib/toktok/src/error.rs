@@ -131,7 +131,30 @@ impl<T> Error<T> {
     where
         T: fmt::Debug,
     {
-        let message = match &self.kind {
+        let message = self.message(options);
+        let mut error = pretty_error::Error::new(&message);
+        if let Some(source) = options.source {
+            let span = match &self.span {
+                Span::Eoi => pretty_error::Span::Eoi,
+                Span::Range(range) => pretty_error::Span::Range(range.clone()),
+            };
+            error = error.with_source(source, span);
+        }
+        if let Some(file_name) = options.file_name {
+            error = error.with_file_name(file_name);
+        }
+        error = error.with_ansi_colors(options.ansi_colors);
+
+        error.to_string()
+    }
+
+    /// The message of the error, without the source. Only `rename_token` and `filter_expected` of
+    /// the options are used.
+    pub fn message(&self, options: &PrettyPrintOptions<'_, T>) -> String
+    where
+        T: fmt::Debug,
+    {
+        match &self.kind {
             ErrorKind::Expected(expected, found) => {
                 let expected_filtered;
                 let expected = match &options.filter_expected {
@@ -156,22 +179,7 @@ impl<T> Error<T> {
             }
             ErrorKind::ExpectedNegative(err) => err.to_string(),
             ErrorKind::Custom(err) => err.to_string(),
-        };
-
-        let mut error = pretty_error::Error::new(&message);
-        if let Some(source) = options.source {
-            let span = match &self.span {
-                Span::Eoi => pretty_error::Span::Eoi,
-                Span::Range(range) => pretty_error::Span::Range(range.clone()),
-            };
-            error = error.with_source(source, span);
-        }
-        if let Some(file_name) = options.file_name {
-            error = error.with_file_name(file_name);
         }
-        error = error.with_ansi_colors(options.ansi_colors);
-
-        error.to_string()
     }
 
     fn merge(e1: Option<Self>, e2: Self) -> Self {
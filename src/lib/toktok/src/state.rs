@@ -76,6 +76,10 @@ impl<'s, 't, T> Input<'s, 't, T> {
         self.source
     }
 
+    pub fn tokens(&self) -> &'t [SpannedToken<T>] {
+        self.tokens
+    }
+
     pub fn positioned_start(&self) -> usize {
         match self.tokens.get(0) {
             Some(token) => token.span.start,
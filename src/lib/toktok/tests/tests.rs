@@ -1,5 +1,5 @@
 use toktok::{
-    combinator::{alt, exact, pair},
+    combinator::{alt, exact, fail, many0, pair, recover},
     PResult, Parser, SpannedToken,
 };
 
@@ -56,6 +56,71 @@ fn test_err() {
     //panic!("{:#?}", statement(input));
 }
 
+// Skips all tokens up to and including the next semicolon
+fn sync<'s, 't>(mut state: State<'s, 't>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    if state.first().is_none() {
+        return Err(state.and_error(toktok::Error::new_expected(
+            toktok::Span::Eoi,
+            vec![],
+            toktok::TokenOrEoi::Eoi,
+        )));
+    }
+    while let Some(token) = state.first() {
+        let is_semicolon = token.token == Token::Semicolon;
+        state = state.split_first().0;
+        if is_semicolon {
+            break;
+        }
+    }
+
+    Ok((state, ()))
+}
+
+#[test]
+fn test_recover() {
+    let source = "abc 12; 12; abc; def ghi;";
+    let tokens = &[
+        SpannedToken { token: Token::Ident, span: 0..3 },
+        SpannedToken { token: Token::Num, span: 4..6 },
+        SpannedToken { token: Token::Semicolon, span: 6..7 },
+        SpannedToken { token: Token::Num, span: 8..10 },
+        SpannedToken { token: Token::Semicolon, span: 10..11 },
+        SpannedToken { token: Token::Ident, span: 12..15 },
+        SpannedToken { token: Token::Semicolon, span: 15..16 },
+        SpannedToken { token: Token::Ident, span: 17..20 },
+        SpannedToken { token: Token::Ident, span: 21..24 },
+        SpannedToken { token: Token::Semicolon, span: 24..25 },
+    ];
+    let input = State::new(source, tokens);
+
+    let (rest, statements) = many0(recover(statement, sync))(input).unwrap();
+    assert!(rest.input().is_empty());
+    let errors = statements
+        .iter()
+        .map(|statement| statement.as_ref().err().map(|e| e.span()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![None, Some(toktok::Span::Range(8..10)), None, Some(toktok::Span::Range(21..24))]
+    );
+}
+
+#[test]
+fn test_recover_fail() {
+    let source = "12;";
+    let tokens = &[
+        SpannedToken { token: Token::Num, span: 0..2 },
+        SpannedToken { token: Token::Semicolon, span: 2..3 },
+    ];
+    let input = State::new(source, tokens);
+
+    let err = recover(fail(statement), sync)(input).unwrap_err();
+    assert!(err.is_fail());
+}
+
 /*use toktok::{
     combinator::{alt, exact, seq},
     Parser, Result, SpannedToken,
@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use value::Value;
+
+fn values(size: usize) -> (Value, Value) {
+    let lhs = "10".repeat(size / 2);
+    let rhs = "1101".repeat(size / 4);
+    (Value::parse_bin(&lhs).unwrap(), Value::parse_bin(&rhs).unwrap())
+}
+
+fn ops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ops");
+    for size in [8, 64, 256] {
+        let (lhs, rhs) = values(size);
+        group.bench_with_input(BenchmarkId::new("add", size), &size, |b, _| {
+            b.iter(|| black_box(&lhs) + black_box(&rhs))
+        });
+        group.bench_with_input(BenchmarkId::new("sub", size), &size, |b, _| {
+            b.iter(|| black_box(&lhs) - black_box(&rhs))
+        });
+        group.bench_with_input(BenchmarkId::new("xor", size), &size, |b, _| {
+            b.iter(|| black_box(&lhs) ^ black_box(&rhs))
+        });
+        group.bench_with_input(BenchmarkId::new("neg", size), &size, |b, _| {
+            b.iter(|| -black_box(&lhs))
+        });
+        group.bench_with_input(BenchmarkId::new("cmp", size), &size, |b, _| {
+            b.iter(|| black_box(&lhs) < black_box(&rhs))
+        });
+        group.bench_with_input(BenchmarkId::new("shl", size), &size, |b, _| {
+            b.iter(|| black_box(&lhs) << 3)
+        });
+    }
+    group.finish();
+}
+
+fn slices(c: &mut Criterion) {
+    let mut group = c.benchmark_group("slices");
+    for size in [8, 64, 256] {
+        let (lhs, rhs) = values(size);
+        group.bench_with_input(BenchmarkId::new("concat", size), &size, |b, _| {
+            b.iter(|| Value::concat([lhs.slice(1..), rhs.slice(..size - 1)]))
+        });
+        group.bench_with_input(BenchmarkId::new("write", size), &size, |b, _| {
+            let mut value = lhs.clone();
+            b.iter(|| value.slice_mut(3..size - 1).write(black_box(&rhs)))
+        });
+    }
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
+    for size in [8, 64, 256] {
+        let (value, _) = values(size);
+        let dec = value.as_dec();
+        group.bench_with_input(BenchmarkId::new("as_dec", size), &size, |b, _| {
+            b.iter(|| black_box(&value).as_dec())
+        });
+        group.bench_with_input(BenchmarkId::new("as_hex", size), &size, |b, _| {
+            b.iter(|| black_box(&value).as_hex())
+        });
+        group.bench_with_input(BenchmarkId::new("parse_dec", size), &size, |b, _| {
+            b.iter(|| Value::parse_dec(black_box(&dec)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, ops, slices, format);
+criterion_main!(benches);
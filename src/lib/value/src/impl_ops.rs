@@ -1,55 +1,35 @@
-use super::{Bit, Value, ValueSlice};
+use super::{slice::LIMB_BITS, Value, ValueSlice};
 use std::cmp::{self, Ord, PartialEq, PartialOrd};
-use std::ops::{Add, BitAnd, BitOr, BitXor, Index, IndexMut, Neg, Not, Shl, Shr, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Shl, Shr, Sub};
 
 // ------------------------------------------------------------------
 // Cmp
 // ------------------------------------------------------------------
 
-fn eq(lhs: &ValueSlice, rhs: &ValueSlice) -> bool {
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-
-    for i in 0..len {
-        if lhs.bits.get(i).copied().unwrap_or_default()
-            != rhs.bits.get(i).copied().unwrap_or_default()
-        {
-            return false;
-        }
-    }
-
-    true
+fn eq(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> bool {
+    let count = cmp::max(lhs.word_count(), rhs.word_count());
+    (0..count).all(|idx| lhs.word(idx) == rhs.word(idx))
 }
 
 // TODO: This assumes positive values
-fn cmp(lhs: &ValueSlice, rhs: &ValueSlice) -> cmp::Ordering {
-    let mut idx = cmp::max(lhs.bits.len(), rhs.bits.len()) - 1;
-
-    loop {
-        let lhs = lhs.bits.get(idx).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(idx).copied().unwrap_or_default();
-        let res = lhs.cmp(&rhs);
-
-        if res != cmp::Ordering::Equal {
-            break res;
-        }
-
-        if idx == 0 {
-            break cmp::Ordering::Equal;
-        } else {
-            idx -= 1;
-        }
-    }
+fn cmp(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> cmp::Ordering {
+    let count = cmp::max(lhs.word_count(), rhs.word_count());
+    (0..count)
+        .rev()
+        .map(|idx| lhs.word(idx).cmp(&rhs.word(idx)))
+        .find(|res| *res != cmp::Ordering::Equal)
+        .unwrap_or(cmp::Ordering::Equal)
 }
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        cmp(self, other)
+        cmp(self.as_slice(), other.as_slice())
     }
 }
 
-impl Ord for ValueSlice {
+impl Ord for ValueSlice<'_> {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        cmp(self, other)
+        cmp(*self, *other)
     }
 }
 
@@ -57,118 +37,111 @@ macro_rules! impl_cmp {
     ($lhs:ty, $rhs:ty) => {
         impl PartialEq<$rhs> for $lhs {
             fn eq(&self, other: &$rhs) -> bool {
-                eq(self, other)
+                eq(self.as_slice(), other.as_slice())
             }
         }
 
         impl PartialOrd<$rhs> for $lhs {
             fn partial_cmp(&self, other: &$rhs) -> Option<cmp::Ordering> {
-                Some(cmp(self, other))
+                Some(cmp(self.as_slice(), other.as_slice()))
             }
         }
     };
 }
 
 impl_cmp!(Value, Value);
-impl_cmp!(Value, ValueSlice);
-impl_cmp!(ValueSlice, Value);
-impl_cmp!(ValueSlice, ValueSlice);
+impl_cmp!(Value, ValueSlice<'_>);
+impl_cmp!(ValueSlice<'_>, Value);
+impl_cmp!(ValueSlice<'_>, ValueSlice<'_>);
 
 // ------------------------------------------------------------------
 // Binary Ops
 // ------------------------------------------------------------------
 
-fn add(lhs: &ValueSlice, rhs: &ValueSlice) -> Value {
-    let mut result = Vec::new();
-    let mut carry = Bit::Zero;
-
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-    for i in 0..len {
-        let lhs = lhs.bits.get(i).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(i).copied().unwrap_or_default();
-
-        let (res, carry_next) = match (lhs, rhs, carry) {
-            (Bit::Zero, Bit::Zero, Bit::Zero) => (Bit::Zero, Bit::Zero),
-            (Bit::Zero, Bit::Zero, Bit::One) => (Bit::One, Bit::Zero),
-            (Bit::Zero, Bit::One, Bit::Zero) => (Bit::One, Bit::Zero),
-            (Bit::Zero, Bit::One, Bit::One) => (Bit::Zero, Bit::One),
-            (Bit::One, Bit::Zero, Bit::Zero) => (Bit::One, Bit::Zero),
-            (Bit::One, Bit::Zero, Bit::One) => (Bit::Zero, Bit::One),
-            (Bit::One, Bit::One, Bit::Zero) => (Bit::Zero, Bit::One),
-            (Bit::One, Bit::One, Bit::One) => (Bit::One, Bit::One),
-        };
-
-        result.push(res);
-        carry = carry_next;
+/// Applies `f` word by word. The result has the size of the bigger operand.
+fn word_wise(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>, f: impl Fn(u64, u64) -> u64) -> Value {
+    let size = cmp::max(lhs.size(), rhs.size());
+    if size <= LIMB_BITS {
+        return Value::from_word(f(lhs.word(0), rhs.word(0)), size);
     }
 
-    Value { bits: result }
+    let mut result = Value::zeroed(size);
+    for (idx, limb) in result.limbs_mut().iter_mut().enumerate() {
+        *limb = f(lhs.word(idx), rhs.word(idx));
+    }
+    result.normalize();
+    result
 }
 
-fn sub(lhs: &ValueSlice, rhs: &ValueSlice) -> Value {
-    let mut result = Vec::new();
-    let mut carry = Bit::Zero;
-
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-    for i in 0..len {
-        let lhs = lhs.bits.get(i).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(i).copied().unwrap_or_default();
-
-        let (res, carry_next) = match (lhs, rhs, carry) {
-            (Bit::Zero, Bit::Zero, Bit::Zero) => (Bit::Zero, Bit::Zero),
-            (Bit::Zero, Bit::Zero, Bit::One) => (Bit::One, Bit::One),
-            (Bit::Zero, Bit::One, Bit::Zero) => (Bit::One, Bit::One),
-            (Bit::Zero, Bit::One, Bit::One) => (Bit::Zero, Bit::One),
-            (Bit::One, Bit::Zero, Bit::Zero) => (Bit::One, Bit::Zero),
-            (Bit::One, Bit::Zero, Bit::One) => (Bit::Zero, Bit::Zero),
-            (Bit::One, Bit::One, Bit::Zero) => (Bit::Zero, Bit::Zero),
-            (Bit::One, Bit::One, Bit::One) => (Bit::One, Bit::One),
-        };
-
-        result.push(res);
-        carry = carry_next;
+fn add(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    let size = cmp::max(lhs.size(), rhs.size());
+    if size <= LIMB_BITS {
+        return Value::from_word(lhs.word(0).wrapping_add(rhs.word(0)), size);
     }
 
-    Value { bits: result }
+    let mut result = Value::zeroed(size);
+    let mut carry = false;
+    for (idx, limb) in result.limbs_mut().iter_mut().enumerate() {
+        let (res, carry_a) = lhs.word(idx).overflowing_add(rhs.word(idx));
+        let (res, carry_b) = res.overflowing_add(u64::from(carry));
+        *limb = res;
+        carry = carry_a || carry_b;
+    }
+    result.normalize();
+    result
 }
 
-fn bit_and(lhs: &ValueSlice, rhs: &ValueSlice) -> Value {
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-    let mut result = Vec::with_capacity(len);
-
-    for i in 0..len {
-        let lhs = lhs.bits.get(i).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(i).copied().unwrap_or_default();
-        result.push(lhs & rhs);
+fn sub(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    let size = cmp::max(lhs.size(), rhs.size());
+    if size <= LIMB_BITS {
+        return Value::from_word(lhs.word(0).wrapping_sub(rhs.word(0)), size);
     }
 
-    Value { bits: result }
+    let mut result = Value::zeroed(size);
+    let mut borrow = false;
+    for (idx, limb) in result.limbs_mut().iter_mut().enumerate() {
+        let (res, borrow_a) = lhs.word(idx).overflowing_sub(rhs.word(idx));
+        let (res, borrow_b) = res.overflowing_sub(u64::from(borrow));
+        *limb = res;
+        borrow = borrow_a || borrow_b;
+    }
+    result.normalize();
+    result
 }
 
-fn bit_or(lhs: &ValueSlice, rhs: &ValueSlice) -> Value {
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-    let mut result = Vec::with_capacity(len);
-
-    for i in 0..len {
-        let lhs = lhs.bits.get(i).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(i).copied().unwrap_or_default();
-        result.push(lhs | rhs);
+/// Multiplies both values. Like all other binary ops, the product is truncated to the size of the
+/// bigger operand.
+fn mul(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    let size = cmp::max(lhs.size(), rhs.size());
+    if size <= LIMB_BITS {
+        return Value::from_word(lhs.word(0).wrapping_mul(rhs.word(0)), size);
     }
 
-    Value { bits: result }
+    let mut result = Value::zeroed(size);
+    let limbs = result.limbs_mut();
+    for lhs_idx in 0..lhs.word_count() {
+        let lhs_word = u128::from(lhs.word(lhs_idx));
+        let mut carry = 0u128;
+        for (idx, limb) in limbs.iter_mut().enumerate().skip(lhs_idx) {
+            let cur = u128::from(*limb) + lhs_word * u128::from(rhs.word(idx - lhs_idx)) + carry;
+            *limb = cur as u64;
+            carry = cur >> LIMB_BITS;
+        }
+    }
+    result.normalize();
+    result
 }
 
-fn bit_xor(lhs: &ValueSlice, rhs: &ValueSlice) -> Value {
-    let len = cmp::max(lhs.bits.len(), rhs.bits.len());
-    let mut result = Vec::with_capacity(len);
+fn bit_and(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    word_wise(lhs, rhs, |lhs, rhs| lhs & rhs)
+}
 
-    for i in 0..len {
-        let lhs = lhs.bits.get(i).copied().unwrap_or_default();
-        let rhs = rhs.bits.get(i).copied().unwrap_or_default();
-        result.push(lhs ^ rhs);
-    }
+fn bit_or(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    word_wise(lhs, rhs, |lhs, rhs| lhs | rhs)
+}
 
-    Value { bits: result }
+fn bit_xor(lhs: ValueSlice<'_>, rhs: ValueSlice<'_>) -> Value {
+    word_wise(lhs, rhs, |lhs, rhs| lhs ^ rhs)
 }
 
 macro_rules! impl_binary_ops {
@@ -177,7 +150,7 @@ macro_rules! impl_binary_ops {
             type Output = Value;
 
             fn add(self, rhs: $rhs) -> Self::Output {
-                add(&self, &rhs)
+                add(self.as_slice(), rhs.as_slice())
             }
         }
 
@@ -185,7 +158,15 @@ macro_rules! impl_binary_ops {
             type Output = Value;
 
             fn sub(self, rhs: $rhs) -> Self::Output {
-                sub(&self, &rhs)
+                sub(self.as_slice(), rhs.as_slice())
+            }
+        }
+
+        impl Mul<$rhs> for $lhs {
+            type Output = Value;
+
+            fn mul(self, rhs: $rhs) -> Self::Output {
+                mul(self.as_slice(), rhs.as_slice())
             }
         }
 
@@ -193,7 +174,7 @@ macro_rules! impl_binary_ops {
             type Output = Value;
 
             fn bitand(self, rhs: $rhs) -> Self::Output {
-                bit_and(&self, &rhs)
+                bit_and(self.as_slice(), rhs.as_slice())
             }
         }
 
@@ -201,7 +182,7 @@ macro_rules! impl_binary_ops {
             type Output = Value;
 
             fn bitor(self, rhs: $rhs) -> Self::Output {
-                bit_or(&self, &rhs)
+                bit_or(self.as_slice(), rhs.as_slice())
             }
         }
 
@@ -209,7 +190,7 @@ macro_rules! impl_binary_ops {
             type Output = Value;
 
             fn bitxor(self, rhs: $rhs) -> Self::Output {
-                bit_xor(&self, &rhs)
+                bit_xor(self.as_slice(), rhs.as_slice())
             }
         }
     };
@@ -217,38 +198,38 @@ macro_rules! impl_binary_ops {
 
 impl_binary_ops!(Value, Value);
 impl_binary_ops!(Value, &Value);
-impl_binary_ops!(Value, &ValueSlice);
+impl_binary_ops!(Value, ValueSlice<'_>);
 impl_binary_ops!(&Value, Value);
 impl_binary_ops!(&Value, &Value);
-impl_binary_ops!(&Value, &ValueSlice);
-impl_binary_ops!(&ValueSlice, Value);
-impl_binary_ops!(&ValueSlice, &Value);
-impl_binary_ops!(&ValueSlice, &ValueSlice);
+impl_binary_ops!(&Value, ValueSlice<'_>);
+impl_binary_ops!(ValueSlice<'_>, Value);
+impl_binary_ops!(ValueSlice<'_>, &Value);
+impl_binary_ops!(ValueSlice<'_>, ValueSlice<'_>);
 
 // ------------------------------------------------------------------
 // Binary Ops (usize)
 // ------------------------------------------------------------------
 
-fn shl(lhs: &ValueSlice, rhs: usize) -> Value {
-    if rhs >= lhs.size() {
-        return Value::zero(lhs.size());
+fn shl(lhs: ValueSlice<'_>, rhs: usize) -> Value {
+    let size = lhs.size();
+    if rhs >= size {
+        return Value::zero(size);
     }
 
-    let mut bits = Vec::with_capacity(lhs.size());
-    bits.extend((0..rhs).map(|_| Bit::Zero));
-    bits.extend(&lhs.bits[0..lhs.bits.len() - rhs]);
-    Value { bits }
+    let mut result = Value::zeroed(size);
+    result.slice_mut(rhs..).write(lhs.slice(..size - rhs));
+    result
 }
 
-fn shr(lhs: &ValueSlice, rhs: usize) -> Value {
-    if rhs >= lhs.size() {
-        return Value::zero(lhs.size());
+fn shr(lhs: ValueSlice<'_>, rhs: usize) -> Value {
+    let size = lhs.size();
+    if rhs >= size {
+        return Value::zero(size);
     }
 
-    let mut bits = Vec::with_capacity(lhs.size());
-    bits.extend(&lhs.bits[rhs..]);
-    bits.extend((0..rhs).map(|_| Bit::Zero));
-    Value { bits }
+    let mut result = Value::zeroed(size);
+    result.slice_mut(..size - rhs).write(lhs.slice(rhs..));
+    result
 }
 
 macro_rules! impl_binary_ops_usize {
@@ -257,7 +238,7 @@ macro_rules! impl_binary_ops_usize {
             type Output = Value;
 
             fn shl(self, rhs: usize) -> Self::Output {
-                shl(&self, rhs)
+                shl(self.as_slice(), rhs)
             }
         }
 
@@ -265,7 +246,7 @@ macro_rules! impl_binary_ops_usize {
             type Output = Value;
 
             fn shr(self, rhs: usize) -> Self::Output {
-                shr(&self, rhs)
+                shr(self.as_slice(), rhs)
             }
         }
     };
@@ -273,18 +254,23 @@ macro_rules! impl_binary_ops_usize {
 
 impl_binary_ops_usize!(Value);
 impl_binary_ops_usize!(&Value);
-impl_binary_ops_usize!(&ValueSlice);
+impl_binary_ops_usize!(ValueSlice<'_>);
 
 // ------------------------------------------------------------------
 // Unary Ops
 // ------------------------------------------------------------------
 
-fn neg(self_: &ValueSlice) -> Value {
-    !self_ + Value::one(1)
+fn neg(self_: ValueSlice<'_>) -> Value {
+    not(self_) + Value::one(1)
 }
 
-fn not(self_: &ValueSlice) -> Value {
-    Value { bits: self_.bits.into_iter().copied().map(Not::not).collect() }
+fn not(self_: ValueSlice<'_>) -> Value {
+    let mut result = Value::zeroed(self_.size());
+    for (idx, limb) in result.limbs_mut().iter_mut().enumerate() {
+        *limb = !self_.word(idx);
+    }
+    result.normalize();
+    result
 }
 
 macro_rules! impl_unary_ops {
@@ -293,7 +279,7 @@ macro_rules! impl_unary_ops {
             type Output = Value;
 
             fn neg(self) -> Self::Output {
-                neg(&self)
+                neg(self.as_slice())
             }
         }
 
@@ -301,7 +287,7 @@ macro_rules! impl_unary_ops {
             type Output = Value;
 
             fn not(self) -> Self::Output {
-                not(&self)
+                not(self.as_slice())
             }
         }
     };
@@ -309,233 +295,234 @@ macro_rules! impl_unary_ops {
 
 impl_unary_ops!(Value);
 impl_unary_ops!(&Value);
-impl_unary_ops!(&ValueSlice);
-
-// ------------------------------------------------------------------
-// Index
-// ------------------------------------------------------------------
-
-impl<I> Index<I> for ValueSlice
-where
-    [Bit]: Index<I, Output = [Bit]>,
-{
-    type Output = ValueSlice;
-
-    fn index(&self, index: I) -> &Self::Output {
-        unsafe { &*(&self.bits[index] as *const [Bit] as *const ValueSlice) }
-    }
-}
-
-impl<I> IndexMut<I> for ValueSlice
-where
-    [Bit]: Index<I, Output = [Bit]>,
-    [Bit]: IndexMut<I>,
-{
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        unsafe { &mut *(&mut self.bits[index] as *mut [Bit] as *mut ValueSlice) }
-    }
-}
+impl_unary_ops!(ValueSlice<'_>);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Bit;
 
     #[test]
     fn test_cmp() {
         // GE
         assert!(
-            Value { bits: vec![Bit::One, Bit::One] }
-                >= Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One])
+                >= Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
         assert!(
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
-                >= Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
+                >= Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
         );
 
         // LE
         assert!(
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
-                <= Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
+                <= Value::from_bits(vec![Bit::One, Bit::One])
         );
         assert!(
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
-                <= Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
+                <= Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
         );
 
         // GT
         assert!(
-            Value { bits: vec![Bit::One, Bit::One] }
-                > Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One])
+                > Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
         assert!(
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One, Bit::One] }
-                > Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::Zero,] }
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One, Bit::One])
+                > Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::Zero,])
         );
 
         // LT
         assert!(
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
-                < Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
+                < Value::from_bits(vec![Bit::One, Bit::One])
         );
         assert!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::Zero,] }
-                < Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::Zero,])
+                < Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One, Bit::One])
         );
 
         // EQ
-        assert!(Value { bits: vec![Bit::Zero, Bit::Zero] } == Value { bits: vec![Bit::Zero] });
+        assert!(Value::from_bits(vec![Bit::Zero, Bit::Zero]) == Value::from_bits(vec![Bit::Zero]));
         assert!(
-            Value { bits: vec![Bit::Zero, Bit::One, Bit::One, Bit::Zero] }
-                == Value { bits: vec![Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::Zero, Bit::One, Bit::One, Bit::Zero])
+                == Value::from_bits(vec![Bit::Zero, Bit::One, Bit::One])
         );
 
         // NE
-        assert!(Value { bits: vec![Bit::Zero, Bit::One] } != Value { bits: vec![Bit::One] });
+        assert!(Value::from_bits(vec![Bit::Zero, Bit::One]) != Value::from_bits(vec![Bit::One]));
         assert!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::One, Bit::One] }
-                != Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::One, Bit::One])
+                != Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
         );
     }
 
     #[test]
     fn test_add() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
-                + Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                + Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One] } + Value { bits: vec![Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One])
+                + Value::from_bits(vec![Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One] } + Value { bits: vec![Bit::One] },
-            Value { bits: vec![Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One]) + Value::from_bits(vec![Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero] }
-                + Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::One] },
-            Value { bits: vec![Bit::Zero, Bit::One, Bit::One, Bit::Zero, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero])
+                + Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::One, Bit::One, Bit::Zero, Bit::One])
         );
     }
 
     #[test]
     fn test_sub() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
-                - Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                - Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::One, Bit::Zero])
+        );
+        assert_eq!(
+            Value::from_bits(vec![Bit::Zero, Bit::Zero])
+                - Value::from_bits(vec![Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::One, Bit::One])
+        );
+        assert_eq!(
+            Value::from_bits(vec![Bit::One, Bit::Zero])
+                - Value::from_bits(vec![Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::One])
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        // 3 * 2 = 6
+        assert_eq!(
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                * Value::from_bits(vec![Bit::Zero, Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::One, Bit::One])
         );
+        // 3 * 3 = 9, truncated to 2 bits
         assert_eq!(
-            Value { bits: vec![Bit::Zero, Bit::Zero] } - Value { bits: vec![Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One]) * Value::from_bits(vec![Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::One, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::Zero] } - Value { bits: vec![Bit::One, Bit::One] },
-            Value { bits: vec![Bit::Zero, Bit::One] }
+            (Value::filled(100) * Value::filled(100)).as_bin(true),
+            format!("{}1", "0".repeat(99))
         );
     }
 
     #[test]
     fn test_bit_and() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
-                & Value { bits: vec![Bit::One, Bit::One, Bit::One] },
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                & Value::from_bits(vec![Bit::One, Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One] } & Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] },
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One])
+                & Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero]),
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero])
         );
     }
 
     #[test]
     fn test_bit_or() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
-                | Value { bits: vec![Bit::One, Bit::One, Bit::One] },
-            Value { bits: vec![Bit::One, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                | Value::from_bits(vec![Bit::One, Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::One])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One] } | Value { bits: vec![Bit::One, Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One])
+                | Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
         );
     }
 
     #[test]
     fn test_bit_xor() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
-                ^ Value { bits: vec![Bit::One, Bit::One, Bit::One] },
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
+                ^ Value::from_bits(vec![Bit::One, Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One] } ^ Value { bits: vec![Bit::One, Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One])
+                ^ Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::One, Bit::Zero])
         );
     }
 
     #[test]
     fn test_shl() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] } << 0,
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]) << 0,
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] } << 2,
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]) << 2,
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] } << 12,
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]) << 12,
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
     }
 
     #[test]
     fn test_shr() {
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] } >> 0,
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]) >> 0,
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One] } >> 2,
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One]) >> 2,
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero])
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero] } >> 12,
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]) >> 12,
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
     }
 
     #[test]
     fn test_neg() {
         assert_eq!(
-            -Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            -Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
         assert_eq!(
-            -Value { bits: vec![Bit::One, Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One] }
+            -Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One])
         );
         assert_eq!(
-            -Value { bits: vec![Bit::One, Bit::One, Bit::One] },
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::Zero] }
+            -Value::from_bits(vec![Bit::One, Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::Zero])
         );
     }
 
     #[test]
     fn test_not() {
         assert_eq!(
-            !Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] },
-            Value { bits: vec![Bit::One, Bit::One, Bit::One] }
+            !Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero]),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::One])
         );
         assert_eq!(
-            !Value { bits: vec![Bit::One, Bit::One, Bit::Zero] },
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::One] }
+            !Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::One])
         );
         assert_eq!(
-            !Value { bits: vec![Bit::One, Bit::One, Bit::One] },
-            Value { bits: vec![Bit::Zero, Bit::Zero, Bit::Zero] }
+            !Value::from_bits(vec![Bit::One, Bit::One, Bit::One]),
+            Value::from_bits(vec![Bit::Zero, Bit::Zero, Bit::Zero])
         );
     }
 }
@@ -6,4 +6,9 @@ mod signed_value;
 mod slice;
 mod value;
 
-pub use self::{bit::Bit, signed_value::SignedValue, slice::ValueSlice, value::Value};
+pub use self::{
+    bit::Bit,
+    signed_value::SignedValue,
+    slice::{ValueSlice, ValueSliceMut},
+    value::Value,
+};
@@ -67,21 +67,21 @@ mod tests {
     #[test]
     fn test_into_twos_complement_positive() {
         assert_eq!(
-            SignedValue::Positive(Value { bits: vec![Bit::One, Bit::One] })
+            SignedValue::Positive(Value::from_bits(vec![Bit::One, Bit::One]))
                 .into_twos_complement(2)
                 .unwrap(),
-            Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One])
         );
 
         assert_eq!(
-            SignedValue::Positive(Value { bits: vec![Bit::One, Bit::One] })
+            SignedValue::Positive(Value::from_bits(vec![Bit::One, Bit::One]))
                 .into_twos_complement(3)
                 .unwrap()
                 .size(),
             3
         );
 
-        assert!(SignedValue::Positive(Value { bits: vec![Bit::One, Bit::One, Bit::Zero] })
+        assert!(SignedValue::Positive(Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]))
             .into_twos_complement(2)
             .is_err());
     }
@@ -89,21 +89,21 @@ mod tests {
     #[test]
     fn test_into_twos_complement_negative() {
         assert_eq!(
-            SignedValue::Negative(Value { bits: vec![Bit::One, Bit::Zero] })
+            SignedValue::Negative(Value::from_bits(vec![Bit::One, Bit::Zero]))
                 .into_twos_complement(2)
                 .unwrap(),
-            Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One])
         );
 
         assert_eq!(
-            SignedValue::Negative(Value { bits: vec![Bit::One, Bit::One] })
+            SignedValue::Negative(Value::from_bits(vec![Bit::One, Bit::One]))
                 .into_twos_complement(3)
                 .unwrap()
                 .size(),
             3
         );
 
-        assert!(SignedValue::Negative(Value { bits: vec![Bit::One, Bit::One, Bit::Zero] })
+        assert!(SignedValue::Negative(Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero]))
             .into_twos_complement(2)
             .is_err());
     }
@@ -114,7 +114,7 @@ mod tests {
         assert!(matches!(parsed, SignedValue::Negative(..)));
         assert_eq!(
             parsed.into_twos_complement(2).unwrap(),
-            Value { bits: vec![Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::One])
         );
 
         assert!(matches!(SignedValue::parse_bin("01010").unwrap(), SignedValue::Positive(..)));
@@ -130,7 +130,7 @@ mod tests {
         assert!(matches!(parsed, SignedValue::Negative(..)));
         assert_eq!(
             parsed.into_twos_complement(4).unwrap(),
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
         );
 
         assert!(matches!(SignedValue::parse_dec("379856").unwrap(), SignedValue::Positive(..)));
@@ -146,22 +146,20 @@ mod tests {
         assert!(matches!(parsed, SignedValue::Negative(..)));
         assert_eq!(
             parsed.into_twos_complement(12).unwrap(),
-            Value {
-                bits: vec![
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One
+            ])
         );
 
         assert!(matches!(SignedValue::parse_hex("0A7e").unwrap(), SignedValue::Positive(..)));
@@ -174,7 +172,7 @@ mod tests {
     #[test]
     fn test_from_value() {
         assert!(matches!(
-            SignedValue::from(Value { bits: vec![Bit::One, Bit::Zero] }),
+            SignedValue::from(Value::from_bits(vec![Bit::One, Bit::Zero])),
             SignedValue::Positive(..)
         ));
     }
@@ -1,26 +1,56 @@
 use super::{Bit, Value};
-use std::borrow::ToOwned;
+use std::cmp;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::{Bound, RangeBounds};
 
-#[derive(Debug, Eq)]
-#[repr(C)]
-pub struct ValueSlice {
-    pub(crate) bits: [Bit],
+/// Number of bits per limb.
+pub(crate) const LIMB_BITS: usize = 64;
+
+/// A borrowed range of bits.
+///
+/// `limbs` holds the bits of the range, the first bit is bit `head` of the first limb. This way
+/// arbitrary bit ranges of a [`Value`] can be borrowed (see [`Value::slice`]).
+#[derive(Clone, Copy)]
+pub struct ValueSlice<'a> {
+    limbs: &'a [u64],
+    head: usize,
+    size: usize,
+}
+
+/// A mutably borrowed range of bits, see [`ValueSlice`].
+pub struct ValueSliceMut<'a> {
+    limbs: &'a mut [u64],
+    head: usize,
+    size: usize,
 }
 
-impl ValueSlice {
+impl<'a> ValueSlice<'a> {
     pub fn size(&self) -> usize {
-        self.bits.len()
+        self.size
     }
 
-    pub fn write(&mut self, value: &ValueSlice) {
-        for (idx, bit) in self.bits.iter_mut().enumerate() {
-            *bit = value.bits.get(idx).copied().unwrap_or_default();
+    /// Returns the bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(self, range: impl RangeBounds<usize>) -> ValueSlice<'a> {
+        let (start, end) = bounds(range, self.size);
+        let (limbs, head) = sub_limbs(self.head, start, end);
+        ValueSlice { limbs: &self.limbs[limbs], head, size: end - start }
+    }
+
+    pub fn to_value(self) -> Value {
+        let mut value = Value::zeroed(self.size);
+        for (idx, limb) in value.limbs_mut().iter_mut().enumerate() {
+            *limb = self.word(idx);
         }
+        value
     }
 
     pub fn is_zero(&self) -> bool {
-        self.bits.iter().all(|b| *b == Bit::Zero)
+        (0..self.word_count()).all(|idx| self.word(idx) == 0)
     }
 
     pub fn as_bin(&self, with_leading_zeros: bool) -> String {
@@ -28,122 +58,317 @@ impl ValueSlice {
             return "0".to_string();
         }
 
-        let mut result = String::new();
+        let len = if with_leading_zeros { self.size } else { self.bit_len() };
+        (0..len).rev().map(|idx| if self.bit(idx) { '1' } else { '0' }).collect()
+    }
+
+    pub fn as_dec(&self) -> String {
+        if self.size <= LIMB_BITS {
+            return self.word(0).to_string();
+        }
 
-        for &b in self.bits.iter().rev().skip_while(|&&b| b == Bit::Zero && !with_leading_zeros) {
-            match b {
-                Bit::Zero => result.push('0'),
-                Bit::One => result.push('1'),
+        // Divide by the largest power of ten that fits into a limb, 19 digits at a time
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut limbs = (0..self.word_count()).map(|idx| self.word(idx)).collect::<Vec<_>>();
+        let mut chunks = Vec::new();
+        while limbs.iter().any(|limb| *limb != 0) {
+            let mut rem = 0u128;
+            for limb in limbs.iter_mut().rev() {
+                let cur = (rem << LIMB_BITS) | u128::from(*limb);
+                *limb = (cur / u128::from(CHUNK)) as u64;
+                rem = cur % u128::from(CHUNK);
             }
+            chunks.push(rem as u64);
         }
 
+        let mut result = match chunks.pop() {
+            Some(chunk) => chunk.to_string(),
+            None => return "0".to_string(),
+        };
+        for chunk in chunks.iter().rev() {
+            result.push_str(&format!("{:019}", chunk));
+        }
         result
     }
 
-    pub fn as_dec(&self) -> String {
-        self.as_radix(10)
+    pub fn as_hex(&self) -> String {
+        if self.size <= LIMB_BITS {
+            return format!("{:X}", self.word(0));
+        }
+
+        let len = cmp::max(1, (self.bit_len() + 3) / 4);
+        (0..len)
+            .rev()
+            .map(|idx| {
+                let start = idx * 4;
+                let digit = self.load(start, cmp::min(4, self.size - start));
+                char::from_digit(digit as u32, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect()
     }
 
-    pub fn as_hex(&self) -> String {
-        self.as_radix(16)
+    /// Returns a copy of the view, so that values and slices can be used alike.
+    pub fn as_slice(&self) -> ValueSlice<'a> {
+        *self
+    }
+
+    // ------------------------------------------------------------
+    // Internal
+    // ------------------------------------------------------------
+
+    /// Loads `count` (at most 64) bits starting at bit `start`.
+    pub(crate) fn load(&self, start: usize, count: usize) -> u64 {
+        debug_assert!(count <= LIMB_BITS && start + count <= self.size);
+        load(self.limbs, self.head + start, count)
+    }
+
+    /// Number of words needed to hold all bits.
+    pub(crate) fn word_count(&self) -> usize {
+        word_count(self.size)
+    }
+
+    /// Returns the `idx`-th word (64 bits) of the slice. Words out of range are zero.
+    pub(crate) fn word(&self, idx: usize) -> u64 {
+        let start = idx * LIMB_BITS;
+        if start >= self.size {
+            return 0;
+        }
+        self.load(start, cmp::min(LIMB_BITS, self.size - start))
+    }
+
+    pub(crate) fn bit(&self, idx: usize) -> bool {
+        self.load(idx, 1) == 1
+    }
+
+    /// Number of bits without leading zeros.
+    pub(crate) fn bit_len(&self) -> usize {
+        (0..self.word_count())
+            .rev()
+            .find_map(|idx| {
+                let word = self.word(idx);
+                (word != 0).then(|| idx * LIMB_BITS + LIMB_BITS - word.leading_zeros() as usize)
+            })
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn bits(&self) -> Vec<Bit> {
+        (0..self.size).map(|idx| Bit::from(self.bit(idx))).collect()
+    }
+}
+
+impl<'a> ValueSliceMut<'a> {
+    pub fn size(&self) -> usize {
+        self.size
     }
 
+    pub fn as_slice(&self) -> ValueSlice<'_> {
+        ValueSlice { limbs: self.limbs, head: self.head, size: self.size }
+    }
+
+    /// Returns the bits in `range`.
+    ///
     /// # Panics
     ///
-    /// Panics if given a radix larger than 36.
-    fn as_radix(&self, radix: u32) -> String {
-        if self.is_zero() {
-            return "0".to_string();
-        }
+    /// Panics if `range` is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> ValueSliceMut<'_> {
+        ValueSliceMut { limbs: self.limbs, head: self.head, size: self.size }.into_slice_mut(range)
+    }
 
-        let mut value = self.to_owned();
-        let mut result = Vec::new();
-
-        while !value.is_zero() {
-            let mut r = 0;
-            let mut value_rest = Vec::new();
-
-            for &b in value.bits.iter().rev() {
-                r = 2 * r + u32::from(b);
-                if r >= radix {
-                    value_rest.push(Bit::One);
-                    r -= radix;
-                } else {
-                    value_rest.push(Bit::Zero);
-                }
-            }
+    /// Like [`ValueSliceMut::slice_mut`], but keeps the lifetime of the borrowed bits.
+    pub fn into_slice_mut(self, range: impl RangeBounds<usize>) -> ValueSliceMut<'a> {
+        let (start, end) = bounds(range, self.size);
+        let (limbs, head) = sub_limbs(self.head, start, end);
+        ValueSliceMut { limbs: &mut self.limbs[limbs], head, size: end - start }
+    }
 
-            value = Value { bits: value_rest.into_iter().rev().collect() };
-            value.remove_leading_zeros();
-            result.push(char::from_digit(r, radix).unwrap().to_ascii_uppercase());
+    /// Overwrites all bits with `value`. Missing bits are zero, additional bits are ignored.
+    pub fn write<'b>(&mut self, value: impl Into<ValueSlice<'b>>) {
+        let value = value.into();
+        let size = self.size;
+        for idx in 0..word_count(size) {
+            let start = idx * LIMB_BITS;
+            self.store(start, cmp::min(LIMB_BITS, size - start), value.word(idx));
         }
+    }
+
+    // ------------------------------------------------------------
+    // Internal
+    // ------------------------------------------------------------
+
+    /// Stores the lower `count` (at most 64) bits of `word` starting at bit `start`.
+    pub(crate) fn store(&mut self, start: usize, count: usize, word: u64) {
+        debug_assert!(count <= LIMB_BITS && start + count <= self.size);
+        store(self.limbs, self.head + start, count, word);
+    }
 
-        result.into_iter().rev().collect()
+    /// Sets all bits to one.
+    pub(crate) fn fill(&mut self) {
+        let size = self.size;
+        for idx in 0..word_count(size) {
+            let start = idx * LIMB_BITS;
+            self.store(start, cmp::min(LIMB_BITS, size - start), u64::MAX);
+        }
     }
 }
 
-impl ToOwned for ValueSlice {
-    type Owned = Value;
+impl<'a> From<&'a Value> for ValueSlice<'a> {
+    fn from(value: &'a Value) -> Self {
+        value.as_slice()
+    }
+}
 
-    fn to_owned(&self) -> Self::Owned {
-        Value { bits: self.bits.into() }
+impl<'a> From<&'a ValueSliceMut<'_>> for ValueSlice<'a> {
+    fn from(value: &'a ValueSliceMut<'_>) -> Self {
+        value.as_slice()
     }
 }
 
-impl Hash for ValueSlice {
+impl Eq for ValueSlice<'_> {}
+
+impl Hash for ValueSlice<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // Remove leading zeros
-        let mut len = self.bits.len();
-        while len > 1 {
-            if self.bits[len - 1] == Bit::Zero {
-                len -= 1;
-            } else {
-                break;
-            }
+        for idx in 0..word_count(self.bit_len()) {
+            self.word(idx).hash(state);
         }
+    }
+}
 
-        self.bits[0..len].hash(state);
+impl fmt::Debug for ValueSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueSlice").field("bits", &self.bits()).finish()
     }
 }
 
+impl fmt::Debug for ValueSliceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueSliceMut").field("bits", &self.as_slice().bits()).finish()
+    }
+}
+
+/// Creates a view of all bits of `limbs`.
+pub(crate) fn slice(limbs: &[u64], size: usize) -> ValueSlice<'_> {
+    ValueSlice { limbs, head: 0, size }
+}
+
+/// Creates a mutable view of all bits of `limbs`.
+pub(crate) fn slice_mut(limbs: &mut [u64], size: usize) -> ValueSliceMut<'_> {
+    ValueSliceMut { limbs, head: 0, size }
+}
+
+/// Mask of the lower `count` bits.
+pub(crate) fn mask(count: usize) -> u64 {
+    if count >= LIMB_BITS {
+        u64::MAX
+    } else {
+        (1 << count) - 1
+    }
+}
+
+/// Number of limbs needed to hold `size` bits.
+pub(crate) fn word_count(size: usize) -> usize {
+    (size + LIMB_BITS - 1) / LIMB_BITS
+}
+
+/// Loads `count` (at most 64) bits starting at bit `bit` of `limbs`.
+fn load(limbs: &[u64], bit: usize, count: usize) -> u64 {
+    if count == 0 {
+        return 0;
+    }
+
+    let (idx, offset) = (bit / LIMB_BITS, bit % LIMB_BITS);
+    let mut word = limbs[idx] >> offset;
+    if offset + count > LIMB_BITS {
+        word |= limbs[idx + 1] << (LIMB_BITS - offset);
+    }
+    word & mask(count)
+}
+
+/// Stores the lower `count` (at most 64) bits of `word` starting at bit `bit` of `limbs`.
+fn store(limbs: &mut [u64], bit: usize, count: usize, word: u64) {
+    if count == 0 {
+        return;
+    }
+
+    let (idx, offset) = (bit / LIMB_BITS, bit % LIMB_BITS);
+    let word = word & mask(count);
+    let low_count = cmp::min(count, LIMB_BITS - offset);
+    let low_mask = mask(low_count) << offset;
+    limbs[idx] = (limbs[idx] & !low_mask) | ((word << offset) & low_mask);
+    if low_count < count {
+        let high_mask = mask(count - low_count);
+        limbs[idx + 1] = (limbs[idx + 1] & !high_mask) | (word >> low_count);
+    }
+}
+
+/// The limbs and the head of the bits `start..end` of a slice starting at bit `head`.
+fn sub_limbs(head: usize, start: usize, end: usize) -> (std::ops::Range<usize>, usize) {
+    let bit = head + start;
+    let first = bit / LIMB_BITS;
+    let head = bit % LIMB_BITS;
+    let count = if end == start { 0 } else { word_count(head + end - start) };
+    (first..first + count, head)
+}
+
+/// Resolves `range` to `(start, end)`. Panics if out of bounds.
+fn bounds(range: impl RangeBounds<usize>, size: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end + 1,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => size,
+    };
+
+    if start > end {
+        panic!("slice index starts at {} but ends at {}", start, end);
+    }
+    if end > size {
+        panic!("range end index {} out of range for slice of length {}", end, size);
+    }
+    (start, end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_as_bin() {
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::Zero,] }.as_bin(false), "0".to_string());
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::Zero,] }.as_bin(true), "00".to_string());
-        assert_eq!(Value { bits: vec![Bit::One, Bit::Zero,] }.as_bin(false), "1".to_string());
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::One,] }.as_bin(false), "10".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::Zero,]).as_bin(false), "0".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::Zero,]).as_bin(true), "00".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::One, Bit::Zero,]).as_bin(false), "1".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::One,]).as_bin(false), "10".to_string());
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero] }.as_bin(false),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero])
+                .as_bin(false),
             "1011".to_string()
         );
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero] }.as_bin(true),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::Zero, Bit::One, Bit::Zero]).as_bin(true),
             "01011".to_string()
         );
 
         assert_eq!(
-            Value {
-                bits: vec![
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::Zero,
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+            ])
             .as_bin(false),
             "11000101110".to_string()
         );
@@ -151,25 +376,23 @@ mod tests {
 
     #[test]
     fn test_as_dec() {
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::Zero,] }.as_dec(), "0".to_string());
-        assert_eq!(Value { bits: vec![Bit::One, Bit::Zero,] }.as_dec(), "1".to_string());
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::One,] }.as_dec(), "2".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::Zero,]).as_dec(), "0".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::One, Bit::Zero,]).as_dec(), "1".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::One,]).as_dec(), "2".to_string());
 
         assert_eq!(
-            Value {
-                bits: vec![
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::Zero,
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+            ])
             .as_dec(),
             "791".to_string()
         );
@@ -177,30 +400,28 @@ mod tests {
 
     #[test]
     fn test_as_hex() {
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::Zero,] }.as_hex(), "0".to_string());
-        assert_eq!(Value { bits: vec![Bit::One, Bit::Zero,] }.as_hex(), "1".to_string());
-        assert_eq!(Value { bits: vec![Bit::Zero, Bit::One,] }.as_hex(), "2".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::Zero,]).as_hex(), "0".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::One, Bit::Zero,]).as_hex(), "1".to_string());
+        assert_eq!(Value::from_bits(vec![Bit::Zero, Bit::One,]).as_hex(), "2".to_string());
         assert_eq!(
-            Value { bits: vec![Bit::One, Bit::One, Bit::One, Bit::One,] }.as_hex(),
+            Value::from_bits(vec![Bit::One, Bit::One, Bit::One, Bit::One,]).as_hex(),
             "F".to_string()
         );
 
         assert_eq!(
-            Value {
-                bits: vec![
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::Zero,
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+            ])
             .as_hex(),
             "62E".to_string()
         );
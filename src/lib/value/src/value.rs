@@ -1,60 +1,69 @@
-use super::{Bit, ValueSlice};
-use std::borrow::{Borrow, BorrowMut};
+use super::{
+    slice::{self, mask, word_count, LIMB_BITS},
+    Bit, ValueSlice, ValueSliceMut,
+};
+use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
+use std::ops::RangeBounds;
 
-#[derive(Debug, Clone, Eq)]
+/// A value of arbitrary size.
+///
+/// The bits are packed into `u64` limbs (least significant limb first). Values with at most 64
+/// bits are stored inline without a heap allocation. Bits above `size` are always zero.
+#[derive(Clone)]
 pub struct Value {
-    pub(crate) bits: Vec<Bit>,
+    limbs: Limbs,
+    size: usize,
 }
 
 impl Value {
     pub fn zero(size: usize) -> Self {
         assert!(size != 0);
-        Self { bits: (0..size).map(|_| Bit::Zero).collect() }
+        Self::zeroed(size)
     }
 
     pub fn one(size: usize) -> Self {
         assert!(size != 0);
-        Self { bits: (0..size).map(|i| if i == 0 { Bit::One } else { Bit::Zero }).collect() }
+        let mut value = Self::zeroed(size);
+        value.limbs.as_mut_slice()[0] = 1;
+        value
     }
 
     pub fn filled(size: usize) -> Self {
         assert!(size != 0);
-        Self { bits: (0..size).map(|_| Bit::One).collect() }
+        let mut value = Self::zeroed(size);
+        value.limbs.as_mut_slice().iter_mut().for_each(|limb| *limb = u64::MAX);
+        value.normalize();
+        value
     }
 
     pub fn remove_leading_zeros(&mut self) {
-        while self.bits.len() > 1 {
-            if self.bits.last() == Some(&Bit::Zero) {
-                self.bits.pop().unwrap();
-            } else {
-                break;
-            }
+        if self.size > 1 {
+            self.resize(self.as_slice().bit_len().max(1));
         }
     }
 
     pub fn extend_zero(&mut self, size: usize) {
-        while size > self.bits.len() {
-            self.bits.push(Bit::Zero);
+        if size > self.size {
+            self.resize(size);
         }
     }
 
     pub fn extend_sign(&mut self, size: usize) {
-        let sign = *self.bits.last().unwrap();
-        while size > self.bits.len() {
-            self.bits.push(sign);
+        assert!(self.size != 0);
+        let sign = self.as_slice().bit(self.size - 1);
+        let old_size = self.size;
+        if size > old_size {
+            self.resize(size);
+            if sign {
+                self.slice_mut(old_size..).fill();
+            }
         }
     }
 
     pub fn with_size(mut self, size: usize) -> Self {
         assert!(size != 0);
-        while self.bits.len() < size {
-            self.bits.push(Bit::Zero);
-        }
-        while self.bits.len() > size {
-            self.bits.pop();
-        }
+        self.resize(size);
         self
     }
 
@@ -68,17 +77,14 @@ impl Value {
             return Err(());
         }
 
-        let value = Self {
-            bits: bin
-                .chars()
-                .rev()
-                .map(|c| match c {
-                    '0' => Ok(Bit::Zero),
-                    '1' => Ok(Bit::One),
-                    _ => Err(()),
-                })
-                .collect::<Result<_, _>>()?,
-        };
+        let mut value = Self::zeroed(bin.len());
+        for (idx, c) in bin.bytes().rev().enumerate() {
+            match c {
+                b'0' => (),
+                b'1' => value.limbs.as_mut_slice()[idx / LIMB_BITS] |= 1 << (idx % LIMB_BITS),
+                _ => return Err(()),
+            }
+        }
 
         Ok(value)
     }
@@ -93,28 +99,23 @@ impl Value {
             return Err(());
         }
 
-        let mut bits = Vec::new();
-
-        let mut dec =
-            dec.chars().map(|c| c.to_digit(10).ok_or(())).collect::<Result<Vec<_>, _>>()?;
-        let mut dec = dec.as_mut_slice();
-
-        while !dec.is_empty() {
-            bits.push((dec.last().unwrap() % 2 == 1).into());
+        let mut limbs = vec![0u64];
+        for c in dec.chars() {
+            let digit = c.to_digit(10).ok_or(())?;
 
-            let mut additive = 0;
-            for i in 0..dec.len() {
-                let next_addive = if dec[i] % 2 == 1 { 5 } else { 0 };
-                dec[i] = dec[i] / 2 + additive;
-                additive = next_addive;
+            // limbs = limbs * 10 + digit
+            let mut carry = u128::from(digit);
+            for limb in &mut limbs {
+                let res = u128::from(*limb) * 10 + carry;
+                *limb = res as u64;
+                carry = res >> LIMB_BITS;
             }
-
-            while !dec.is_empty() && dec[0] == 0 {
-                dec = &mut dec[1..];
+            if carry != 0 {
+                limbs.push(carry as u64);
             }
         }
 
-        let mut value = Self { bits };
+        let mut value = Self::from_limbs(limbs);
         value.remove_leading_zeros();
         Ok(value)
     }
@@ -129,91 +130,231 @@ impl Value {
             return Err(());
         }
 
-        let mut bits = Vec::new();
-
-        for hex in hex.chars().rev() {
-            let val = hex.to_digit(16).ok_or(())?;
-            bits.push((val & 0b0001 != 0).into());
-            bits.push((val & 0b0010 != 0).into());
-            bits.push((val & 0b0100 != 0).into());
-            bits.push((val & 0b1000 != 0).into());
+        let mut value = Self::zeroed(hex.chars().count() * 4);
+        for (idx, c) in hex.chars().rev().enumerate() {
+            let digit = u64::from(c.to_digit(16).ok_or(())?);
+            let bit = idx * 4;
+            value.limbs.as_mut_slice()[bit / LIMB_BITS] |= digit << (bit % LIMB_BITS);
         }
 
-        let mut value = Self { bits };
         value.remove_leading_zeros();
         Ok(value)
     }
 
+    /// Creates a value of `size` bits from the lower `size` bits of `value`.
+    pub fn from_u64(value: u64, size: usize) -> Self {
+        assert!(size != 0);
+        let mut result = Self::zeroed(size);
+        result.limbs_mut()[0] = value;
+        result.normalize();
+        result
+    }
+
+    /// Returns the value as `u64`, `None` if it does not fit into 64 bits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [first, rest @ ..] => rest.iter().all(|limb| *limb == 0).then(|| *first),
+        }
+    }
+
     pub fn concat<'a, I>(slices: I) -> Self
     where
-        I: IntoIterator<Item = &'a ValueSlice>,
+        I: IntoIterator<Item = ValueSlice<'a>>,
         I::IntoIter: DoubleEndedIterator,
     {
-        let mut bits = Vec::new();
+        let slices = slices.into_iter().rev().collect::<Vec<_>>();
 
-        for slice in slices.into_iter().rev() {
-            bits.extend_from_slice(&slice.bits);
+        let mut value = Self::zeroed(slices.iter().map(|slice| slice.size()).sum());
+        let mut start = 0;
+        for slice in slices {
+            value.slice_mut(start..start + slice.size()).write(slice);
+            start += slice.size();
         }
 
-        Self { bits }
+        value
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.as_slice().is_zero()
+    }
+
+    pub fn as_bin(&self, with_leading_zeros: bool) -> String {
+        self.as_slice().as_bin(with_leading_zeros)
+    }
+
+    pub fn as_dec(&self) -> String {
+        self.as_slice().as_dec()
     }
 
-    pub fn as_slice(&self) -> &ValueSlice {
-        &**self
+    pub fn as_hex(&self) -> String {
+        self.as_slice().as_hex()
     }
 
-    pub fn as_mut_slice(&mut self) -> &mut ValueSlice {
-        &mut **self
+    /// Overwrites all bits with `value`, see [`ValueSliceMut::write`].
+    pub fn write<'a>(&mut self, value: impl Into<ValueSlice<'a>>) {
+        self.as_mut_slice().write(value);
+    }
+
+    pub fn as_slice(&self) -> ValueSlice<'_> {
+        slice::slice(&self.limbs.as_slice()[..word_count(self.size)], self.size)
+    }
+
+    pub fn as_mut_slice(&mut self) -> ValueSliceMut<'_> {
+        let size = self.size;
+        slice::slice_mut(self.limbs_mut(), size)
+    }
+
+    /// Returns the bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> ValueSlice<'_> {
+        self.as_slice().slice(range)
+    }
+
+    /// Returns the bits in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> ValueSliceMut<'_> {
+        let size = self.size;
+        slice::slice_mut(self.limbs_mut(), size).into_slice_mut(range)
+    }
+
+    // ------------------------------------------------------------
+    // Internal
+    // ------------------------------------------------------------
+
+    /// Creates a zero value. Unlike [`Value::zero`], `size` may be zero.
+    pub(crate) fn zeroed(size: usize) -> Self {
+        Self { limbs: Limbs::zeroed(word_count(size)), size }
+    }
+
+    /// Creates a value from a single word. `size` must be at most 64.
+    pub(crate) fn from_word(word: u64, size: usize) -> Self {
+        debug_assert!(size <= LIMB_BITS);
+        Self { limbs: Limbs::Inline(word & mask(size)), size }
+    }
+
+    /// Creates a value with one bit per limb bit.
+    fn from_limbs(limbs: Vec<u64>) -> Self {
+        let size = limbs.len() * LIMB_BITS;
+        Self { limbs: Limbs::from_vec(limbs), size }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_bits(bits: Vec<Bit>) -> Self {
+        let mut value = Self::zeroed(bits.len());
+        for (idx, bit) in bits.into_iter().enumerate() {
+            value.limbs.as_mut_slice()[idx / LIMB_BITS] |= u64::from(bit) << (idx % LIMB_BITS);
+        }
+        value
+    }
+
+    pub(crate) fn limbs_mut(&mut self) -> &mut [u64] {
+        &mut self.limbs.as_mut_slice()[..word_count(self.size)]
+    }
+
+    /// Clears all bits above `size`.
+    pub(crate) fn normalize(&mut self) {
+        let size = self.size;
+        if let Some(last) = self.limbs_mut().last_mut() {
+            let rest = size % LIMB_BITS;
+            if rest != 0 {
+                *last &= mask(rest);
+            }
+        }
+    }
+
+    fn resize(&mut self, size: usize) {
+        self.limbs.resize(word_count(size));
+        self.size = size;
+        self.normalize();
     }
 }
 
 impl From<Bit> for Value {
     fn from(bit: Bit) -> Self {
-        Self { bits: vec![bit] }
+        Self::from_word(u64::from(bit), 1)
     }
 }
 
-impl AsRef<ValueSlice> for Value {
-    fn as_ref(&self) -> &ValueSlice {
-        unsafe { &*(self.bits.as_slice() as *const [Bit] as *const ValueSlice) }
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
     }
 }
 
-impl AsMut<ValueSlice> for Value {
-    fn as_mut(&mut self) -> &mut ValueSlice {
-        unsafe { &mut *(self.bits.as_mut_slice() as *mut [Bit] as *mut ValueSlice) }
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Value").field("bits", &self.as_slice().bits()).finish()
     }
 }
 
-impl Deref for Value {
-    type Target = ValueSlice;
+/// Limb storage with a small size optimization.
+#[derive(Debug, Clone)]
+enum Limbs {
+    Inline(u64),
+    Heap(Vec<u64>),
+}
 
-    fn deref(&self) -> &Self::Target {
-        self.as_ref()
+impl Limbs {
+    fn zeroed(count: usize) -> Self {
+        if count <= 1 {
+            Self::Inline(0)
+        } else {
+            Self::Heap(vec![0; count])
+        }
     }
-}
 
-impl DerefMut for Value {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.as_mut()
+    fn from_vec(limbs: Vec<u64>) -> Self {
+        if limbs.len() <= 1 {
+            Self::Inline(limbs.first().copied().unwrap_or(0))
+        } else {
+            Self::Heap(limbs)
+        }
     }
-}
 
-impl Borrow<ValueSlice> for Value {
-    fn borrow(&self) -> &ValueSlice {
-        &*self
+    fn as_slice(&self) -> &[u64] {
+        match self {
+            Self::Inline(limb) => std::slice::from_ref(limb),
+            Self::Heap(limbs) => limbs,
+        }
     }
-}
 
-impl BorrowMut<ValueSlice> for Value {
-    fn borrow_mut(&mut self) -> &mut ValueSlice {
-        &mut *self
+    fn as_mut_slice(&mut self) -> &mut [u64] {
+        match self {
+            Self::Inline(limb) => std::slice::from_mut(limb),
+            Self::Heap(limbs) => limbs,
+        }
     }
-}
 
-impl Hash for Value {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.as_slice().hash(state);
+    fn resize(&mut self, count: usize) {
+        match self {
+            Self::Inline(limb) => {
+                if count > 1 {
+                    let mut limbs = vec![0; count];
+                    limbs[0] = *limb;
+                    *self = Self::Heap(limbs);
+                }
+            }
+            Self::Heap(limbs) => {
+                if count <= 1 {
+                    *self = Self::Inline(limbs[0]);
+                } else {
+                    limbs.resize(count, 0);
+                }
+            }
+        }
     }
 }
 
@@ -225,12 +366,12 @@ mod tests {
     fn test_parse_bin() {
         assert_eq!(
             Value::parse_bin("1101").unwrap(),
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::One] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::One])
         );
 
         assert_eq!(
             Value::parse_bin("000101").unwrap(),
-            Value { bits: vec![Bit::One, Bit::Zero, Bit::One, Bit::Zero, Bit::Zero, Bit::Zero,] }
+            Value::from_bits(vec![Bit::One, Bit::Zero, Bit::One, Bit::Zero, Bit::Zero, Bit::Zero,])
         );
 
         assert_eq!(Value::parse_bin("01").unwrap().size(), 2);
@@ -247,20 +388,18 @@ mod tests {
     fn test_parse_dec() {
         assert_eq!(
             Value::parse_dec("791").unwrap(),
-            Value {
-                bits: vec![
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::Zero,
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+            ])
         );
 
         assert_eq!(Value::parse_dec("7").unwrap().size(), 3);
@@ -277,26 +416,24 @@ mod tests {
     fn test_parse_hex() {
         assert_eq!(
             Value::parse_hex("ffA1").unwrap(),
-            Value {
-                bits: vec![
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::Zero,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                    Bit::One,
-                ]
-            }
+            Value::from_bits(vec![
+                Bit::One,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::Zero,
+                Bit::One,
+                Bit::Zero,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+                Bit::One,
+            ])
         );
 
         assert_eq!(Value::parse_hex("0").unwrap().size(), 1);
@@ -0,0 +1,169 @@
+mod reference;
+
+use proptest::prelude::*;
+use reference::Reference;
+use value::{Bit, Value};
+
+fn bin() -> impl Strategy<Value = String> {
+    "[01]{1,200}"
+}
+
+fn parse(bin: &str) -> (Value, Reference) {
+    (Value::parse_bin(bin).unwrap(), Reference::parse_bin(bin))
+}
+
+fn assert_same(value: &Value, reference: &Reference) -> Result<(), TestCaseError> {
+    prop_assert_eq!(value.size(), reference.size());
+    prop_assert_eq!(value.as_bin(true), reference.as_bin(true));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn parse_bin(bin in bin()) {
+        let (value, reference) = parse(&bin);
+        assert_same(&value, &reference)?;
+        prop_assert_eq!(value.as_bin(false), reference.as_bin(false));
+    }
+
+    #[test]
+    fn parse_dec(dec in "[0-9]{1,80}") {
+        assert_same(&Value::parse_dec(&dec).unwrap(), &Reference::parse_dec(&dec))?;
+    }
+
+    #[test]
+    fn parse_hex(hex in "[0-9a-fA-F]{1,50}") {
+        let bin = hex
+            .chars()
+            .map(|c| format!("{:04b}", c.to_digit(16).unwrap()))
+            .collect::<String>();
+        let mut reference = Reference::parse_bin(&bin);
+        reference.remove_leading_zeros();
+        assert_same(&Value::parse_hex(&hex).unwrap(), &reference)?;
+    }
+
+    #[test]
+    fn as_dec_hex(bin in bin()) {
+        let (value, reference) = parse(&bin);
+        prop_assert_eq!(value.as_dec(), reference.as_dec());
+        prop_assert_eq!(value.as_hex(), reference.as_hex());
+        prop_assert_eq!(Value::parse_dec(&value.as_dec()).unwrap(), value.clone());
+        prop_assert_eq!(Value::parse_hex(&value.as_hex()).unwrap(), value);
+    }
+
+    #[test]
+    fn binary_ops(lhs in bin(), rhs in bin()) {
+        let (lhs, lhs_ref) = parse(&lhs);
+        let (rhs, rhs_ref) = parse(&rhs);
+
+        assert_same(&(&lhs + &rhs), &lhs_ref.add(&rhs_ref))?;
+        assert_same(&(&lhs - &rhs), &lhs_ref.sub(&rhs_ref))?;
+        assert_same(&(&lhs * &rhs), &lhs_ref.mul(&rhs_ref))?;
+        assert_same(&(&lhs & &rhs), &lhs_ref.zip(&rhs_ref, |a, b| a & b))?;
+        assert_same(&(&lhs | &rhs), &lhs_ref.zip(&rhs_ref, |a, b| a | b))?;
+        assert_same(&(&lhs ^ &rhs), &lhs_ref.zip(&rhs_ref, |a, b| a ^ b))?;
+
+        prop_assert_eq!(lhs.cmp(&rhs), lhs_ref.cmp(&rhs_ref));
+        prop_assert_eq!(lhs == rhs, lhs_ref.cmp(&rhs_ref).is_eq());
+    }
+
+    #[test]
+    fn unary_ops(bin in bin(), shift in 0usize..220) {
+        let (value, reference) = parse(&bin);
+
+        assert_same(&!&value, &reference.not())?;
+        assert_same(&-&value, &reference.neg())?;
+        assert_same(&(&value << shift), &reference.shl(shift))?;
+        assert_same(&(&value >> shift), &reference.shr(shift))?;
+    }
+
+    #[test]
+    fn resize(bin in bin(), size in 1usize..260) {
+        let (value, reference) = parse(&bin);
+
+        let (mut a, mut b) = (value.clone(), reference.clone());
+        a.extend_zero(size);
+        b.extend_zero(size);
+        assert_same(&a, &b)?;
+
+        let (mut a, mut b) = (value.clone(), reference.clone());
+        a.extend_sign(size);
+        b.extend_sign(size);
+        assert_same(&a, &b)?;
+
+        let (mut a, mut b) = (value.clone(), reference.clone());
+        a.remove_leading_zeros();
+        b.remove_leading_zeros();
+        assert_same(&a, &b)?;
+
+        assert_same(&value.with_size(size), &reference.with_size(size))?;
+    }
+
+    #[test]
+    fn u64(bin in bin(), word in any::<u64>(), size in 1usize..200) {
+        let (value, reference) = parse(&bin);
+        let bin = reference.as_bin(false);
+        let expected = (bin.len() <= 64).then(|| u64::from_str_radix(&bin, 2).unwrap());
+        prop_assert_eq!(value.to_u64(), expected);
+
+        let value = Value::from_u64(word, size);
+        let reference = Reference::parse_bin(&format!("{:b}", word)).with_size(size);
+        assert_same(&value, &reference)?;
+    }
+
+    #[test]
+    fn concat(bins in prop::collection::vec(bin(), 1..6)) {
+        let (values, references): (Vec<_>, Vec<_>) = bins.iter().map(|bin| parse(bin)).unzip();
+        let value = Value::concat(values.iter().map(|value| value.as_slice()));
+        assert_same(&value, &Reference::concat(&references))?;
+    }
+
+    #[test]
+    fn slice(bin in bin(), other in bin(), start in 0usize..200, len in 0usize..200) {
+        let (mut value, mut reference) = parse(&bin);
+        let (other, other_ref) = parse(&other);
+        let start = start.min(value.size());
+        let end = (start + len).min(value.size());
+
+        // Read
+        let slice = Reference { bits: reference.bits[start..end].to_vec() };
+        if start != end {
+            assert_same(&value.slice(start..end).to_value(), &slice)?;
+            assert_same(&(value.slice(start..end) + &other), &slice.add(&other_ref))?;
+        }
+        prop_assert_eq!(value.slice(start..end).is_zero(), slice.is_zero());
+
+        // Write
+        value.slice_mut(start..end).write(&other);
+        let written = other_ref.clone().with_size(end - start);
+        reference.bits[start..end].copy_from_slice(&written.bits);
+        assert_same(&value, &reference)?;
+    }
+
+    #[test]
+    fn nested_slice(bin in bin(), other in bin(), start in 0usize..200, inner in 0usize..200) {
+        let (mut value, mut reference) = parse(&bin);
+        let (other, other_ref) = parse(&other);
+        let start = start.min(value.size());
+        let inner = inner.min(value.size() - start);
+
+        // Read
+        let slice = Reference { bits: reference.bits[start + inner..].to_vec() };
+        if start + inner != value.size() {
+            assert_same(&value.slice(start..).slice(inner..).to_value(), &slice)?;
+        }
+
+        // Write
+        value.slice_mut(start..).slice_mut(inner..).write(&other);
+        let written = other_ref.clone().with_size(value.size() - start - inner);
+        reference.bits[start + inner..].copy_from_slice(&written.bits);
+        assert_same(&value, &reference)?;
+    }
+}
+
+#[test]
+fn bits() {
+    assert_eq!(Value::from(Bit::One), Value::one(1));
+    assert_eq!(Value::filled(130).as_bin(false), "1".repeat(130));
+    assert_eq!(Value::one(200) << 199, Value::parse_bin(&format!("1{}", "0".repeat(199))).unwrap());
+}
@@ -0,0 +1,182 @@
+//! The previous `Vec<Bit>` based implementation of `Value`, used as a reference model.
+
+use value::Bit;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub bits: Vec<Bit>,
+}
+
+impl Reference {
+    pub fn parse_bin(bin: &str) -> Self {
+        Self { bits: bin.chars().rev().map(|c| Bit::from(c == '1')).collect() }
+    }
+
+    pub fn parse_dec(dec: &str) -> Self {
+        let mut result = Self { bits: vec![Bit::Zero] };
+        for c in dec.chars() {
+            let digit = Self::parse_bin(&format!("{:b}", c.to_digit(10).unwrap()));
+            // result * 10 = result * 8 + result * 2
+            let wide = result.clone().with_size(result.size() + 4);
+            result = wide.shl(3).add(&wide.shl(1)).add(&digit);
+            result.remove_leading_zeros();
+        }
+        result
+    }
+
+    pub fn size(&self) -> usize {
+        self.bits.len()
+    }
+
+    fn get(&self, idx: usize) -> Bit {
+        self.bits.get(idx).copied().unwrap_or_default()
+    }
+
+    pub fn remove_leading_zeros(&mut self) {
+        while self.bits.len() > 1 && *self.bits.last().unwrap() == Bit::Zero {
+            self.bits.pop();
+        }
+    }
+
+    pub fn extend_zero(&mut self, size: usize) {
+        if size > self.bits.len() {
+            self.bits.resize(size, Bit::Zero);
+        }
+    }
+
+    pub fn extend_sign(&mut self, size: usize) {
+        let sign = *self.bits.last().unwrap();
+        if size > self.bits.len() {
+            self.bits.resize(size, sign);
+        }
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.bits.resize(size, Bit::Zero);
+        self
+    }
+
+    pub fn concat(refs: &[Reference]) -> Self {
+        Self { bits: refs.iter().rev().flat_map(|r| r.bits.iter().copied()).collect() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.bits.iter().all(|bit| *bit == Bit::Zero)
+    }
+
+    pub fn as_bin(&self, with_leading_zeros: bool) -> String {
+        let mut r = self.clone();
+        if !with_leading_zeros {
+            r.remove_leading_zeros();
+            if r.is_zero() {
+                return "0".to_string();
+            }
+        }
+        r.bits.iter().rev().map(|bit| if *bit == Bit::One { '1' } else { '0' }).collect()
+    }
+
+    pub fn as_dec(&self) -> String {
+        // Repeated division by 10 on the bit vector
+        let mut bits = self.bits.clone();
+        let mut digits = Vec::new();
+        loop {
+            let mut rem = 0u8;
+            for bit in bits.iter_mut().rev() {
+                let cur = rem * 2 + u8::from(*bit);
+                *bit = Bit::from(cur >= 10);
+                rem = cur % 10;
+            }
+            digits.push(char::from(b'0' + rem));
+            if bits.iter().all(|bit| *bit == Bit::Zero) {
+                break;
+            }
+        }
+        digits.iter().rev().collect()
+    }
+
+    pub fn as_hex(&self) -> String {
+        let mut r = self.clone();
+        r.remove_leading_zeros();
+        let digits = r
+            .bits
+            .chunks(4)
+            .map(|chunk| {
+                let digit = chunk.iter().rev().fold(0, |acc, bit| acc * 2 + u32::from(*bit));
+                char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect::<Vec<_>>();
+        digits.iter().rev().collect()
+    }
+
+    pub fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let len = std::cmp::max(self.size(), other.size());
+        for idx in (0..len).rev() {
+            let res = self.get(idx).cmp(&other.get(idx));
+            if res != std::cmp::Ordering::Equal {
+                return res;
+            }
+        }
+        std::cmp::Ordering::Equal
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        self.add_carry(other, false)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        // lhs - rhs = lhs + !rhs + 1
+        let len = std::cmp::max(self.size(), other.size());
+        self.add_carry(&other.clone().with_size(len).not(), true)
+    }
+
+    fn add_carry(&self, other: &Self, mut carry: bool) -> Self {
+        let len = std::cmp::max(self.size(), other.size());
+        let mut bits = Vec::with_capacity(len);
+        for idx in 0..len {
+            let (a, b) = (bool::from(self.get(idx)), bool::from(other.get(idx)));
+            bits.push(Bit::from(a ^ b ^ carry));
+            carry = (a && b) || (carry && (a ^ b));
+        }
+        Self { bits }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        // Shift and add
+        let len = std::cmp::max(self.size(), other.size());
+        let lhs = self.clone().with_size(len);
+        let mut result = Self { bits: vec![Bit::Zero; len] };
+        for (idx, bit) in other.bits.iter().enumerate() {
+            if *bit == Bit::One {
+                result = result.add(&lhs.shl(idx));
+            }
+        }
+        result
+    }
+
+    pub fn zip(&self, other: &Self, f: impl Fn(Bit, Bit) -> Bit) -> Self {
+        let len = std::cmp::max(self.size(), other.size());
+        Self { bits: (0..len).map(|idx| f(self.get(idx), other.get(idx))).collect() }
+    }
+
+    pub fn not(&self) -> Self {
+        Self { bits: self.bits.iter().map(|bit| !*bit).collect() }
+    }
+
+    pub fn neg(&self) -> Self {
+        self.not().add(&Self { bits: vec![Bit::One] })
+    }
+
+    pub fn shl(&self, rhs: usize) -> Self {
+        let size = self.size();
+        let mut bits = vec![Bit::Zero; rhs.min(size)];
+        bits.extend(&self.bits[..size - rhs.min(size)]);
+        Self { bits }
+    }
+
+    pub fn shr(&self, rhs: usize) -> Self {
+        let size = self.size();
+        let mut bits = self.bits[rhs.min(size)..].to_vec();
+        bits.resize(size, Bit::Zero);
+        Self { bits }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::{Error, ErrorKind};
+use crate::expr::{parse_expr, parse_expr_list, Expr};
+use crate::isa::{Field, Instruction, Isa};
+use crate::lexer::{lex, Cursor, TokenKind};
+use memory_file::{MemoryFile, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+
+struct Statement<'i, 's> {
+    address: i128,
+    kind: StatementKind<'i, 's>,
+    span: Range<usize>,
+}
+
+enum StatementKind<'i, 's> {
+    Instruction(&'i Instruction, Vec<Expr<'s>>),
+    Words(Vec<Expr<'s>>),
+}
+
+/// Assembles the source into the memory of the ISA.
+///
+/// Each line holds an optional label (`NAME:`) and an optional instruction or directive. Operands
+/// are expressions of numbers, labels and the current address (`.`). The directives are:
+///
+/// - `.org ADDRESS`: continues at the given address, labels of the line are bound to it
+/// - `.word VALUE, ...`: stores the values in consecutive words
+pub fn assemble(isa: &Isa, source: &str) -> Result<MemoryFile, Error> {
+    // Collect labels and the addresses of the statements
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+    let lines = lex(source)?;
+    for line in &lines {
+        let mut cursor = Cursor::new(line);
+
+        // Labels
+        let mut line_labels = Vec::new();
+        while cursor.peek().kind == TokenKind::Ident
+            && cursor.peek_nth(1).map_or(false, |token| token.text == ":")
+        {
+            line_labels.push(cursor.next());
+            cursor.next();
+        }
+
+        // The labels of a `.org` line are bound to the new address
+        let is_org = !cursor.at_end() && cursor.peek().text == ".org";
+        if is_org {
+            cursor.next();
+            let expr = parse_expr(&mut cursor)?;
+            cursor.expect_end()?;
+            address = expr.eval(&labels, address)?;
+        }
+
+        for label in line_labels {
+            if labels.insert(label.text, address).is_some() {
+                let kind = ErrorKind::DuplicateLabel(label.text.to_string());
+                return Err(Error::new(kind, label.span.clone()));
+            }
+        }
+        if is_org || cursor.at_end() {
+            continue;
+        }
+
+        // Instruction or directive
+        let start = cursor.peek().span.start;
+        let name = cursor.expect(TokenKind::Ident, "expected an instruction or directive")?;
+        match name.text {
+            ".word" => {
+                let values = parse_expr_list(&mut cursor)?;
+                if values.is_empty() {
+                    return Err(cursor.unexpected("expected at least one value"));
+                }
+                let words = values.len() as i128;
+                let span = cursor.span_from(start);
+                let next = next_address(address, words, &span)?;
+                statements.push(Statement { address, kind: StatementKind::Words(values), span });
+                address = next;
+            }
+            directive if directive.starts_with('.') => {
+                let kind = ErrorKind::UnknownDirective(directive.to_string());
+                return Err(Error::new(kind, name.span.clone()));
+            }
+            mnemonic => {
+                let instruction = isa.instruction(mnemonic).ok_or_else(|| {
+                    Error::new(ErrorKind::UnknownMnemonic(mnemonic.to_string()), name.span.clone())
+                })?;
+                let operands = parse_expr_list(&mut cursor)?;
+                let span = cursor.span_from(start);
+                if operands.len() != instruction.operands().len() {
+                    let kind = ErrorKind::OperandCount {
+                        expected: instruction.operands().len(),
+                        actual: operands.len(),
+                    };
+                    return Err(Error::new(kind, span));
+                }
+
+                let words = (instruction.width() / isa.word_size()) as i128;
+                let next = next_address(address, words, &span)?;
+                let kind = StatementKind::Instruction(instruction, operands);
+                statements.push(Statement { address, kind, span });
+                address = next;
+            }
+        }
+    }
+
+    // Encode statements
+    let mut data = HashMap::new();
+    for statement in statements {
+        let bits = match &statement.kind {
+            StatementKind::Instruction(instruction, operands) => {
+                let mut bits = String::new();
+                for field in instruction.layout() {
+                    match field {
+                        Field::Bits(b) => bits += b,
+                        Field::Operand { index, width } => {
+                            let operand = &operands[*index];
+                            bits += &encode(operand, &labels, statement.address, *width)?;
+                        }
+                    }
+                }
+                bits
+            }
+            StatementKind::Words(values) => values
+                .iter()
+                .map(|value| encode(value, &labels, statement.address, isa.word_size()))
+                .collect::<Result<String, _>>()?,
+        };
+
+        for (idx, word) in bits.as_bytes().chunks(isa.word_size()).enumerate() {
+            let address = statement.address + idx as i128;
+            let address_bits =
+                bits_of(address, isa.address_size()).filter(|_| address >= 0).ok_or_else(|| {
+                    let kind =
+                        ErrorKind::AddressTooWide { address, address_size: isa.address_size() };
+                    Error::new(kind, statement.span.clone())
+                })?;
+
+            // Both consist of binary digits of the right size
+            let address = Value::parse_bin(&address_bits).unwrap();
+            let word = Value::parse_bin(std::str::from_utf8(word).unwrap()).unwrap();
+            if data.insert(address, word).is_some() {
+                let kind = ErrorKind::Overlap { address: statement.address + idx as i128 };
+                return Err(Error::new(kind, statement.span.clone()));
+            }
+        }
+    }
+
+    Ok(MemoryFile::new(isa.address_size(), isa.word_size(), data).unwrap())
+}
+
+/// The address after a statement of `words` words.
+fn next_address(address: i128, words: i128, span: &Range<usize>) -> Result<i128, Error> {
+    address.checked_add(words).ok_or_else(|| Error::new(ErrorKind::Overflow, span.clone()))
+}
+
+fn encode(
+    expr: &Expr<'_>,
+    labels: &HashMap<&str, i128>,
+    here: i128,
+    width: usize,
+) -> Result<String, Error> {
+    let value = expr.eval(labels, here)?;
+    bits_of(value, width)
+        .ok_or_else(|| Error::new(ErrorKind::ValueTooWide { value, width }, expr.span.clone()))
+}
+
+/// Two's complement of the value with the given width, `None` if the value is neither
+/// representable as unsigned nor as signed number.
+fn bits_of(value: i128, width: usize) -> Option<String> {
+    let min = -(1i128 << (width - 1));
+    let max = (1i128 << width) - 1;
+    if value < min || value > max {
+        return None;
+    }
+
+    let bits = format!("{:0128b}", value);
+    Some(bits[bits.len() - width..].to_string())
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// Byte range in the ISA description or the assembly source.
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidCharacter,
+    InvalidNumber,
+    /// What was expected instead.
+    InvalidSyntax(&'static str),
+    // ISA description
+    MissingWordSize,
+    MissingAddressSize,
+    InvalidSize,
+    DuplicateMnemonic(String),
+    DuplicateOperand(String),
+    UnknownOperand(String),
+    UnusedOperand(String),
+    LayoutWidth {
+        width: usize,
+        word_size: usize,
+    },
+    // Assembly source
+    UnknownDirective(String),
+    UnknownMnemonic(String),
+    OperandCount {
+        expected: usize,
+        actual: usize,
+    },
+    UnknownLabel(String),
+    DuplicateLabel(String),
+    ValueTooWide {
+        value: i128,
+        width: usize,
+    },
+    AddressTooWide {
+        address: i128,
+        address_size: usize,
+    },
+    Overlap {
+        address: i128,
+    },
+    DivisionByZero,
+    Overflow,
+}
+
+impl Error {
+    pub(crate) fn new(kind: ErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    /// Renders the error with the offending line of `source`, which is the ISA description or the
+    /// assembly source the error was reported for.
+    pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        let message = self.kind.to_string();
+        let span = if self.span.start >= source.len() {
+            pretty_error::Span::Eoi
+        } else {
+            pretty_error::Span::Range(self.span.clone())
+        };
+
+        let mut error = pretty_error::Error::new(&message)
+            .with_source(source, span)
+            .with_ansi_colors(ansi_colors);
+        if let Some(file_name) = file_name {
+            error = error.with_file_name(file_name);
+        }
+        error.to_string()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidCharacter => write!(f, "invalid character"),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ErrorKind::InvalidSyntax(expected) => write!(f, "{}", expected),
+            ErrorKind::MissingWordSize => write!(f, "missing word size, add `word <SIZE>`"),
+            ErrorKind::MissingAddressSize => {
+                write!(f, "missing address size, add `address <SIZE>`")
+            }
+            ErrorKind::InvalidSize => write!(f, "size must be between 1 and 64"),
+            ErrorKind::DuplicateMnemonic(mnemonic) => {
+                write!(f, "instruction `{}` is declared more than once", mnemonic)
+            }
+            ErrorKind::DuplicateOperand(operand) => {
+                write!(f, "operand `{}` is declared more than once", operand)
+            }
+            ErrorKind::UnknownOperand(operand) => write!(f, "unknown operand `{}`", operand),
+            ErrorKind::UnusedOperand(operand) => {
+                write!(f, "operand `{}` is not part of the layout", operand)
+            }
+            ErrorKind::LayoutWidth { width, word_size } => write!(
+                f,
+                "layout has {} bits, which is not a multiple of the word size ({} bits)",
+                width, word_size
+            ),
+            ErrorKind::UnknownDirective(directive) => {
+                write!(f, "unknown directive `{}`, expected `.org` or `.word`", directive)
+            }
+            ErrorKind::UnknownMnemonic(mnemonic) => write!(f, "unknown instruction `{}`", mnemonic),
+            ErrorKind::OperandCount { expected, actual } => {
+                write!(f, "expected {} operands, found {}", expected, actual)
+            }
+            ErrorKind::UnknownLabel(label) => write!(f, "unknown label `{}`", label),
+            ErrorKind::DuplicateLabel(label) => {
+                write!(f, "label `{}` is defined more than once", label)
+            }
+            ErrorKind::ValueTooWide { value, width } => {
+                write!(f, "value {} does not fit into {} bits", value, width)
+            }
+            ErrorKind::AddressTooWide { address, address_size } => write!(
+                f,
+                "address {} does not fit into the address register ({} bits)",
+                address, address_size
+            ),
+            ErrorKind::Overlap { address } => {
+                write!(f, "address {} is already in use", address)
+            }
+            ErrorKind::DivisionByZero => write!(f, "division by zero"),
+            ErrorKind::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
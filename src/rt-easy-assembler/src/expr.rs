@@ -0,0 +1,172 @@
+use crate::error::{Error, ErrorKind};
+use crate::lexer::{parse_number, Cursor, TokenKind};
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Expr<'s> {
+    pub kind: ExprKind<'s>,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ExprKind<'s> {
+    Number(i128),
+    Label(&'s str),
+    /// The address of the current statement (`.`).
+    Here,
+    Unary(UnaryOp, Box<Expr<'s>>),
+    Binary(BinaryOp, Box<Expr<'s>>, Box<Expr<'s>>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BinaryOp {
+    Or,
+    Xor,
+    And,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+/// Binary operators by precedence, from lowest to highest.
+const PRECEDENCE: [&[(&str, BinaryOp)]; 6] = [
+    &[("|", BinaryOp::Or)],
+    &[("^", BinaryOp::Xor)],
+    &[("&", BinaryOp::And)],
+    &[("<<", BinaryOp::Shl), (">>", BinaryOp::Shr)],
+    &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+    &[("*", BinaryOp::Mul), ("/", BinaryOp::Div), ("%", BinaryOp::Rem)],
+];
+
+pub(crate) fn parse_expr<'s>(cursor: &mut Cursor<'_, 's>) -> Result<Expr<'s>, Error> {
+    parse_binary(cursor, 0)
+}
+
+/// Comma separated expressions until the end of the line.
+pub(crate) fn parse_expr_list<'s>(cursor: &mut Cursor<'_, 's>) -> Result<Vec<Expr<'s>>, Error> {
+    let mut exprs = Vec::new();
+    if cursor.at_end() {
+        return Ok(exprs);
+    }
+
+    loop {
+        exprs.push(parse_expr(cursor)?);
+        if !cursor.eat_symbol(",") {
+            cursor.expect_end()?;
+            return Ok(exprs);
+        }
+    }
+}
+
+fn parse_binary<'s>(cursor: &mut Cursor<'_, 's>, level: usize) -> Result<Expr<'s>, Error> {
+    if level == PRECEDENCE.len() {
+        return parse_unary(cursor);
+    }
+
+    let start = cursor.peek().span.start;
+    let mut lhs = parse_binary(cursor, level + 1)?;
+    while let Some((_, op)) = PRECEDENCE[level].iter().find(|(symbol, _)| cursor.eat_symbol(symbol))
+    {
+        let rhs = parse_binary(cursor, level + 1)?;
+        let kind = ExprKind::Binary(*op, Box::new(lhs), Box::new(rhs));
+        lhs = Expr { kind, span: cursor.span_from(start) };
+    }
+
+    Ok(lhs)
+}
+
+fn parse_unary<'s>(cursor: &mut Cursor<'_, 's>) -> Result<Expr<'s>, Error> {
+    let start = cursor.peek().span.start;
+    let op = if cursor.eat_symbol("-") {
+        UnaryOp::Neg
+    } else if cursor.eat_symbol("~") {
+        UnaryOp::Not
+    } else {
+        return parse_primary(cursor);
+    };
+
+    let expr = parse_unary(cursor)?;
+    Ok(Expr { kind: ExprKind::Unary(op, Box::new(expr)), span: cursor.span_from(start) })
+}
+
+fn parse_primary<'s>(cursor: &mut Cursor<'_, 's>) -> Result<Expr<'s>, Error> {
+    let start = cursor.peek().span.start;
+    if cursor.eat_symbol("(") {
+        let expr = parse_expr(cursor)?;
+        cursor.expect_symbol(")", "expected `)`")?;
+        return Ok(Expr { kind: expr.kind, span: cursor.span_from(start) });
+    }
+    if cursor.eat_symbol(".") {
+        return Ok(Expr { kind: ExprKind::Here, span: cursor.span_from(start) });
+    }
+
+    let token = cursor.peek();
+    let kind = match token.kind {
+        TokenKind::Number => {
+            let number = parse_number(token.text)
+                .and_then(|number| i128::try_from(number).ok())
+                .ok_or_else(|| Error::new(ErrorKind::InvalidNumber, token.span.clone()))?;
+            ExprKind::Number(number)
+        }
+        TokenKind::Ident if !token.text.starts_with('.') => ExprKind::Label(token.text),
+        _ => return Err(cursor.unexpected("expected an expression")),
+    };
+    cursor.next();
+
+    Ok(Expr { kind, span: token.span.clone() })
+}
+
+impl Expr<'_> {
+    /// Evaluates the expression, `here` is the address of the current statement.
+    pub fn eval(&self, labels: &HashMap<&str, i128>, here: i128) -> Result<i128, Error> {
+        let error = |kind| Error::new(kind, self.span.clone());
+
+        match &self.kind {
+            ExprKind::Number(number) => Ok(*number),
+            ExprKind::Label(label) => labels
+                .get(label)
+                .copied()
+                .ok_or_else(|| error(ErrorKind::UnknownLabel(label.to_string()))),
+            ExprKind::Here => Ok(here),
+            ExprKind::Unary(op, expr) => {
+                let value = expr.eval(labels, here)?;
+                match op {
+                    UnaryOp::Neg => value.checked_neg().ok_or_else(|| error(ErrorKind::Overflow)),
+                    UnaryOp::Not => Ok(!value),
+                }
+            }
+            ExprKind::Binary(op, lhs, rhs) => {
+                let lhs = lhs.eval(labels, here)?;
+                let rhs = rhs.eval(labels, here)?;
+                let shift = || u32::try_from(rhs).ok().filter(|shift| *shift < i128::BITS);
+                let result = match op {
+                    BinaryOp::Or => Some(lhs | rhs),
+                    BinaryOp::Xor => Some(lhs ^ rhs),
+                    BinaryOp::And => Some(lhs & rhs),
+                    BinaryOp::Shl => shift().and_then(|shift| lhs.checked_shl(shift)),
+                    BinaryOp::Shr => shift().and_then(|shift| lhs.checked_shr(shift)),
+                    BinaryOp::Add => lhs.checked_add(rhs),
+                    BinaryOp::Sub => lhs.checked_sub(rhs),
+                    BinaryOp::Mul => lhs.checked_mul(rhs),
+                    BinaryOp::Div | BinaryOp::Rem if rhs == 0 => {
+                        return Err(error(ErrorKind::DivisionByZero))
+                    }
+                    BinaryOp::Div => lhs.checked_div(rhs),
+                    BinaryOp::Rem => lhs.checked_rem(rhs),
+                };
+                result.ok_or_else(|| error(ErrorKind::Overflow))
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::error::{Error, ErrorKind};
+use crate::lexer::{lex, parse_number, Cursor, TokenKind};
+
+/// Description of an instruction set.
+///
+/// ```text
+/// word 16        # size of a memory word in bits
+/// address 8      # size of an address in bits
+///
+/// # MNEMONIC OPERANDS = LAYOUT
+/// LOAD addr      = 0001 0000 addr:8
+/// ADDI reg, imm  = 0010 reg:4 imm:8
+/// HALT           = 0xF:4 0:12
+/// ```
+///
+/// The layout lists the fields of the instruction from the most to the least significant bit:
+/// binary digits, constants with a width (`VALUE:WIDTH`) and operands with a width
+/// (`OPERAND:WIDTH`). Instructions can span several words, the layout must be a multiple of the
+/// word size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Isa {
+    word_size: usize,
+    address_size: usize,
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    mnemonic: String,
+    operands: Vec<String>,
+    layout: Vec<Field>,
+    width: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Field {
+    /// Fixed bits, most significant bit first.
+    Bits(String),
+    /// The operand with the given index, in two's complement.
+    Operand { index: usize, width: usize },
+}
+
+/// Fields wider than this are not supported.
+const MAX_SIZE: usize = 64;
+
+impl Isa {
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut word_size = None;
+        let mut address_size = None;
+        let mut instructions = Vec::<(Instruction, _)>::new();
+
+        for line in lex(source)? {
+            let mut cursor = Cursor::new(&line);
+            if cursor.at_end() {
+                continue;
+            }
+
+            let name =
+                cursor.expect(TokenKind::Ident, "expected a mnemonic, `word` or `address`")?;
+            let is_size = cursor.peek().kind == TokenKind::Number;
+            match name.text {
+                "word" if is_size => word_size = Some(parse_size(&mut cursor)?),
+                "address" if is_size => address_size = Some(parse_size(&mut cursor)?),
+                mnemonic if mnemonic.starts_with('.') => {
+                    let kind = ErrorKind::InvalidSyntax("mnemonics must not start with `.`");
+                    return Err(Error::new(kind, name.span.clone()));
+                }
+                mnemonic => {
+                    let instruction = parse_instruction(&mut cursor, mnemonic)?;
+                    if instructions.iter().any(|(i, _)| i.mnemonic.eq_ignore_ascii_case(mnemonic)) {
+                        return Err(Error::new(
+                            ErrorKind::DuplicateMnemonic(mnemonic.to_string()),
+                            name.span.clone(),
+                        ));
+                    }
+                    instructions.push((instruction, name.span.clone()));
+                }
+            }
+            cursor.expect_end()?;
+        }
+
+        let end = source.len()..source.len();
+        let word_size =
+            word_size.ok_or_else(|| Error::new(ErrorKind::MissingWordSize, end.clone()))?;
+        let address_size =
+            address_size.ok_or_else(|| Error::new(ErrorKind::MissingAddressSize, end))?;
+
+        // Check layouts
+        for (instruction, span) in &instructions {
+            if instruction.width % word_size != 0 {
+                let kind = ErrorKind::LayoutWidth { width: instruction.width, word_size };
+                return Err(Error::new(kind, span.clone()));
+            }
+        }
+
+        let instructions = instructions.into_iter().map(|(instruction, _)| instruction).collect();
+        Ok(Self { word_size, address_size, instructions })
+    }
+
+    pub fn word_size(&self) -> usize {
+        self.word_size
+    }
+
+    pub fn address_size(&self) -> usize {
+        self.address_size
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Mnemonics are case insensitive.
+    pub fn instruction(&self, mnemonic: &str) -> Option<&Instruction> {
+        self.instructions.iter().find(|i| i.mnemonic.eq_ignore_ascii_case(mnemonic))
+    }
+}
+
+impl Instruction {
+    pub fn mnemonic(&self) -> &str {
+        &self.mnemonic
+    }
+
+    pub fn operands(&self) -> &[String] {
+        &self.operands
+    }
+
+    pub fn layout(&self) -> &[Field] {
+        &self.layout
+    }
+
+    /// Width of the layout in bits.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+fn parse_size(cursor: &mut Cursor<'_, '_>) -> Result<usize, Error> {
+    let token = cursor.next();
+    match parse_number(token.text).and_then(|size| usize::try_from(size).ok()) {
+        Some(size) if (1..=MAX_SIZE).contains(&size) => Ok(size),
+        Some(_) => Err(Error::new(ErrorKind::InvalidSize, token.span.clone())),
+        None => Err(Error::new(ErrorKind::InvalidNumber, token.span.clone())),
+    }
+}
+
+fn parse_instruction(cursor: &mut Cursor<'_, '_>, mnemonic: &str) -> Result<Instruction, Error> {
+    // Operands
+    let mut operands = Vec::<String>::new();
+    let mut operand_spans = Vec::new();
+    if !cursor.eat_symbol("=") {
+        loop {
+            let operand = cursor.expect(TokenKind::Ident, "expected an operand name")?;
+            if operands.iter().any(|o| o == operand.text) {
+                let kind = ErrorKind::DuplicateOperand(operand.text.to_string());
+                return Err(Error::new(kind, operand.span.clone()));
+            }
+            operands.push(operand.text.to_string());
+            operand_spans.push(operand.span.clone());
+
+            if !cursor.eat_symbol(",") {
+                cursor.expect_symbol("=", "expected `,` or `=`")?;
+                break;
+            }
+        }
+    }
+
+    // Layout
+    let mut layout = Vec::new();
+    let mut used = vec![false; operands.len()];
+    while !cursor.at_end() {
+        let token = cursor.next();
+        let width = match cursor.eat_symbol(":") {
+            true => Some(parse_size(cursor)?),
+            false => None,
+        };
+
+        let field = match (token.kind, width) {
+            (TokenKind::Number, None) => {
+                let bits = token.text.replace('_', "");
+                if bits.contains(|c| c != '0' && c != '1') {
+                    return Err(Error::new(
+                        ErrorKind::InvalidSyntax("expected binary digits or `VALUE:WIDTH`"),
+                        token.span.clone(),
+                    ));
+                }
+                Field::Bits(bits)
+            }
+            (TokenKind::Number, Some(width)) => {
+                let value = parse_number(token.text)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidNumber, token.span.clone()))?;
+                if value >> width != 0 {
+                    let kind = ErrorKind::ValueTooWide { value: value as i128, width };
+                    return Err(Error::new(kind, token.span.clone()));
+                }
+                Field::Bits(format!("{:01$b}", value, width))
+            }
+            (TokenKind::Ident, Some(width)) => {
+                let index = operands.iter().position(|o| o == token.text).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::UnknownOperand(token.text.to_string()),
+                        token.span.clone(),
+                    )
+                })?;
+                used[index] = true;
+                Field::Operand { index, width }
+            }
+            (TokenKind::Ident, None) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidSyntax("expected `:` and the width of the operand"),
+                    cursor.peek().span.clone(),
+                ))
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidSyntax("expected a field of the layout"),
+                    token.span.clone(),
+                ))
+            }
+        };
+        layout.push(field);
+    }
+    if layout.is_empty() {
+        return Err(cursor.unexpected("expected the layout of the instruction"));
+    }
+    if let Some(index) = used.iter().position(|used| !used) {
+        let kind = ErrorKind::UnusedOperand(operands[index].clone());
+        return Err(Error::new(kind, operand_spans[index].clone()));
+    }
+
+    let width = layout
+        .iter()
+        .map(|field| match field {
+            Field::Bits(bits) => bits.len(),
+            Field::Operand { width, .. } => *width,
+        })
+        .sum();
+    Ok(Instruction { mnemonic: mnemonic.to_string(), operands, layout, width })
+}
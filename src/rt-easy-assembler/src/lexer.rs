@@ -0,0 +1,164 @@
+use crate::error::{Error, ErrorKind};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Ident,
+    Number,
+    Symbol,
+    /// End of a line, the last line ends with the end of input.
+    Newline,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Token<'s> {
+    pub kind: TokenKind,
+    pub text: &'s str,
+    pub span: Range<usize>,
+}
+
+const SYMBOLS: [&str; 17] =
+    ["<<", ">>", ",", ":", "=", "(", ")", "+", "-", "*", "/", "%", "&", "|", "^", "~", "."];
+
+/// Splits the source into lines of tokens. Comments start with `;` or `#`.
+pub(crate) fn lex(source: &str) -> Result<Vec<Vec<Token<'_>>>, Error> {
+    let mut lines = vec![Vec::new()];
+    let mut pos = 0;
+    while let Some(c) = source[pos..].chars().next() {
+        let start = pos;
+        let rest = &source[pos..];
+        let (kind, len) = if c == '\n' {
+            (TokenKind::Newline, 1)
+        } else if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        } else if c == ';' || c == '#' {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        } else if c.is_ascii_alphabetic() || c == '_' || c == '.' && starts_ident(&rest[1..]) {
+            (TokenKind::Ident, 1 + ident_len(&rest[1..]))
+        } else if c.is_ascii_digit() {
+            (TokenKind::Number, ident_len(rest))
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+            (TokenKind::Symbol, symbol.len())
+        } else {
+            return Err(Error::new(ErrorKind::InvalidCharacter, start..start + c.len_utf8()));
+        };
+
+        pos += len;
+        let token = Token { kind, text: &source[start..pos], span: start..pos };
+        lines.last_mut().unwrap().push(token);
+        if kind == TokenKind::Newline {
+            lines.push(Vec::new());
+        }
+    }
+
+    let end = Token { kind: TokenKind::Newline, text: "", span: source.len()..source.len() };
+    lines.last_mut().unwrap().push(end);
+    Ok(lines)
+}
+
+fn starts_ident(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+}
+
+fn ident_len(s: &str) -> usize {
+    s.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(s.len())
+}
+
+/// Parses an unsigned number with an optional `0x`, `0o` or `0b` prefix. Underscores are allowed
+/// as separators.
+pub(crate) fn parse_number(text: &str) -> Option<u128> {
+    let text = text.replace('_', "");
+    let (digits, radix) = match text.get(..2) {
+        Some("0x") | Some("0X") => (&text[2..], 16),
+        Some("0o") | Some("0O") => (&text[2..], 8),
+        Some("0b") | Some("0B") => (&text[2..], 2),
+        _ => (&text[..], 10),
+    };
+    u128::from_str_radix(digits, radix).ok()
+}
+
+/// Tokens of a single line, the last token is always a [`TokenKind::Newline`].
+pub(crate) struct Cursor<'t, 's> {
+    tokens: &'t [Token<'s>],
+    pos: usize,
+}
+
+impl<'t, 's> Cursor<'t, 's> {
+    pub fn new(tokens: &'t [Token<'s>]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    pub fn peek(&self) -> &'t Token<'s> {
+        &self.tokens[self.pos]
+    }
+
+    pub fn peek_nth(&self, n: usize) -> Option<&'t Token<'s>> {
+        self.tokens.get(self.pos + n)
+    }
+
+    pub fn next(&mut self) -> &'t Token<'s> {
+        let token = &self.tokens[self.pos];
+        if token.kind != TokenKind::Newline {
+            self.pos += 1;
+        }
+        token
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.peek().kind == TokenKind::Newline
+    }
+
+    pub fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let token = self.peek();
+        if token.kind == TokenKind::Symbol && token.text == symbol {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect_symbol(&mut self, symbol: &str, expected: &'static str) -> Result<(), Error> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    pub fn expect(
+        &mut self,
+        kind: TokenKind,
+        expected: &'static str,
+    ) -> Result<&'t Token<'s>, Error> {
+        if self.peek().kind == kind && kind != TokenKind::Newline {
+            Ok(self.next())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    pub fn expect_end(&self) -> Result<(), Error> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.unexpected("expected the end of the line"))
+        }
+    }
+
+    /// Error at the next token.
+    pub fn unexpected(&self, expected: &'static str) -> Error {
+        Error::new(ErrorKind::InvalidSyntax(expected), self.peek().span.clone())
+    }
+
+    /// Span from `start` to the end of the previous token.
+    pub fn span_from(&self, start: usize) -> Range<usize> {
+        let end = match self.pos {
+            0 => start,
+            pos => self.tokens[pos - 1].span.end,
+        };
+        start..end.max(start)
+    }
+}
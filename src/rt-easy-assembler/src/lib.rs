@@ -0,0 +1,11 @@
+#![deny(rust_2018_idioms)]
+
+mod assemble;
+mod error;
+mod expr;
+mod isa;
+mod lexer;
+
+pub use self::assemble::assemble;
+pub use self::error::{Error, ErrorKind};
+pub use self::isa::{Field, Instruction, Isa};
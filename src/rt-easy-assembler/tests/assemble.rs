@@ -0,0 +1,95 @@
+use memory_file::{MemoryFile, Value};
+use rt_easy_assembler::{assemble, Field, Isa};
+
+const ISA: &str = r#"
+word 16
+address 8
+
+# MNEMONIC OPERANDS = LAYOUT
+LOAD addr     = 0001 0000 addr:8
+STORE addr    = 0010 0000 addr:8
+ADDI reg, imm = 0011 reg:4 imm:8
+JMP target    = 0x8:4 0:4 target:8
+LONG value    = 1111 0000 0000 0000 value:16
+HALT          = 1111_1111_1111_1111
+"#;
+
+fn mem(data: &[(&str, &str)]) -> MemoryFile {
+    let data = data
+        .iter()
+        .map(|(addr, value)| (Value::parse_hex(addr).unwrap(), Value::parse_hex(value).unwrap()))
+        .collect();
+    MemoryFile::new(8, 16, data).unwrap()
+}
+
+#[test]
+fn isa() {
+    let isa = Isa::parse(ISA).unwrap();
+    assert_eq!(isa.word_size(), 16);
+    assert_eq!(isa.address_size(), 8);
+    assert_eq!(isa.instructions().len(), 6);
+
+    let addi = isa.instruction("addi").unwrap();
+    assert_eq!(addi.mnemonic(), "ADDI");
+    assert_eq!(addi.operands(), ["reg", "imm"]);
+    assert_eq!(
+        addi.layout(),
+        [
+            Field::Bits("0011".to_string()),
+            Field::Operand { index: 0, width: 4 },
+            Field::Operand { index: 1, width: 8 },
+        ]
+    );
+    assert_eq!(isa.instruction("JMP").unwrap().layout()[0], Field::Bits("1000".to_string()));
+    assert_eq!(isa.instruction("LONG").unwrap().width(), 32);
+}
+
+#[test]
+fn program() {
+    let isa = Isa::parse(ISA).unwrap();
+    let source = r#"
+; Count down from 5
+start:  LOAD count
+loop:   ADDI 1, -1
+        jmp loop
+        STORE result
+        HALT
+
+count:  .word 5
+result: .word 0, start + 2 * (result - count), 0xFFFF
+"#;
+
+    assert_eq!(
+        assemble(&isa, source).unwrap(),
+        mem(&[
+            ("0", "1005"),
+            ("1", "31FF"),
+            ("2", "8001"),
+            ("3", "2006"),
+            ("4", "FFFF"),
+            ("5", "5"),
+            ("6", "0"),
+            ("7", "2"),
+            ("8", "FFFF"),
+        ])
+    );
+}
+
+#[test]
+fn directives() {
+    let isa = Isa::parse(ISA).unwrap();
+    let source = r#"
+        .org 0x10
+here:   JMP .
+        LONG -2
+        .org here + 8
+        .word here
+data:   .org 0x20
+        .word data
+"#;
+
+    assert_eq!(
+        assemble(&isa, source).unwrap(),
+        mem(&[("10", "8010"), ("11", "F000"), ("12", "FFFE"), ("18", "10"), ("20", "20")])
+    );
+}
@@ -0,0 +1,116 @@
+use rt_easy_assembler::{assemble, Error, ErrorKind, Isa};
+
+const ISA: &str = "word 8\naddress 4\nLDI value = 01 value:6\nNOP = 0000_0000\n";
+
+fn isa_error(source: &str) -> Error {
+    Isa::parse(source).unwrap_err()
+}
+
+fn assemble_error(source: &str) -> Error {
+    assemble(&Isa::parse(ISA).unwrap(), source).unwrap_err()
+}
+
+fn at<'s>(source: &'s str, error: &Error) -> &'s str {
+    &source[error.span.clone()]
+}
+
+#[test]
+fn isa() {
+    let error = isa_error("address 4\nNOP = 00000000");
+    assert_eq!(error.kind, ErrorKind::MissingWordSize);
+
+    let source = "word 8\naddress 4\nNOP = 0000 0000\nnop = 0000 0001";
+    let error = isa_error(source);
+    assert_eq!(error.kind, ErrorKind::DuplicateMnemonic("nop".to_string()));
+    assert_eq!(at(source, &error), "nop");
+
+    let source = "word 8\naddress 4\nLDI a, b = 01 b:6";
+    let error = isa_error(source);
+    assert_eq!(error.kind, ErrorKind::UnusedOperand("a".to_string()));
+    assert_eq!(at(source, &error), "a");
+
+    let source = "word 8\naddress 4\nLDI a = 01 c:6";
+    let error = isa_error(source);
+    assert_eq!(error.kind, ErrorKind::UnknownOperand("c".to_string()));
+
+    let source = "word 8\naddress 4\nLDI a = 01 a:4";
+    let error = isa_error(source);
+    assert_eq!(error.kind, ErrorKind::LayoutWidth { width: 6, word_size: 8 });
+    assert_eq!(at(source, &error), "LDI");
+
+    let source = "word 8\naddress 4\nNOP = 0000 0002";
+    let error = isa_error(source);
+    assert!(matches!(error.kind, ErrorKind::InvalidSyntax(_)));
+    assert_eq!(at(source, &error), "0002");
+
+    let error = isa_error("word 65\naddress 4");
+    assert_eq!(error.kind, ErrorKind::InvalidSize);
+}
+
+#[test]
+fn source() {
+    let source = "LDI 1\nfoo 2";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::UnknownMnemonic("foo".to_string()));
+    assert_eq!(at(source, &error), "foo");
+
+    let source = "LDI 1, 2";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::OperandCount { expected: 1, actual: 2 });
+    assert_eq!(at(source, &error), "LDI 1, 2");
+
+    let source = "LDI missing";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::UnknownLabel("missing".to_string()));
+
+    let source = "a: NOP\na: NOP";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::DuplicateLabel("a".to_string()));
+    assert_eq!(error.span, 7..8);
+
+    let source = "LDI 31 + 33";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::ValueTooWide { value: 64, width: 6 });
+    assert_eq!(at(source, &error), "31 + 33");
+
+    let source = ".org 15\nNOP\nNOP";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::AddressTooWide { address: 16, address_size: 4 });
+    assert_eq!(at(source, &error), "NOP");
+
+    let source = "NOP\n.org 0\n.word 1";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::Overlap { address: 0 });
+
+    let source = ".org 170141183460469231731687303715884105727\n.word 1, 2";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::Overflow);
+    assert_eq!(at(source, &error), ".word 1, 2");
+
+    let source = ".org 170141183460469231731687303715884105727\nNOP";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::Overflow);
+    assert_eq!(at(source, &error), "NOP");
+
+    let source = ".word 1 / (2 - 2)";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::DivisionByZero);
+
+    let source = ".data 1";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::UnknownDirective(".data".to_string()));
+
+    let source = ".word (1";
+    let error = assemble_error(source);
+    assert_eq!(error.kind, ErrorKind::InvalidSyntax("expected `)`"));
+    assert_eq!(error.span, 8..8);
+}
+
+#[test]
+fn pretty_print() {
+    let source = "NOP\nLDI 100\n";
+    let error = assemble_error(source);
+    let rendered = error.pretty_print(source, Some("program.asm"), false);
+    assert!(rendered.contains("value 100 does not fit into 6 bits"), "{}", rendered);
+    assert!(rendered.contains("program.asm:2:5"), "{}", rendered);
+}
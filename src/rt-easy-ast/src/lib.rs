@@ -5,9 +5,27 @@ pub use rtcore::common::*;
 
 #[derive(Debug, Clone)]
 pub struct Ast<'s> {
+    pub includes: Vec<Include<'s>>,
     pub declarations: Vec<Declaration<'s>>,
     pub statements: Vec<Statement<'s>>,
     pub trailing_label: Option<Spanned<Label<'s>>>,
+    pub expansions: Vec<Expansion<'s>>,
+}
+
+/// `include "path"`. Includes are resolved by the parser, the declarations of included files are
+/// part of [`Ast::declarations`].
+#[derive(Debug, Clone)]
+pub struct Include<'s> {
+    pub path: Spanned<&'s str>,
+    pub span: Span,
+}
+
+/// A macro expansion, indexed by [`ExpansionId`]. The span of the call site belongs to the parent
+/// expansion if the macro was called from within another macro.
+#[derive(Debug, Clone)]
+pub struct Expansion<'s> {
+    pub name: Ident<'s>,
+    pub call_site: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -16,11 +34,13 @@ pub enum Declaration<'s> {
     Bus(DeclareBus<'s>),
     Memory(DeclareMemory<'s>),
     RegisterArray(DeclareRegisterArray<'s>),
+    Constant(DeclareConstant<'s>),
 }
 
 impl Declaration<'_> {
     pub fn span(&self) -> Span {
         match self {
+            Self::Constant(n) => n.span,
             Self::Register(n) => n.span,
             Self::Bus(n) => n.span,
             Self::Memory(n) => n.span,
@@ -31,11 +51,19 @@ impl Declaration<'_> {
 
 #[derive(Debug, Clone)]
 pub struct DeclareRegister<'s> {
-    pub registers: Vec<RegBus<'s>>,
+    pub registers: Vec<DeclareRegisterItem<'s>>,
     pub kind: RegisterKind,
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct DeclareRegisterItem<'s> {
+    pub ident: Spanned<Ident<'s>>,
+    pub range: Option<Spanned<BitRangeExpr<'s>>>,
+    pub init: Option<Expression<'s>>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct DeclareBus<'s> {
     pub buses: Vec<RegBus<'s>>,
@@ -55,10 +83,23 @@ pub struct DeclareRegisterArray<'s> {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct DeclareConstant<'s> {
+    pub constants: Vec<DeclareConstantItem<'s>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeclareConstantItem<'s> {
+    pub ident: Spanned<Ident<'s>>,
+    pub value: Expression<'s>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct RegBus<'s> {
     pub ident: Spanned<Ident<'s>>,
-    pub range: Option<Spanned<BitRange>>,
+    pub range: Option<Spanned<BitRangeExpr<'s>>>,
     pub span: Span,
 }
 
@@ -95,8 +136,8 @@ pub struct Memory<'s> {
 #[derive(Debug, Clone)]
 pub struct DeclareRegisterArrayItem<'s> {
     pub ident: Spanned<Ident<'s>>,
-    pub range: Option<Spanned<BitRange>>,
-    pub len: usize,
+    pub range: Option<Spanned<BitRangeExpr<'s>>>,
+    pub len: Spanned<ConstUsize<'s>>,
     pub span: Span,
 }
 
@@ -305,6 +346,32 @@ pub struct UnaryTerm<'s> {
     pub span: Span,
 }
 
+/// A bit range as written in the source. The bounds may reference constants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BitRangeExpr<'s> {
+    pub msb: ConstUsize<'s>,
+    pub lsb: Option<ConstUsize<'s>>,
+}
+
+impl From<BitRange> for BitRangeExpr<'_> {
+    fn from(range: BitRange) -> Self {
+        Self { msb: range.msb.into(), lsb: range.lsb.map(Into::into) }
+    }
+}
+
+/// A non-negative integer, given as a number or as the name of a constant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConstUsize<'s> {
+    Number(usize),
+    Constant(Ident<'s>),
+}
+
+impl From<usize> for ConstUsize<'_> {
+    fn from(value: usize) -> Self {
+        Self::Number(value)
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct MemoryRange<'s> {
     pub address_register: Spanned<Ident<'s>>,
@@ -315,5 +382,7 @@ pub struct MemoryRange<'s> {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ident<'s>(pub &'s str);
 
+/// A label. Labels declared inside a macro are renamed for every expansion of the macro, the
+/// second field is the expansion they belong to.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct Label<'s>(pub &'s str);
+pub struct Label<'s>(pub &'s str, pub Option<ExpansionId>);
@@ -1,58 +1,303 @@
+use crate::MemoryOptions;
 use anyhow::{bail, Context, Result};
+use memory_file::{Format, MemoryFile};
+use rtcore::common::FileId;
+use rtcore::files::Files;
+use rtvhdl::error::RenderError;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 pub fn check(file: PathBuf, ansi_colors: bool) -> Result<()> {
-    let (source, file_name) = read_file(&file)?;
+    let (files, root) = read_files(&file)?;
 
-    let ast = match parser::parse(&source) {
+    let ast = match parser::parse_files(&files, root) {
         Ok(ast) => ast,
-        Err(e) => bail!(parser::pretty_print_error(&e, &source, file_name, ansi_colors)),
+        Err(e) => {
+            // Report the errors of the recovered parts too
+            let mut message = parser::pretty_print_file_errors(&e, &files, ansi_colors);
+            if let Some(partial) = e.partial {
+                if let Err(e) = compiler::check_partial(*partial, &Default::default()) {
+                    message += "\n\n";
+                    message += &e.pretty_print_files(&files, ansi_colors);
+                }
+            }
+            bail!(message)
+        }
     };
     match compiler::check(ast, &Default::default()) {
         Ok(()) => (),
-        Err(e) => bail!(e.pretty_print(&source, file_name, ansi_colors)),
+        Err(e) => bail!(e.pretty_print_files(&files, ansi_colors)),
     };
 
     Ok(())
 }
 
-pub fn test(file: PathBuf, test_file: PathBuf, ansi_colors: bool) -> Result<()> {
+pub fn explain(code: &str) -> Result<String> {
+    let digits = code.strip_prefix(|c| c == 'E' || c == 'e').unwrap_or(code);
+    let number = match digits.parse::<usize>() {
+        Ok(number) => number,
+        Err(_) => bail!("invalid error code \"{}\", expected e.g. E012", code),
+    };
+
+    match compiler::explain(number) {
+        Some(explanation) => Ok(explanation),
+        None => bail!("no error with code E{:03}", number),
+    }
+}
+
+/// Returns the report of the tests, fails with it if a test failed.
+pub fn test(
+    file: PathBuf,
+    test_file: PathBuf,
+    options: &unit_test::Options,
+    ansi_colors: bool,
+) -> Result<String> {
     // Build rt file
     let program = {
-        let (source, file_name) = read_file(&file)?;
+        let (files, root) = read_files(&file)?;
 
-        let ast = match parser::parse(&source) {
+        let ast = match parser::parse_files(&files, root) {
             Ok(ast) => ast,
-            Err(e) => bail!(parser::pretty_print_error(&e, &source, file_name, ansi_colors)),
+            Err(e) => bail!(parser::pretty_print_file_errors(&e, &files, ansi_colors)),
         };
 
         let backend = compiler_backend_simulator::BackendSimulator;
         match compiler::compile(&backend, (), ast, &Default::default()) {
             Ok(program) => program,
-            Err(e) => bail!(e.pretty_print(&source, file_name, ansi_colors)),
+            Err(e) => bail!(e.pretty_print_files(&files, ansi_colors)),
         }
     };
 
     // Parse test file
-    let unit_test = {
-        let (source, file_name) = read_file(&test_file)?;
+    let (source, file_name) = read_file(&test_file)?;
+    let mut unit_test = match unit_test::parser::parse(&source) {
+        Ok(unit_test) => unit_test,
+        Err(e) => {
+            bail!(unit_test::parser::pretty_print_error(&e, &source, file_name, ansi_colors))
+        }
+    };
+
+    // Tables are resolved relative to the test file
+    let dir = test_file.parent().unwrap_or_else(|| Path::new(""));
+    if let Err(e) = unit_test.load_tables(|path| fs::read_to_string(dir.join(path)).ok()) {
+        bail!(e.pretty_print(&source, file_name, ansi_colors));
+    }
+
+    // Run unit test
+    let report = unit_test::run(program, unit_test, options);
+    let output = report.pretty_print(&source, file_name, ansi_colors);
+    if !report.is_success() {
+        bail!(output);
+    }
+
+    Ok(output)
+}
+
+pub fn vhdl(
+    file: PathBuf,
+    module: Option<String>,
+    memories: &[String],
+    memory_options: &MemoryOptions,
+    ansi_colors: bool,
+) -> Result<String> {
+    // Build rt file
+    let vhdl = {
+        let (files, root) = read_files(&file)?;
+
+        let ast = match parser::parse_files(&files, root) {
+            Ok(ast) => ast,
+            Err(e) => bail!(parser::pretty_print_file_errors(&e, &files, ansi_colors)),
+        };
+
+        let backend = compiler_backend_vhdl::BackendVhdl;
+        match compiler::compile(&backend, (), ast, &Default::default()) {
+            Ok(vhdl) => vhdl,
+            Err(e) => bail!(e.pretty_print_files(&files, ansi_colors)),
+        }
+    };
 
-        match unit_test::parser::parse(&source) {
-            Ok(unit_test) => unit_test,
-            Err(e) => {
-                bail!(unit_test::parser::pretty_print_error(&e, &source, file_name, ansi_colors))
+    // Import memory files
+    let memories = memories
+        .iter()
+        .map(|memory| {
+            let (name, memory_file) = match memory.split_once('=') {
+                Some(memory) => memory,
+                None => bail!("invalid memory \"{}\", expected NAME=FILE", memory),
+            };
+            let memory_file = Path::new(memory_file);
+            let format = memory_format(memory_file, memory_options.format)?;
+            let source = fs::read(memory_file)
+                .with_context(|| format!("Failed to read from {}", memory_file.display()))?;
+
+            let name = rtvhdl::Ident(name.to_string());
+            match vhdl.import_memory_file(&name, format, &source, &memory_options.into()) {
+                Ok(mem) => Ok((name, mem)),
+                Err(RenderError::InvalidMemoryFile { error, .. }) => {
+                    let file_name = memory_file.display().to_string();
+                    bail!(error.pretty_print(&source, Some(&file_name), ansi_colors))
+                }
+                Err(e) => Err(e.into()),
             }
+        })
+        .collect::<Result<_>>()?;
+
+    // Render
+    let module = match module {
+        Some(module) => module,
+        None => file.file_stem().and_then(OsStr::to_str).unwrap_or("module").to_string(),
+    };
+    Ok(vhdl.render(&module, memories)?)
+}
+
+pub fn memconvert(
+    input: PathBuf,
+    output: PathBuf,
+    ar_size: usize,
+    dr_size: usize,
+    to: Option<Format>,
+    memory_options: &MemoryOptions,
+    ansi_colors: bool,
+) -> Result<()> {
+    let from = memory_format(&input, memory_options.format)?;
+    let to = memory_format(&output, to)?;
+    let options = memory_options.into();
+
+    let source =
+        fs::read(&input).with_context(|| format!("Failed to read from {}", input.display()))?;
+    let mem = match MemoryFile::import(from, &source, ar_size, dr_size, &options) {
+        Ok(mem) => mem,
+        Err(e) => {
+            let file_name = input.display().to_string();
+            bail!(e.pretty_print(&source, Some(&file_name), ansi_colors))
         }
     };
+    let bytes = match mem.export(to, &options) {
+        Ok(bytes) => bytes,
+        Err(e) => bail!("memory can not be saved as {}: {}", to, e),
+    };
 
-    // Run unit test
-    unit_test::run(program, unit_test).context("Tests failed")?;
+    write_file(&output, &bytes)
+}
+
+/// Fails with the differences if the memories are not equal.
+pub fn memdiff(
+    old: PathBuf,
+    new: PathBuf,
+    ar_size: Option<usize>,
+    dr_size: Option<usize>,
+    memory_options: &MemoryOptions,
+    ansi_colors: bool,
+) -> Result<()> {
+    let old_mem = read_memory(&old, ar_size, dr_size, memory_options, ansi_colors)?;
+    let new_mem = read_memory(&new, ar_size, dr_size, memory_options, ansi_colors)?;
+
+    let diff = old_mem.diff(&new_mem);
+    if !diff.is_empty() {
+        bail!("Memories differ:\n{}", diff);
+    }
 
     Ok(())
 }
 
+pub fn asm(isa_file: PathBuf, file: PathBuf, ansi_colors: bool) -> Result<MemoryFile> {
+    let isa_source = fs::read_to_string(&isa_file)
+        .with_context(|| format!("Failed to read from {}", isa_file.display()))?;
+    let isa = match assembler::Isa::parse(&isa_source) {
+        Ok(isa) => isa,
+        Err(e) => {
+            let file_name = isa_file.display().to_string();
+            bail!(e.pretty_print(&isa_source, Some(&file_name), ansi_colors))
+        }
+    };
+
+    let source = fs::read_to_string(&file)
+        .with_context(|| format!("Failed to read from {}", file.display()))?;
+    match assembler::assemble(&isa, &source) {
+        Ok(mem) => Ok(mem),
+        Err(e) => {
+            let file_name = file.display().to_string();
+            bail!(e.pretty_print(&source, Some(&file_name), ansi_colors))
+        }
+    }
+}
+
+/// Saves the memory in the format of `memory_options` or of the file extension.
+pub fn save_memory(mem: &MemoryFile, output: &Path, memory_options: &MemoryOptions) -> Result<()> {
+    let format = memory_format(output, memory_options.format)?;
+    let bytes = match mem.export(format, &memory_options.into()) {
+        Ok(bytes) => bytes,
+        Err(e) => bail!("memory can not be saved as {}: {}", format, e),
+    };
+
+    write_file(output, &bytes)
+}
+
+/// Reads a memory file. The sizes are required unless the file is in the rtmem format.
+fn read_memory(
+    file: &Path,
+    ar_size: Option<usize>,
+    dr_size: Option<usize>,
+    memory_options: &MemoryOptions,
+    ansi_colors: bool,
+) -> Result<MemoryFile> {
+    let format = memory_format(file, memory_options.format)?;
+    let source =
+        fs::read(file).with_context(|| format!("Failed to read from {}", file.display()))?;
+    let mem = match (format, ar_size, dr_size) {
+        (_, Some(ar_size), Some(dr_size)) => {
+            MemoryFile::import(format, &source, ar_size, dr_size, &memory_options.into())
+        }
+        (Format::RtEasy, None, None) => match std::str::from_utf8(&source) {
+            Ok(source) => MemoryFile::parse(source),
+            Err(_) => bail!("{} is not valid UTF-8", file.display()),
+        },
+        _ => bail!("--ar-size and --dr-size are required for {} files", format),
+    };
+
+    match mem {
+        Ok(mem) => Ok(mem),
+        Err(e) => {
+            let file_name = file.display().to_string();
+            bail!(e.pretty_print(&source, Some(&file_name), ansi_colors))
+        }
+    }
+}
+
+pub fn write_file(file: &Path, contents: &[u8]) -> Result<()> {
+    fs::write(file, contents).with_context(|| format!("Failed to write to {}", file.display()))
+}
+
+/// The explicit format, or the format of the file extension.
+fn memory_format(file: &Path, format: Option<Format>) -> Result<Format> {
+    if let Some(format) = format {
+        return Ok(format);
+    }
+
+    match file.extension().and_then(OsStr::to_str).and_then(Format::from_extension) {
+        Some(format) => Ok(format),
+        None => bail!("unknown memory file format of {}, use --format", file.display()),
+    }
+}
+
+impl From<&MemoryOptions> for memory_file::Options {
+    fn from(options: &MemoryOptions) -> Self {
+        Self { endianness: options.endianness, word_bytes: options.word_bytes }
+    }
+}
+
+/// Reads `file` and all files it includes. Includes are resolved relative to the including file.
+fn read_files(file: &Path) -> Result<(Files, FileId)> {
+    let source = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read from {}", file.display()))?;
+
+    let mut files = Files::new();
+    let root = files.add(file.to_string_lossy(), source);
+    parser::collect_files(&mut files, root, |name| fs::read_to_string(name).ok());
+
+    Ok((files, root))
+}
+
 fn read_file(file: &Path) -> Result<(String, Option<&str>)> {
     let source = fs::read_to_string(&file)
         .with_context(|| format!("Failed to read from {}", file.display()))?;
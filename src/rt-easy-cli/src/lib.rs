@@ -4,6 +4,7 @@ mod commands;
 
 use ansi_term::Colour::Green;
 use anyhow::Result;
+use memory_file::{Endianness, Format};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -30,9 +31,100 @@ pub enum Command {
         file: PathBuf,
         #[structopt(parse(from_os_str))]
         test_file: PathBuf,
+        #[structopt(
+            long,
+            help = "Fail on asserts of the program that are not followed by `expect assert_error`"
+        )]
+        fail_on_assert_error: bool,
+    },
+    #[structopt(about = "Explain a compiler error code, e.g. E012")]
+    Explain { code: String },
+    #[structopt(about = "Generate VHDL from the rt file")]
+    Vhdl {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+        #[structopt(long, help = "Module name (default: file name)")]
+        module: Option<String>,
+        #[structopt(long = "memory", help = "Initial memory content, as NAME=FILE")]
+        memories: Vec<String>,
+        #[structopt(flatten)]
+        memory_options: MemoryOptions,
+        #[structopt(short, long, parse(from_os_str), help = "Output file (default: stdout)")]
+        output: Option<PathBuf>,
+    },
+    #[structopt(about = "Convert a memory file to another format")]
+    Memconvert {
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        #[structopt(parse(from_os_str))]
+        output: PathBuf,
+        #[structopt(long, help = "Size of the address register")]
+        ar_size: usize,
+        #[structopt(long, help = "Size of the data register")]
+        dr_size: usize,
+        #[structopt(long, parse(try_from_str = parse_format), help = "Output format (default: by extension)")]
+        to: Option<Format>,
+        #[structopt(flatten)]
+        memory_options: MemoryOptions,
+    },
+    #[structopt(about = "Show the differences between two memory files")]
+    Memdiff {
+        #[structopt(parse(from_os_str))]
+        old: PathBuf,
+        #[structopt(parse(from_os_str))]
+        new: PathBuf,
+        #[structopt(long, help = "Size of the address register (default: rtmem header)")]
+        ar_size: Option<usize>,
+        #[structopt(long, help = "Size of the data register (default: rtmem header)")]
+        dr_size: Option<usize>,
+        #[structopt(flatten)]
+        memory_options: MemoryOptions,
+    },
+    #[structopt(about = "Assemble a program into a memory file")]
+    Asm {
+        #[structopt(parse(from_os_str), help = "Description of the instruction set")]
+        isa: PathBuf,
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+        #[structopt(flatten)]
+        memory_options: MemoryOptions,
+        #[structopt(
+            short,
+            long,
+            parse(from_os_str),
+            help = "Output file (default: stdout as rtmem)"
+        )]
+        output: Option<PathBuf>,
     },
 }
 
+#[derive(Debug, StructOpt)]
+pub struct MemoryOptions {
+    #[structopt(
+        long,
+        parse(try_from_str = parse_format),
+        help = "Memory file format: rtmem, ihex, bin, readmemh, readmemb, mif or coe (default: by extension)"
+    )]
+    pub format: Option<Format>,
+    #[structopt(
+        long,
+        default_value = "little",
+        parse(try_from_str = parse_endianness),
+        help = "Byte order of words in ihex and bin files: little or big"
+    )]
+    pub endianness: Endianness,
+    #[structopt(long, help = "Bytes per word in ihex and bin files (default: fit data register)")]
+    pub word_bytes: Option<usize>,
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    s.parse().map_err(|()| format!("unknown memory file format \"{}\"", s))
+}
+
+fn parse_endianness(s: &str) -> Result<Endianness, String> {
+    s.parse().map_err(|()| format!("unknown endianness \"{}\"", s))
+}
+
 pub fn run(opt: Opt) -> Result<String> {
     let ansi_colors = !opt.no_ansi;
     let msg = match opt.command {
@@ -40,9 +132,46 @@ pub fn run(opt: Opt) -> Result<String> {
             commands::check(file, ansi_colors)?;
             "Code is syntactically valid"
         }
-        Command::Test { file, test_file } => {
-            commands::test(file, test_file, ansi_colors)?;
-            "Tests passed"
+        Command::Test { file, test_file, fail_on_assert_error } => {
+            let options = unit_test::Options { fail_on_assert_error };
+            return commands::test(file, test_file, &options, ansi_colors);
+        }
+        Command::Explain { code } => return commands::explain(&code),
+        Command::Vhdl { file, module, memories, memory_options, output } => {
+            let vhdl = commands::vhdl(file, module, &memories, &memory_options, ansi_colors)?;
+            match output {
+                Some(output) => {
+                    commands::write_file(&output, vhdl.as_bytes())?;
+                    "VHDL generated"
+                }
+                None => return Ok(vhdl),
+            }
+        }
+        Command::Memconvert { input, output, ar_size, dr_size, to, memory_options } => {
+            commands::memconvert(
+                input,
+                output,
+                ar_size,
+                dr_size,
+                to,
+                &memory_options,
+                ansi_colors,
+            )?;
+            "Memory file converted"
+        }
+        Command::Memdiff { old, new, ar_size, dr_size, memory_options } => {
+            commands::memdiff(old, new, ar_size, dr_size, &memory_options, ansi_colors)?;
+            "Memories are equal"
+        }
+        Command::Asm { isa, file, memory_options, output } => {
+            let mem = commands::asm(isa, file, ansi_colors)?;
+            match output {
+                Some(output) => {
+                    commands::save_memory(&mem, &output, &memory_options)?;
+                    "Program assembled"
+                }
+                None => return Ok(mem.to_string()),
+            }
         }
     };
 
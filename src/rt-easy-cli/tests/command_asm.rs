@@ -0,0 +1,60 @@
+use rt_easy_cli::{Command, MemoryOptions, Opt};
+use std::path::PathBuf;
+
+fn memory_options() -> MemoryOptions {
+    MemoryOptions { format: None, endianness: Default::default(), word_bytes: None }
+}
+
+#[test]
+fn asm() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Asm {
+            isa: file("program.isa"),
+            file: file("program.asm"),
+            memory_options: memory_options(),
+            output: None,
+        },
+    };
+    let mem = rt_easy_cli::run(opt).unwrap();
+    assert_eq!(mem, "H 4 8\n\n1C\n2D\n3E\nF0\n\nC:\n3\nFF\n0\n");
+}
+
+#[test]
+fn asm_output() {
+    let output = std::env::temp_dir().join("rt-easy-cli-asm.memh");
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Asm {
+            isa: file("program.isa"),
+            file: file("program.asm"),
+            memory_options: memory_options(),
+            output: Some(output.clone()),
+        },
+    };
+    rt_easy_cli::run(opt).unwrap();
+
+    let memh = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert_eq!(memh, "1C\n2D\n3E\nF0\n@C\n03\nFF\n00\n");
+}
+
+#[test]
+fn asm_invalid() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Asm {
+            isa: file("program.isa"),
+            file: file("invalid.asm"),
+            memory_options: memory_options(),
+            output: None,
+        },
+    };
+    let err = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(err.contains("unknown instruction `SUB`"), "{}", err);
+    assert!(err.contains("invalid.asm:2:9"), "{}", err);
+}
+
+fn file(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", name].iter().collect()
+}
@@ -19,6 +19,29 @@ fn check_invalid02() {
     assert!(rt_easy_cli::run(opt).is_err());
 }
 
+#[test]
+fn check_invalid03() {
+    let opt = Opt { no_ansi: true, command: Command::Check { file: file("invalid03.rt") } };
+    let err = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(err.contains("invalid03.rt:3:6"), "{}", err);
+    assert!(err.contains("invalid03.rt:4:9"), "{}", err);
+    assert!(err.contains("invalid03.rt:5:6"), "{}", err);
+}
+
+#[test]
+fn check_include() {
+    let opt = Opt { no_ansi: true, command: Command::Check { file: file("include/mult.rt") } };
+    rt_easy_cli::run(opt).unwrap();
+}
+
+#[test]
+fn check_include_invalid() {
+    let opt = Opt { no_ansi: true, command: Command::Check { file: file("include/invalid.rt") } };
+    let err = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(err.contains("include/invalid.rt:1:9"), "{}", err);
+    assert!(err.contains("file not found"), "{}", err);
+}
+
 fn file(name: &str) -> PathBuf {
     [env!("CARGO_MANIFEST_DIR"), "tests", name].iter().collect()
 }
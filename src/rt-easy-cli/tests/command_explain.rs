@@ -0,0 +1,26 @@
+use rt_easy_cli::{Command, Opt};
+
+fn explain(code: &str) -> anyhow::Result<String> {
+    let opt = Opt { no_ansi: true, command: Command::Explain { code: code.to_string() } };
+    rt_easy_cli::run(opt)
+}
+
+#[test]
+fn explain_code() {
+    let explanation = explain("E012").unwrap();
+    assert!(explanation.contains("index expression is too wide"), "{}", explanation);
+    assert_eq!(explain("e12").unwrap(), explanation);
+    assert_eq!(explain("12").unwrap(), explanation);
+}
+
+#[test]
+fn explain_unknown_code() {
+    let err = explain("E999").unwrap_err().to_string();
+    assert_eq!(err, "no error with code E999");
+}
+
+#[test]
+fn explain_invalid_code() {
+    let err = explain("X12").unwrap_err().to_string();
+    assert_eq!(err, "invalid error code \"X12\", expected e.g. E012");
+}
@@ -0,0 +1,41 @@
+use rt_easy_cli::{Command, MemoryOptions, Opt};
+use std::path::PathBuf;
+
+fn memdiff(old: &str, new: &str) -> anyhow::Result<String> {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Memdiff {
+            old: file(old),
+            new: file(new),
+            ar_size: None,
+            dr_size: None,
+            memory_options: MemoryOptions {
+                format: None,
+                endianness: Default::default(),
+                word_bytes: None,
+            },
+        },
+    };
+    rt_easy_cli::run(opt)
+}
+
+#[test]
+fn memdiff_equal() {
+    assert_eq!(memdiff("memory_old.rtmem", "memory_old.rtmem").unwrap(), "Memories are equal");
+}
+
+#[test]
+fn memdiff_differ() {
+    let err = memdiff("memory_old.rtmem", "memory_new.rtmem").unwrap_err().to_string();
+    assert_eq!(err, "Memories differ:\n1: 2D -> 2F\n3: F0 -> unset\nC: unset -> 03\n");
+}
+
+#[test]
+fn memdiff_sizes_required() {
+    let err = memdiff("memory_old.rtmem", "memory.memh").unwrap_err().to_string();
+    assert_eq!(err, "--ar-size and --dr-size are required for readmemh files");
+}
+
+fn file(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", name].iter().collect()
+}
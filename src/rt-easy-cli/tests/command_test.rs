@@ -5,9 +5,47 @@ use std::path::PathBuf;
 fn test() {
     let opt = Opt {
         no_ansi: true,
-        command: Command::Test { file: file("mult.rt"), test_file: file("mult_test.rtt") },
+        command: Command::Test {
+            file: file("mult.rt"),
+            test_file: file("mult_test.rtt"),
+            fail_on_assert_error: false,
+        },
     };
-    rt_easy_cli::run(opt).unwrap();
+    let report = rt_easy_cli::run(opt).unwrap();
+    assert!(report.ends_with("test result: ok. 1 passed; 0 failed"), "{}", report);
+}
+
+#[test]
+fn test_failed() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Test {
+            file: file("mult.rt"),
+            test_file: file("mult_test_failed.rtt"),
+            fail_on_assert_error: false,
+        },
+    };
+    let report = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(report.contains("test 4 * 7 ... ok\ntest 4 * 3 ... FAILED\n"), "{}", report);
+    assert!(report.contains("mult_test_failed.rtt:15:12"), "{}", report);
+    assert!(report.contains("`OUTPUT = 13`: left is 12 (0xC), right is 13 (0xD)"), "{}", report);
+    assert!(report.ends_with("test result: FAILED. 1 passed; 1 failed"), "{}", report);
+}
+
+#[test]
+fn test_table() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Test {
+            file: file("mult.rt"),
+            test_file: file("mult_table_test.rtt"),
+            fail_on_assert_error: false,
+        },
+    };
+    let report = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(report.contains("test inline [A = 2, B = 2, PRODUCT = 5] ... FAILED\n"), "{}", report);
+    assert!(report.contains("test csv [A = 15, B = 17, PRODUCT = 255] ... ok\n"), "{}", report);
+    assert!(report.ends_with("test result: FAILED. 5 passed; 1 failed"), "{}", report);
 }
 
 fn file(name: &str) -> PathBuf {
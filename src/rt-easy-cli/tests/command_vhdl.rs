@@ -0,0 +1,88 @@
+use rt_easy_cli::{Command, MemoryOptions, Opt};
+use std::path::PathBuf;
+
+fn memory_options() -> MemoryOptions {
+    MemoryOptions { format: None, endianness: Default::default(), word_bytes: None }
+}
+
+#[test]
+fn vhdl() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Vhdl {
+            file: file("memory.rt"),
+            module: None,
+            memories: vec![format!("MEM={}", file("memory.memh").display())],
+            memory_options: memory_options(),
+            output: None,
+        },
+    };
+    let vhdl = rt_easy_cli::run(opt).unwrap();
+    assert!(vhdl.contains("ENTITY EU_memory IS"), "{}", vhdl);
+    assert!(vhdl.contains("1 => \"00101010\""), "{}", vhdl);
+    assert!(vhdl.contains("2 => \"11111111\""), "{}", vhdl);
+}
+
+#[test]
+fn vhdl_invalid_memory() {
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Vhdl {
+            file: file("memory.rt"),
+            module: None,
+            memories: vec![format!("MEM={}", file("memory.rt").display())],
+            memory_options: memory_options(),
+            output: None,
+        },
+    };
+    let err = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(err.starts_with("unknown memory file format of"), "{}", err);
+}
+
+#[test]
+fn memconvert() {
+    let output = std::env::temp_dir().join("rt-easy-cli-memconvert.coe");
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Memconvert {
+            input: file("memory.memh"),
+            output: output.clone(),
+            ar_size: 4,
+            dr_size: 8,
+            to: None,
+            memory_options: memory_options(),
+        },
+    };
+    rt_easy_cli::run(opt).unwrap();
+
+    let coe = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert_eq!(
+        coe,
+        "memory_initialization_radix=16;\nmemory_initialization_vector=\n00,\n2A,\nFF;\n"
+    );
+}
+
+#[test]
+fn memconvert_invalid() {
+    let output = std::env::temp_dir().join("rt-easy-cli-memconvert-invalid.coe");
+    let opt = Opt {
+        no_ansi: true,
+        command: Command::Memconvert {
+            input: file("memory.memh"),
+            output: output.clone(),
+            ar_size: 4,
+            dr_size: 4,
+            to: None,
+            memory_options: memory_options(),
+        },
+    };
+    let err = rt_easy_cli::run(opt).unwrap_err().to_string();
+    assert!(err.contains("value does not fit into the data register (4 bits)"), "{}", err);
+    assert!(err.contains("memory.memh:2:4"), "{}", err);
+    assert!(!output.exists());
+}
+
+fn file(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", name].iter().collect()
+}
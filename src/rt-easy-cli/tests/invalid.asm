@@ -0,0 +1,2 @@
+        LOAD a
+        SUB b
@@ -0,0 +1,10 @@
+; result = a + b
+        LOAD a
+        ADD b
+        STORE result
+        HALT
+
+        .org 0xC
+a:      .word 3
+b:      .word -1
+result: .word 0
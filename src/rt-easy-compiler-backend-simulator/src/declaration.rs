@@ -21,6 +21,11 @@ impl Generate<mir::Declaration<'_>> for Declaration {
                     register_arrays: Generate::generate(declare_register_array.register_arrays)?,
                 }))
             }
+            mir::Declaration::Constant(declare_constant) => {
+                Ok(Declaration::Constant(DeclareConstant {
+                    constants: Generate::generate(declare_constant.constants)?,
+                }))
+            }
         }
     }
 }
@@ -37,6 +42,17 @@ impl Generate<mir::Memory<'_>> for Memory {
     }
 }
 
+impl Generate<mir::DeclareRegisterItem<'_>> for DeclareRegisterItem {
+    fn generate(declare_register_item: mir::DeclareRegisterItem<'_>) -> Result<Self> {
+        Ok(DeclareRegisterItem {
+            ident: gen_ident(declare_register_item.ident.node),
+            range: declare_register_item.range.map(|s| s.node),
+            kind: declare_register_item.kind,
+            init: declare_register_item.init,
+        })
+    }
+}
+
 impl Generate<mir::DeclareRegisterArrayItem<'_>> for DeclareRegisterArrayItem {
     fn generate(declare_register_array_item: mir::DeclareRegisterArrayItem<'_>) -> Result<Self> {
         Ok(DeclareRegisterArrayItem {
@@ -46,3 +62,9 @@ impl Generate<mir::DeclareRegisterArrayItem<'_>> for DeclareRegisterArrayItem {
         })
     }
 }
+
+impl Generate<mir::Constant<'_>> for Constant {
+    fn generate(constant: mir::Constant<'_>) -> Result<Self> {
+        Ok(Constant { ident: gen_ident(constant.ident.node), value: constant.value })
+    }
+}
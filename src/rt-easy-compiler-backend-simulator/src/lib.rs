@@ -20,6 +20,14 @@ impl compiler::Backend for BackendSimulator {
     }
 }
 
+/// Generates a single expression, e.g. one built by `compiler::compile_expression`.
+pub fn generate_expression(expression: compiler::mir::Expression<'_>) -> rtprogram::Expression {
+    match Generate::generate(expression) {
+        Ok(expression) => expression,
+        Err(infallible) => match infallible {},
+    }
+}
+
 type Result<T> = std::result::Result<T, std::convert::Infallible>;
 
 trait Generate<I>: Sized {
@@ -27,7 +35,11 @@ trait Generate<I>: Sized {
 }
 
 fn gen_label(l: compiler::mir::Label<'_>) -> rtprogram::Label {
-    rtprogram::Label(l.0.to_owned())
+    match l.1 {
+        // Labels of macro bodies are unique per expansion
+        Some(expansion) => rtprogram::Label(format!("{}#{}", l.0, expansion.0)),
+        None => rtprogram::Label(l.0.to_owned()),
+    }
 }
 
 fn gen_ident(id: compiler::mir::Ident<'_>) -> rtprogram::Ident {
@@ -18,6 +18,7 @@ pub fn generate_declarations<'s>(mir_declarations: &[mir::Declaration<'s>]) -> D
                         gen_ident(register.ident.node),
                         generate_bit_range(register.range.map(|s| s.node)),
                         register.kind,
+                        register.init.clone(),
                     ));
                 }
             }
@@ -32,15 +33,15 @@ pub fn generate_declarations<'s>(mir_declarations: &[mir::Declaration<'s>]) -> D
             }
             mir::Declaration::Memory(declaration) => {
                 for memory in &declaration.memories {
-                    let (ar_name, ar_range, ar_kind) = declarations
+                    let (ar_name, ar_range, ar_kind, _) = declarations
                         .registers
                         .iter()
-                        .find(|(name, _, _)| name.0 == memory.range.address_register.node.0)
+                        .find(|(name, _, _, _)| name.0 == memory.range.address_register.node.0)
                         .unwrap();
-                    let (dr_name, dr_range, dr_kind) = declarations
+                    let (dr_name, dr_range, dr_kind, _) = declarations
                         .registers
                         .iter()
-                        .find(|(name, _, _)| name.0 == memory.range.data_register.node.0)
+                        .find(|(name, _, _, _)| name.0 == memory.range.data_register.node.0)
                         .unwrap();
 
                     declarations.memories.push((
@@ -59,6 +60,8 @@ pub fn generate_declarations<'s>(mir_declarations: &[mir::Declaration<'s>]) -> D
                     ));
                 }
             }
+            // Constants are folded into the expressions and not emitted as generics
+            mir::Declaration::Constant(_) => (),
         }
     }
 
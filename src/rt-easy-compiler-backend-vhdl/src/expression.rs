@@ -58,7 +58,7 @@ pub fn generate_register<'s>(reg: &mir::Register<'s>, declarations: &Declaration
     let ident = gen_ident(reg.ident.node);
 
     let range_declaration =
-        declarations.registers.iter().find(|(name, _, _)| ident == *name).unwrap().1;
+        declarations.registers.iter().find(|(name, _, _, _)| ident == *name).unwrap().1;
 
     Register {
         ident,
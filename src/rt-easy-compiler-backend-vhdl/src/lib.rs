@@ -27,6 +27,14 @@ impl compiler::Backend for BackendVhdl {
     }
 }
 
+fn gen_label(label: compiler::mir::Label<'_>) -> rtvhdl::Label {
+    match label.1 {
+        // Labels of macro bodies are unique per expansion
+        Some(expansion) => rtvhdl::Label::expanded(label.0, expansion.0),
+        None => rtvhdl::Label::named(label.0),
+    }
+}
+
 fn gen_ident(id: compiler::mir::Ident<'_>) -> rtvhdl::Ident {
     rtvhdl::Ident(id.0.to_owned())
 }
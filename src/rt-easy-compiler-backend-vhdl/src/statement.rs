@@ -1,5 +1,6 @@
 use super::{
     expression::generate_expression,
+    gen_label,
     operation::{generate_assignment, generate_read, generate_write},
     vhdl::VhdlBuilder,
 };
@@ -78,7 +79,7 @@ impl StatementBuilder {
             // Otherwise the entry for the label in next_state_conditional is created
             // or extended with the criteria of this step.
             mir::Operation::Goto(mir_goto) => {
-                let label = Label::named(mir_goto.label.node.0);
+                let label = gen_label(mir_goto.label.node);
 
                 if step.criteria.is_empty() {
                     self.next_state_default = label;
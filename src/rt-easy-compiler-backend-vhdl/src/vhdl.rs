@@ -1,5 +1,5 @@
 use super::{
-    declarations::generate_declarations, next_state_logic_deps::next_state_logic_deps,
+    declarations::generate_declarations, gen_label, next_state_logic_deps::next_state_logic_deps,
     statement::StatementBuilder,
 };
 use crate::error::SynthError;
@@ -129,7 +129,7 @@ fn calc_label_goto_prefix(mir: &mir::Mir<'_>) -> String {
 fn make_label(idx: usize, statement: Option<&mir::Statement<'_>>) -> Label {
     match statement {
         Some(statement) => match statement.label.as_ref() {
-            Some(label) => Label::named(label.node.0),
+            Some(label) => gen_label(label.node),
             None => Label::unnamed(idx),
         },
         None => Label::terminated(),
@@ -0,0 +1,16 @@
+mod util;
+
+#[test]
+fn constant() {
+    const SOURCE: &str = r#"
+        declare constant WIDTH = 8, MSB = WIDTH - 1
+        declare register A(MSB:0)
+
+        A <- A + WIDTH;
+    "#;
+
+    let vhdl = util::compile(SOURCE).render("constant", Default::default()).unwrap();
+    assert!(!vhdl.contains("WIDTH"));
+    assert!(!vhdl.contains("MSB"));
+    assert!(vhdl.contains("register_A : unsigned(7 DOWNTO 0)"));
+}
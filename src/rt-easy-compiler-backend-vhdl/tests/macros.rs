@@ -0,0 +1,17 @@
+mod util;
+
+#[test]
+fn macros() {
+    const SOURCE: &str = r#"
+        declare register A(7:0)
+        define WAIT { LOOP: if A <> 0 then A <- A - 1, goto LOOP fi; }
+
+        LOOP: A <- 5;
+        WAIT; WAIT; goto LOOP;
+    "#;
+
+    let vhdl = util::compile(SOURCE).render("macros", Default::default()).unwrap();
+    assert!(vhdl.contains("NAMED_LOOP"));
+    assert!(vhdl.contains("EXPANDED_0_LOOP"));
+    assert!(vhdl.contains("EXPANDED_1_LOOP"));
+}
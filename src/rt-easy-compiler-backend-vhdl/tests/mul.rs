@@ -0,0 +1,16 @@
+mod util;
+
+#[test]
+fn mul() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), B(7:0), X(15:0)
+
+        X <- sxt A * B + 1;
+    "#;
+
+    let vhdl = util::compile(SOURCE).render("mul", Default::default()).unwrap();
+    assert!(vhdl.contains("FUNCTION f_mul(in0 : unsigned; in1 : unsigned) RETURN unsigned IS"));
+    assert!(vhdl.contains(
+        "f_mul(sign_extend(f_sxt(zero_extend(register_A, 8)), 16), zero_extend(register_B, 16))"
+    ));
+}
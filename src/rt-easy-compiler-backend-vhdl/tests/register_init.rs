@@ -0,0 +1,27 @@
+mod util;
+
+#[test]
+fn register_init() {
+    const SOURCE: &str = r#"
+        declare register PC(7:0) = 0x10, A(3:0)
+
+        PC <- PC + 1, A <- 1;
+    "#;
+
+    let vhdl = util::compile(SOURCE).render("register_init", Default::default()).unwrap();
+    assert!(vhdl.contains(r#"SIGNAL register_PC : unsigned(7 DOWNTO 0) := "00010000";"#));
+    assert!(vhdl.contains("SIGNAL register_A : unsigned(3 DOWNTO 0) := (OTHERS => '0');"));
+
+    // Reset port of the execution unit
+    let eu_start = vhdl.find("ENTITY EU_register_init IS").unwrap();
+    let eu_end = vhdl.find("END EU_register_init;").unwrap();
+    assert!(vhdl[eu_start..eu_end].contains("reset : IN STD_LOGIC;"));
+
+    // Reset branch
+    assert!(vhdl.contains("ClockedOp : PROCESS (clock, reset)"));
+    assert!(vhdl.contains(r#"register_PC <= "00010000";"#));
+    assert!(vhdl.contains("register_A <= (OTHERS => '0');"));
+    let reset = vhdl.find("IF reset = '1' THEN\n            register_PC").unwrap();
+    let clock = vhdl[reset..].find("ELSIF rising_edge(clock) THEN").unwrap();
+    assert!(vhdl[reset..reset + clock].contains("register_A <= (OTHERS => '0');"));
+}
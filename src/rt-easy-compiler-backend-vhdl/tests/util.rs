@@ -6,7 +6,7 @@ use rtvhdl::Vhdl;
 pub fn compile(source: &str) -> Vhdl {
     let ast = match parser::parse(source) {
         Ok(ast) => ast,
-        Err(e) => panic!("{}", parser::pretty_print_error(&e, source, None, false)),
+        Err(e) => panic!("{}", parser::pretty_print_errors(&e, source, None, false)),
     };
 
     let backend = BackendVhdl;
@@ -20,7 +20,7 @@ pub fn compile(source: &str) -> Vhdl {
 pub fn compile_err(source: &str) -> Error {
     let ast = match parser::parse(source) {
         Ok(ast) => ast,
-        Err(e) => panic!("{}", parser::pretty_print_error(&e, source, None, false)),
+        Err(e) => panic!("{}", parser::pretty_print_errors(&e, source, None, false)),
     };
 
     let backend = BackendVhdl;
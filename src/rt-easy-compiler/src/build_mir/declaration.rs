@@ -1,32 +1,49 @@
 use super::{expression::BuildExpr, Result};
+use crate::check_mir::const_eval::Evaluate;
 use crate::mir::*;
-use crate::symbols::Symbols;
+use crate::{symbols::Symbols, InternalError};
 use rtast as ast;
 
 pub fn build<'s>(
     declaration: ast::Declaration<'s>,
     symbols: &Symbols<'_>,
 ) -> Result<Declaration<'s>> {
-    match declaration {
-        ast::Declaration::Register(declare_register) => {
-            Ok(Declaration::Register(DeclareRegister {
-                registers: declare_register
-                    .registers
-                    .into_iter()
-                    .map(|reg| Ok(Register::build(reg, symbols)?.inner))
-                    .collect::<Result<_>>()?,
-                span: declare_register.span,
-            }))
-        }
-        ast::Declaration::Bus(declare_bus) => Ok(Declaration::Bus(DeclareBus {
+    let declaration = match declaration {
+        ast::Declaration::Register(declare_register) => Declaration::Register(DeclareRegister {
+            registers: declare_register
+                .registers
+                .into_iter()
+                .map(|reg| {
+                    let range = reg.range.map(|range| symbols.bit_range(range)).transpose()?;
+                    let size = range.map(|range| range.node.size()).unwrap_or(1);
+                    Ok(DeclareRegisterItem {
+                        ident: reg.ident,
+                        range,
+                        kind: declare_register.kind,
+                        init: match reg.init {
+                            Some(init) => {
+                                let init = Expression::build(init, symbols)?.inner;
+                                Some(init.evaluate(size).ok_or_else(|| {
+                                    InternalError("failed to evaluate initial value".to_string())
+                                })?)
+                            }
+                            None => None,
+                        },
+                        span: reg.span,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            span: declare_register.span,
+        }),
+        ast::Declaration::Bus(declare_bus) => Declaration::Bus(DeclareBus {
             buses: declare_bus
                 .buses
                 .into_iter()
                 .map(|bus| Ok(Bus::build(bus, symbols)?.inner))
                 .collect::<Result<_>>()?,
             span: declare_bus.span,
-        })),
-        ast::Declaration::Memory(declare_memory) => Ok(Declaration::Memory(DeclareMemory {
+        }),
+        ast::Declaration::Memory(declare_memory) => Declaration::Memory(DeclareMemory {
             memories: declare_memory
                 .memories
                 .into_iter()
@@ -41,20 +58,46 @@ pub fn build<'s>(
                 })
                 .collect(),
             span: declare_memory.span,
-        })),
+        }),
         ast::Declaration::RegisterArray(declare_reg_array) => {
-            Ok(Declaration::RegisterArray(DeclareRegisterArray {
+            Declaration::RegisterArray(DeclareRegisterArray {
                 register_arrays: declare_reg_array
                     .register_arrays
                     .into_iter()
-                    .map(|declare_register_array_item| DeclareRegisterArrayItem {
-                        ident: declare_register_array_item.ident,
-                        range: declare_register_array_item.range,
-                        len: declare_register_array_item.len,
+                    .map(|declare_register_array_item| {
+                        Ok(DeclareRegisterArrayItem {
+                            ident: declare_register_array_item.ident,
+                            range: declare_register_array_item
+                                .range
+                                .map(|range| symbols.bit_range(range))
+                                .transpose()?,
+                            len: symbols.const_usize(
+                                declare_register_array_item.len.node,
+                                declare_register_array_item.len.span,
+                            )?,
+                        })
                     })
-                    .collect(),
+                    .collect::<Result<_>>()?,
                 span: declare_reg_array.span,
-            }))
+            })
         }
-    }
+        ast::Declaration::Constant(declare_constant) => Declaration::Constant(DeclareConstant {
+            constants: declare_constant
+                .constants
+                .into_iter()
+                .map(|constant| {
+                    Ok(Constant {
+                        ident: constant.ident,
+                        value: symbols
+                            .constant(constant.ident.node)
+                            .ok_or_else(|| InternalError("constant not found".to_string()))?
+                            .clone(),
+                    })
+                })
+                .collect::<Result<_>>()?,
+            span: declare_constant.span,
+        }),
+    };
+
+    Ok(declaration)
 }
@@ -48,6 +48,29 @@ impl<'s> BuildExpr<ast::Atom<'s>> for Atom<'s> {
                 let concat = ConcatExpr::build(concat, symbols)?;
                 Expr { inner: Atom::Concat(concat.inner), size: concat.size }
             }
+            ast::Atom::RegBus(reg_bus) if reg_bus.range.is_none() => {
+                match symbols.constant(reg_bus.ident.node) {
+                    // Fold constant
+                    Some(value) => Expr {
+                        size: value.size(),
+                        inner: Atom::Number(Spanned {
+                            node: Number { value: value.clone(), kind: ast::NumberKind::Decimal },
+                            span: reg_bus.span,
+                        }),
+                    },
+                    None => {
+                        let reg_bus = <Either<_, _>>::build(reg_bus, symbols)?;
+                        match reg_bus.inner {
+                            Either::Left(reg) => {
+                                Expr { inner: Atom::Register(reg), size: reg_bus.size }
+                            }
+                            Either::Right(bus) => {
+                                Expr { inner: Atom::Bus(bus), size: reg_bus.size }
+                            }
+                        }
+                    }
+                }
+            }
             ast::Atom::RegBus(reg_bus) => {
                 let reg_bus = <Either<_, _>>::build(reg_bus, symbols)?;
                 match reg_bus.inner {
@@ -102,28 +125,24 @@ impl<'s> BuildExpr<ast::UnaryTerm<'s>> for UnaryTerm<'s> {
 
 impl<'s> BuildExpr<ast::RegBus<'s>> for Either<Register<'s>, Bus<'s>> {
     fn build(item: ast::RegBus<'s>, symbols: &Symbols<'_>) -> Result<Expr<Self>> {
+        let range = item.range.map(|range| symbols.bit_range(range)).transpose()?;
         match symbols.symbol(item.ident.node) {
-            Some(Symbol::Register(range, kind)) => {
-                let size = util::range_into(range, item.range)?;
+            Some(Symbol::Register(declared, kind)) => {
+                let size = util::range_into(declared, range)?;
                 Ok(Expr {
                     inner: Either::Left(Register {
                         ident: item.ident,
-                        range: item.range,
+                        range,
                         kind,
                         span: item.span,
                     }),
                     size,
                 })
             }
-            Some(Symbol::Bus(range, kind)) => {
-                let size = util::range_into(range, item.range)?;
+            Some(Symbol::Bus(declared, kind)) => {
+                let size = util::range_into(declared, range)?;
                 Ok(Expr {
-                    inner: Either::Right(Bus {
-                        ident: item.ident,
-                        range: item.range,
-                        kind,
-                        span: item.span,
-                    }),
+                    inner: Either::Right(Bus { ident: item.ident, range, kind, span: item.span }),
                     size,
                 })
             }
@@ -134,11 +153,12 @@ impl<'s> BuildExpr<ast::RegBus<'s>> for Either<Register<'s>, Bus<'s>> {
 
 impl<'s> BuildExpr<ast::RegBus<'s>> for Register<'s> {
     fn build(item: ast::RegBus<'s>, symbols: &Symbols<'_>) -> Result<Expr<Self>> {
+        let range = item.range.map(|range| symbols.bit_range(range)).transpose()?;
         match symbols.symbol(item.ident.node) {
-            Some(Symbol::Register(range, kind)) => {
-                let size = util::range_into(range, item.range)?;
+            Some(Symbol::Register(declared, kind)) => {
+                let size = util::range_into(declared, range)?;
                 Ok(Expr {
-                    inner: Register { ident: item.ident, range: item.range, kind, span: item.span },
+                    inner: Register { ident: item.ident, range, kind, span: item.span },
                     size,
                 })
             }
@@ -149,13 +169,11 @@ impl<'s> BuildExpr<ast::RegBus<'s>> for Register<'s> {
 
 impl<'s> BuildExpr<ast::RegBus<'s>> for Bus<'s> {
     fn build(item: ast::RegBus<'s>, symbols: &Symbols<'_>) -> Result<Expr<Self>> {
+        let range = item.range.map(|range| symbols.bit_range(range)).transpose()?;
         match symbols.symbol(item.ident.node) {
-            Some(Symbol::Bus(range, kind)) => {
-                let size = util::range_into(range, item.range)?;
-                Ok(Expr {
-                    inner: Bus { ident: item.ident, range: item.range, kind, span: item.span },
-                    size,
-                })
+            Some(Symbol::Bus(declared, kind)) => {
+                let size = util::range_into(declared, range)?;
+                Ok(Expr { inner: Bus { ident: item.ident, range, kind, span: item.span }, size })
             }
             _ => Err(InternalError("missing bus".to_string())),
         }
@@ -37,6 +37,16 @@ pub fn build_mir<'s>(ast: rtast::Ast<'s>, symbols: &Symbols<'s>) -> Result<Mir<'
     })
 }
 
+pub fn build_expression<'s>(
+    expression: rtast::Expression<'s>,
+    symbols: &Symbols<'s>,
+) -> Result<(Expression<'s>, usize)> {
+    use self::expression::BuildExpr;
+
+    let expression = Expression::build(expression, symbols)?;
+    Ok((expression.inner, expression.size))
+}
+
 fn build_statements<'s>(
     statements: Vec<rtast::Statement<'s>>,
     symbols: &Symbols<'s>,
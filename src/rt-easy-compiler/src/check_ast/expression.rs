@@ -115,21 +115,43 @@ impl<'s> CheckExpr<'s> for ConcatPart<'s> {
 
 impl<'s> CheckExpr<'s> for RegBus<'s> {
     fn check_expr(&self, symbols: &Symbols<'_>, error_sink: &mut impl FnMut(CompilerError)) -> Res {
+        let range = match self.range.map(|range| symbols.bit_range(range)).transpose() {
+            Ok(range) => range,
+            Err(e) => {
+                error_sink(e);
+                return Res { size: None, fixed_size: true, constant: false };
+            }
+        };
+
         let size = match symbols.symbol(self.ident.node) {
-            Some(Symbol::Register(range, _)) => match util::range_into(range, self.range) {
+            Some(Symbol::Register(declared, _)) => match util::range_into(declared, range) {
                 Ok(size) => Some(size),
                 Err(e) => {
                     error_sink(e);
                     None
                 }
             },
-            Some(Symbol::Bus(range, _)) => match util::range_into(range, self.range) {
+            Some(Symbol::Bus(declared, _)) => match util::range_into(declared, range) {
                 Ok(size) => Some(size),
                 Err(e) => {
                     error_sink(e);
                     None
                 }
             },
+            Some(Symbol::Constant) => {
+                if let Some(range) = range {
+                    error_sink(CompilerError::new(
+                        CompilerErrorKind::WrongSymbolType {
+                            expected: &[SymbolType::Register, SymbolType::Bus],
+                            found: SymbolType::Constant,
+                        },
+                        range.span,
+                    ));
+                }
+
+                let size = symbols.constant(self.ident.node).map(|value| value.size());
+                return Res { size, fixed_size: false, constant: true };
+            }
             Some(Symbol::RegisterArray { .. }) => {
                 error_sink(CompilerError::new(
                     CompilerErrorKind::RegArrayMissingIndex(self.ident.node.0.to_string()),
@@ -148,11 +170,9 @@ impl<'s> CheckExpr<'s> for RegBus<'s> {
                 None
             }
             _ => {
-                error_sink(CompilerError::new(
-                    CompilerErrorKind::SymbolNotFound(
-                        &[SymbolType::Register, SymbolType::Bus],
-                        self.ident.node.0.to_string(),
-                    ),
+                error_sink(symbols.symbol_not_found(
+                    &[SymbolType::Register, SymbolType::Bus],
+                    self.ident.node,
                     self.ident.span,
                 ));
                 None
@@ -195,11 +215,9 @@ impl<'s> CheckExpr<'s> for RegisterArray<'s> {
                 None
             }
             _ => {
-                error_sink(CompilerError::new(
-                    CompilerErrorKind::SymbolNotFound(
-                        &[SymbolType::RegisterArray],
-                        self.ident.node.0.to_string(),
-                    ),
+                error_sink(symbols.symbol_not_found(
+                    &[SymbolType::RegisterArray],
+                    self.ident.node,
                     self.ident.span,
                 ));
                 None
@@ -2,6 +2,7 @@ mod expression;
 mod operation;
 mod statements;
 
+pub use self::expression::CheckExpr;
 use crate::{symbols::Symbols, Error};
 
 pub fn check<'s>(ast: &rtast::Ast<'s>) -> Result<Symbols<'s>, Error> {
@@ -10,7 +11,7 @@ pub fn check<'s>(ast: &rtast::Ast<'s>) -> Result<Symbols<'s>, Error> {
     let mut error_sink = |e| errors.push(e);
 
     // Build symbols
-    let symbols = Symbols::build(ast, &mut error_sink);
+    let symbols = Symbols::build(ast, &mut error_sink)?;
 
     // Check statements
     statements::check(&ast.statements, &symbols, &mut error_sink)?;
@@ -22,3 +23,26 @@ pub fn check<'s>(ast: &rtast::Ast<'s>) -> Result<Symbols<'s>, Error> {
         Err(Error::Errors(errors))
     }
 }
+
+pub fn check_expression<'s>(
+    ast: &rtast::Ast<'s>,
+    expression: &rtast::Expression<'s>,
+) -> Result<Symbols<'s>, Error> {
+    // Errors
+    let mut errors = Vec::new();
+    let mut error_sink = |e| errors.push(e);
+
+    // Build symbols
+    let symbols = Symbols::build(ast, &mut error_sink)?;
+
+    // Check statements and expression
+    statements::check(&ast.statements, &symbols, &mut error_sink)?;
+    expression.check_expr(&symbols, &mut error_sink);
+
+    // Check errors
+    if errors.is_empty() {
+        Ok(symbols)
+    } else {
+        Err(Error::Errors(errors))
+    }
+}
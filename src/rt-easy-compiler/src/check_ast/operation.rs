@@ -87,10 +87,7 @@ impl<'s> CheckOp<'s> for Goto<'s> {
                 )));
             }
 
-            error_sink(CompilerError::new(
-                CompilerErrorKind::LabelNotFound(self.label.node.0.to_string()),
-                self.label.span,
-            ));
+            error_sink(symbols.label_not_found(self.label));
         }
 
         Ok(Res { contains_goto: true, contains_mutate: false })
@@ -195,11 +192,9 @@ impl<'s> CheckOp<'s> for Write<'s> {
                 },
                 self.ident.span,
             )),
-            _ => error_sink(CompilerError::new(
-                CompilerErrorKind::SymbolNotFound(
-                    &[SymbolType::Memory],
-                    self.ident.node.0.to_string(),
-                ),
+            _ => error_sink(symbols.symbol_not_found(
+                &[SymbolType::Memory],
+                self.ident.node,
                 self.ident.span,
             )),
         }
@@ -223,11 +218,9 @@ impl<'s> CheckOp<'s> for Read<'s> {
                 },
                 self.ident.span,
             )),
-            _ => error_sink(CompilerError::new(
-                CompilerErrorKind::SymbolNotFound(
-                    &[SymbolType::Memory],
-                    self.ident.node.0.to_string(),
-                ),
+            _ => error_sink(symbols.symbol_not_found(
+                &[SymbolType::Memory],
+                self.ident.node,
                 self.ident.span,
             )),
         }
@@ -275,6 +268,16 @@ impl<'s> CheckOp<'s> for Assignment<'s> {
             }
         };
 
+        // Check assign to constant
+        if let Lvalue::RegBus(reg_bus) = &self.lhs {
+            if let Some(Symbol::Constant) = symbols.symbol(reg_bus.ident.node) {
+                error_sink(CompilerError::new(
+                    CompilerErrorKind::AssignmentLhsContainsANonLvalue,
+                    self.lhs.span(),
+                ));
+            }
+        }
+
         // Check assign to input
         match &self.lhs {
             Lvalue::RegBus(reg_bus) => {
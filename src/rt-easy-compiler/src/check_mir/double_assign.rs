@@ -2,7 +2,7 @@ use super::sim::{sim, Result, SimState};
 use crate::mir::*;
 use crate::symbols::{Symbol, Symbols};
 use crate::{CompilerError, CompilerErrorKind, InternalError, SymbolType};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 pub fn check(
     symbols: &Symbols<'_>,
@@ -32,7 +32,7 @@ struct AssignTarget<'s> {
 #[derive(Debug, Clone)]
 struct AssignInfo {
     range: Option<BitRange>,
-    _span: Span,
+    span: Span,
 }
 
 impl<'s> State<'s> {
@@ -44,7 +44,7 @@ impl<'s> State<'s> {
         self.assigned
             .entry(AssignTarget { name, type_ })
             .or_default()
-            .push(AssignInfo { range, _span: span });
+            .push(AssignInfo { range, span });
     }
 }
 
@@ -140,43 +140,48 @@ impl<'s> SimState<'s> for State<'s> {
 
     fn finish(self, statement: &Statement<'s>, error_sink: &mut impl FnMut(CompilerError)) {
         for (target, infos) in self.assigned {
-            if has_conflict(infos) {
-                error_sink(CompilerError::new(
-                    CompilerErrorKind::DoubleAssign(target.type_, target.name.0.to_string()),
-                    statement.steps.span,
-                ));
+            if let Some((first, second)) = find_conflict(&infos) {
+                error_sink(
+                    CompilerError::new(
+                        CompilerErrorKind::DoubleAssign(target.type_, target.name.0.to_string()),
+                        statement.steps.span,
+                    )
+                    .with_related(first, "first assigned here")
+                    .with_related(second, "assigned again here"),
+                );
             }
         }
     }
 }
 
-fn has_conflict(infos: Vec<AssignInfo>) -> bool {
-    let mut bits_assigned = HashSet::new();
-    let mut all_bits_assigned = false;
+/// Returns the spans of the first two assignments that assign the same bit.
+fn find_conflict(infos: &[AssignInfo]) -> Option<(Span, Span)> {
+    let mut bits_assigned = HashMap::new();
+    let mut all_bits_assigned = None;
 
     for info in infos {
-        if all_bits_assigned {
-            return true;
+        if let Some(first) = all_bits_assigned {
+            return Some((first, info.span));
         }
 
         match info.range {
             Some(range) => {
                 for bit in range.bits() {
-                    let is_new = bits_assigned.insert(bit);
-                    if !is_new {
-                        return true;
+                    if let Some(first) = bits_assigned.insert(bit, info.span) {
+                        return Some((first, info.span));
                     }
                 }
             }
             None => {
                 if !bits_assigned.is_empty() {
-                    return true;
+                    // All previous assignments assigned bits, the first one conflicts
+                    return Some((infos[0].span, info.span));
                 }
 
-                all_bits_assigned = true;
+                all_bits_assigned = Some(info.span);
             }
         }
     }
 
-    false
+    None
 }
@@ -18,16 +18,16 @@ pub fn check(
 
 #[derive(Debug, Clone)]
 struct State {
-    gotos: usize,
+    gotos: Vec<Span>,
 }
 
 impl State {
     fn new() -> Self {
-        Self { gotos: 0 }
+        Self { gotos: Vec::new() }
     }
 
-    fn add_goto(&mut self) {
-        self.gotos += 1;
+    fn add_goto(&mut self, span: Span) {
+        self.gotos.push(span);
     }
 }
 
@@ -38,8 +38,8 @@ impl<'s> SimState<'s> for State {
     fn nop(&mut self, _: &Nop) -> Result {
         Ok(())
     }
-    fn goto(&mut self, _: &Goto<'s>) -> Result {
-        self.add_goto();
+    fn goto(&mut self, goto: &Goto<'s>) -> Result {
+        self.add_goto(goto.span);
         Ok(())
     }
     fn write(&mut self, _: &Write<'s>) -> Result {
@@ -59,8 +59,12 @@ impl<'s> SimState<'s> for State {
     }
 
     fn finish(self, statement: &Statement<'s>, error_sink: &mut impl FnMut(CompilerError)) {
-        if self.gotos > 1 {
-            error_sink(CompilerError::new(CompilerErrorKind::DoubleGoto, statement.steps.span));
+        if let [first, second, ..] = self.gotos[..] {
+            error_sink(
+                CompilerError::new(CompilerErrorKind::DoubleGoto, statement.steps.span)
+                    .with_related(first, "first goto")
+                    .with_related(second, "second goto"),
+            );
         }
     }
 }
@@ -1,5 +1,5 @@
 mod case_values;
-mod const_eval;
+pub mod const_eval;
 mod double_assign;
 mod double_goto;
 mod ordering;
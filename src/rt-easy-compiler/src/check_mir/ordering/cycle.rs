@@ -0,0 +1,42 @@
+use crate::mir::*;
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::{HashSet, VecDeque};
+
+/// Finds a shortest dependency cycle through `start`.
+///
+/// `dependencies` are the direct dependencies of every step. The cycle is returned in data flow
+/// order: Every step is a dependency of the next one and the last step is a dependency of the
+/// first one.
+pub fn find_cycle(
+    start: StepId,
+    dependencies: &HashMap<StepId, HashSet<StepId>>,
+) -> Option<Vec<StepId>> {
+    // Breadth first search along the dependencies back to `start`. `feeds[dep] = id` means that
+    // `id` depends on `dep`.
+    let mut feeds = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(id) = queue.pop_front() {
+        let mut deps = dependencies.get(&id)?.iter().copied().collect::<Vec<_>>();
+        deps.sort_by_key(|dep| dep.0);
+
+        for dep in deps {
+            if dep == start {
+                // `start` feeds `id`, which feeds the steps back to `start`
+                let mut cycle = vec![id];
+                let mut curr = id;
+                while curr != start {
+                    curr = feeds[&curr];
+                    cycle.push(curr);
+                }
+                return Some(cycle);
+            }
+            if let Entry::Vacant(entry) = feeds.entry(dep) {
+                entry.insert(id);
+                queue.push_back(dep);
+            }
+        }
+    }
+
+    None
+}
@@ -38,6 +38,28 @@ pub fn calc_direct_dependencies(step: &Step<'_>, others: &[Step<'_>]) -> HashSet
     ids
 }
 
+/// Returns the names of the buses assigned by `other` that `step` depends on.
+pub fn buses_read_from<'s>(step: &Step<'s>, other: &Step<'s>) -> Vec<&'s str> {
+    let mut names = match &other.operation {
+        Operation::Assignment(assignment) => match &assignment.lhs {
+            Lvalue::Bus(bus) if step.is_dependent_on(bus) => vec![bus.ident.node.0],
+            Lvalue::ConcatUnclocked(concat) => concat
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ConcatPartLvalueUnclocked::Bus(bus, _) => {
+                        step.is_dependent_on(bus).then(|| bus.ident.node.0)
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    names.dedup();
+    names
+}
+
 trait IsDependentOn {
     fn is_dependent_on(&self, bus: &Bus<'_>) -> bool;
 }
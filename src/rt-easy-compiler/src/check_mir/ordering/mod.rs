@@ -1,8 +1,10 @@
+mod cycle;
 mod deps_absolute;
 mod deps_direct;
 
 use crate::mir::*;
 use crate::{CompilerError, CompilerErrorKind, InternalError};
+use std::collections::{HashMap, HashSet};
 
 pub fn check_and_order(
     mir: &mut Mir<'_>,
@@ -28,18 +30,28 @@ fn check_and_order_(
     }
 
     // Calc absolute dependencies
+    let direct_dependencies = steps
+        .iter()
+        .map(|step| (step.id, step.annotation.dependencies.clone()))
+        .collect::<HashMap<_, _>>();
+    let mut reported_cycles = Vec::<HashSet<StepId>>::new();
     let mut has_feedback_loop = false;
     for i in 0..steps.len() {
         let res = deps_absolute::calc_absolute_dependencies(&steps[i], &*steps);
-        steps[i].annotation.dependencies = match res {
-            Ok(deps) => deps,
+        match res {
+            Ok(deps) => steps[i].annotation.dependencies = deps,
             Err(_feedback_loop) => {
                 // Set has_feedback_loop
                 has_feedback_loop = true;
-                error_sink(CompilerError::new(
-                    CompilerErrorKind::FeedbackLoop,
-                    statement.steps.span,
-                ));
+
+                // Report every cycle once
+                let cycle = cycle::find_cycle(steps[i].id, &direct_dependencies)
+                    .ok_or_else(|| InternalError("missing feedback loop cycle".to_string()))?;
+                let ids = cycle.iter().copied().collect::<HashSet<_>>();
+                if !reported_cycles.contains(&ids) {
+                    error_sink(feedback_loop_error(statement.steps.span, &cycle, steps)?);
+                    reported_cycles.push(ids);
+                }
 
                 // Continue with next step to get all errors
                 continue;
@@ -74,3 +86,55 @@ fn check_and_order_(
 
     Ok(())
 }
+
+/// Describes the cycle step by step, starting at the first assignment in the source if possible.
+fn feedback_loop_error(
+    span: Span,
+    cycle: &[StepId],
+    steps: &[Step<'_>],
+) -> Result<CompilerError, InternalError> {
+    let cycle = cycle
+        .iter()
+        .map(|id| {
+            steps
+                .iter()
+                .find(|step| step.id == *id)
+                .ok_or_else(|| InternalError(format!("missing step: {}", id.0)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let start = cycle
+        .iter()
+        .enumerate()
+        .filter(|(_, step)| matches!(step.operation, Operation::Assignment(_)))
+        .min_by_key(|(_, step)| step.span().range().start)
+        .map(|(idx, _)| idx)
+        .unwrap_or(0);
+    let cycle = cycle[start..].iter().chain(&cycle[..start]).collect::<Vec<_>>();
+
+    let mut error = CompilerError::new(CompilerErrorKind::FeedbackLoop, span);
+    let mut chain = Vec::new();
+    for (idx, step) in cycle.iter().enumerate() {
+        let next_idx = (idx + 1) % cycle.len();
+        let (node, message) = match &step.operation {
+            Operation::Assignment(_) => {
+                let buses = deps_direct::buses_read_from(cycle[next_idx], step)
+                    .iter()
+                    .map(|name| format!("bus \"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let message =
+                    format!("({}) assigns {}, read by ({})", idx + 1, buses, next_idx + 1);
+                (buses, message)
+            }
+            _ => {
+                let message = format!("({}) condition, controls ({})", idx + 1, next_idx + 1);
+                ("condition".to_string(), message)
+            }
+        };
+        chain.push(node);
+        error = error.with_related(step.span(), message);
+    }
+    chain.push(chain[0].clone());
+
+    Ok(error.with_note(format!("the cycle is: {}", chain.join(" -> "))))
+}
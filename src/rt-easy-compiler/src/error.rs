@@ -1,5 +1,6 @@
 use crate::SymbolType;
 use rtcore::common::{BitRange, Span};
+use rtcore::files::Files;
 use std::fmt;
 
 #[derive(Debug)]
@@ -11,6 +12,16 @@ pub enum Error {
 
 impl Error {
     pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        self.pretty_print_with(|error| error.pretty_print(source, file_name, ansi_colors))
+    }
+
+    /// Pretty prints the errors of a program that was parsed from multiple files. Every error is
+    /// printed with the source of the file its span belongs to.
+    pub fn pretty_print_files(&self, files: &Files, ansi_colors: bool) -> String {
+        self.pretty_print_with(|error| error.pretty_print_files(files, ansi_colors))
+    }
+
+    fn pretty_print_with(&self, pretty_print: impl Fn(&CompilerError) -> String) -> String {
         match self {
             Error::Errors(errors) => {
                 // Sort errors
@@ -23,7 +34,7 @@ impl Error {
                     if idx != 0 {
                         result += "\n\n";
                     }
-                    result += &error.pretty_print(source, file_name, ansi_colors);
+                    result += &pretty_print(error);
                 }
                 result
             }
@@ -49,29 +60,147 @@ impl From<InternalError> for Error {
 pub struct CompilerError {
     pub kind: CompilerErrorKind,
     pub span: Span,
+    /// Other locations involved in the error, e.g. the first declaration of a duplicate symbol.
+    pub related: Vec<RelatedSpan>,
+    /// Notes specific to this error, shown after the general note of the kind.
+    pub notes: Vec<String>,
+    /// Hints specific to this error, e.g. a suggestion for a misspelled name.
+    pub help: Vec<String>,
+    /// The macro calls that produced the span, innermost first.
+    pub expansions: Vec<ExpansionSite>,
+}
+
+/// A secondary location of an error together with a short description.
+#[derive(Debug, Clone)]
+pub struct RelatedSpan {
+    pub span: Span,
+    pub message: String,
+}
+
+/// The call site of a macro expansion.
+#[derive(Debug, Clone)]
+pub struct ExpansionSite {
+    pub name: String,
+    pub call_site: Span,
 }
 
 impl CompilerError {
     pub fn new(kind: CompilerErrorKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            related: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+            expansions: Vec::new(),
+        }
+    }
+
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push(RelatedSpan { span, message: message.into() });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Resolves the macro calls that produced the span of this error.
+    pub(crate) fn with_expansions(mut self, expansions: &[rtast::Expansion<'_>]) -> Self {
+        let mut expansion = self.span.expansion;
+        while let Some(id) = expansion {
+            let site = &expansions[id.0];
+            self.expansions
+                .push(ExpansionSite { name: site.name.0.to_string(), call_site: site.call_site });
+            expansion = site.call_site.expansion;
+        }
+        self
     }
 
     pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        self.pretty_print_(|_| (Some(source), file_name), ansi_colors)
+    }
+
+    pub fn pretty_print_files(&self, files: &Files, ansi_colors: bool) -> String {
+        self.pretty_print_(
+            |span| match files.get(span.file) {
+                Some(file) => (Some(&file.source), Some(&file.name)),
+                None => (None, None),
+            },
+            ansi_colors,
+        )
+    }
+
+    fn pretty_print_<'a>(
+        &self,
+        file: impl Fn(Span) -> (Option<&'a str>, Option<&'a str>),
+        ansi_colors: bool,
+    ) -> String {
         let message = self.kind.to_string();
         let error_code = format!("[E{:03}]", self.kind.code());
+        let mut error =
+            error_at(&message, self.span, &file, ansi_colors).with_error_code(&error_code);
+        for related in &self.related {
+            if let (Some(source), file_name) = file(related.span) {
+                let mut label = pretty_error::Label::new(
+                    &related.message,
+                    source,
+                    pretty_error::Span::Range(related.span.range()),
+                );
+                if let Some(file_name) = file_name {
+                    label = label.with_file_name(file_name);
+                }
+                error = error.with_label(label);
+            }
+        }
+        if let Some(note) = self.kind.note() {
+            error = error.with_note(note);
+        }
+        for note in &self.notes {
+            error = error.with_note(note);
+        }
+        if let Some(help) = self.kind.help() {
+            error = error.with_help(help);
+        }
+        for help in &self.help {
+            error = error.with_help(help);
+        }
+        let mut result = error.to_string();
 
-        let mut error = pretty_error::Error::new(&message)
-            .with_error_code(&error_code)
-            .with_source(source, pretty_error::Span::Range(self.span.range()))
-            .with_ansi_colors(ansi_colors);
-        if let Some(file_name) = file_name {
-            error = error.with_file_name(file_name);
+        for site in &self.expansions {
+            let message = format!("in this expansion of macro \"{}\"", site.name);
+            result += "\n";
+            result += &error_at(&message, site.call_site, &file, ansi_colors).to_string();
         }
 
-        error.to_string()
+        result
     }
 }
 
+fn error_at<'m, 'a: 'm>(
+    message: &'m str,
+    span: Span,
+    file: impl Fn(Span) -> (Option<&'a str>, Option<&'a str>),
+    ansi_colors: bool,
+) -> pretty_error::Error<'m> {
+    let (source, file_name) = file(span);
+    let mut error = pretty_error::Error::new(message).with_ansi_colors(ansi_colors);
+    if let Some(source) = source {
+        error = error.with_source(source, pretty_error::Span::Range(span.range()));
+    }
+    if let Some(file_name) = file_name {
+        error = error.with_file_name(file_name);
+    }
+
+    error
+}
+
 #[derive(Debug)]
 pub enum CompilerErrorKind {
     DuplicateSymbol(String),
@@ -102,9 +231,15 @@ pub enum CompilerErrorKind {
     DoubleGoto,
     RegisterArrayTooManyReads { name: String, allowed: usize },
     FeedbackLoop,
+    ConstantTooLarge(String),
+    InitialValueDoesNotFit { size: usize, value_size: usize },
 }
 
 impl CompilerErrorKind {
+    /// The highest code returned by [`code`](Self::code), the codes are numbered from 1 without
+    /// gaps. Increase it when adding a kind with a new code.
+    pub const MAX_CODE: usize = 30;
+
     pub fn code(&self) -> usize {
         use CompilerErrorKind::*;
 
@@ -137,6 +272,35 @@ impl CompilerErrorKind {
             DoubleGoto => 26,
             RegisterArrayTooManyReads { .. } => 27,
             FeedbackLoop => 28,
+            ConstantTooLarge(_) => 29,
+            InitialValueDoesNotFit { .. } => 30,
+        }
+    }
+
+    /// Additional explanation shown below the message.
+    pub fn note(&self) -> Option<&'static str> {
+        use CompilerErrorKind::*;
+
+        match self {
+            FeedbackLoop => Some(
+                "buses take their new value in the same clock cycle, so a bus must not depend on itself",
+            ),
+            DoubleGoto => Some("only one goto can be executed per clock cycle"),
+            _ => None,
+        }
+    }
+
+    /// A hint on how to fix the error.
+    pub fn help(&self) -> Option<&'static str> {
+        use CompilerErrorKind::*;
+
+        match self {
+            DoubleAssign(..) => {
+                Some("assign disjoint bit ranges or move one assignment into another statement")
+            }
+            DoubleGoto => Some("use if or switch to select one of the gotos"),
+            FeedbackLoop => Some("store one of the values in a register to break the loop"),
+            _ => None,
         }
     }
 }
@@ -228,6 +392,12 @@ impl fmt::Display for CompilerErrorKind {
                 write!(f, "register array \"{}\" is read more than {} times", name, allowed)
             }
             FeedbackLoop => write!(f, "statement has a feedback loop"),
+            ConstantTooLarge(name) => {
+                write!(f, "constant \"{}\" is too large to be used as a bit index or length", name)
+            }
+            InitialValueDoesNotFit { size, value_size } => {
+                write!(f, "initial value is too wide: {} > {}", value_size, size)
+            }
         }
     }
 }
@@ -0,0 +1,72 @@
+const ERROR_INDEX: &str = include_str!("../../../web/book/src/compiler-error-index/errors.md");
+
+/// Returns the explanation of the error with the given code, as found in the compiler error
+/// index of the book. Code fences are reduced to plain code blocks and hidden lines are shown.
+pub fn explain(code: usize) -> Option<String> {
+    let heading = format!("## E{:03}", code);
+
+    let lines = ERROR_INDEX.lines().skip_while(|line| line.trim_end() != heading).skip(1);
+    let mut explanation = String::new();
+    let mut in_code = false;
+    for line in lines {
+        if !in_code && line.starts_with("## ") {
+            break;
+        }
+
+        if line.starts_with("```") {
+            in_code = !in_code;
+            explanation += "```";
+        } else if in_code && line.trim_start().starts_with('~') {
+            let (ws, rest) = line.split_once('~').unwrap();
+            explanation += ws;
+            explanation += rest;
+        } else {
+            explanation += line;
+        }
+        explanation += "\n";
+    }
+
+    let explanation = explanation.trim();
+    if explanation.is_empty() {
+        None
+    } else {
+        Some(explanation.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CompilerErrorKind;
+
+    #[test]
+    fn every_code_has_explanation() {
+        for code in 1..=CompilerErrorKind::MAX_CODE {
+            let explanation =
+                explain(code).unwrap_or_else(|| panic!("missing explanation for E{:03}", code));
+            assert!(!explanation.contains("TODO"), "incomplete explanation for E{:03}", code);
+        }
+    }
+
+    #[test]
+    fn max_code() {
+        let kind = CompilerErrorKind::InitialValueDoesNotFit { size: 0, value_size: 0 };
+        assert_eq!(kind.code(), CompilerErrorKind::MAX_CODE);
+        assert_eq!(explain(CompilerErrorKind::MAX_CODE + 1), None);
+    }
+
+    #[test]
+    fn explanation_contains_examples() {
+        let explanation = explain(12).unwrap();
+        assert!(explanation.starts_with("This error occurs"));
+        assert!(explanation.contains("### Examples"));
+        assert!(explanation.contains("\n```\ndeclare register X(7:0), I(1:0), J(3:0)\n"));
+        assert!(!explanation.contains("## E013"));
+    }
+
+    #[test]
+    fn unknown_code() {
+        assert_eq!(explain(0), None);
+        assert_eq!(explain(999), None);
+    }
+}
@@ -1,14 +1,22 @@
 #![deny(rust_2018_idioms)]
+// Compiler errors carry related spans and macro expansions, they are only created on failure
+#![allow(clippy::result_large_err)]
 
 mod build_mir;
 mod check_ast;
 mod check_mir;
 mod error;
+mod explain;
+mod suggest;
 mod symbols;
 mod util;
 
 pub mod mir;
-pub use self::error::{BackendError, CompilerError, CompilerErrorKind, Error, InternalError};
+pub use self::error::{
+    BackendError, CompilerError, CompilerErrorKind, Error, ExpansionSite, InternalError,
+    RelatedSpan,
+};
+pub use self::explain::explain;
 pub use self::symbols::SymbolType;
 
 pub trait Backend {
@@ -47,16 +55,55 @@ pub fn check(ast: rtast::Ast<'_>, options: &Options) -> Result<(), Error> {
     Ok(())
 }
 
+/// Checks an ast that the parser recovered from syntax errors. Errors that may be caused by the
+/// statements the parser skipped (unknown labels) are not reported.
+pub fn check_partial(ast: rtast::Ast<'_>, options: &Options) -> Result<(), Error> {
+    match check_(ast, options) {
+        Ok(_) => Ok(()),
+        Err(Error::Errors(errors)) => {
+            let errors = errors
+                .into_iter()
+                .filter(|error| !matches!(error.kind, CompilerErrorKind::LabelNotFound(_)))
+                .collect::<Vec<_>>();
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(Error::Errors(errors))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks a single expression against the declarations in `ast` and builds it.
+///
+/// Returns the expression and its size in bits.
+pub fn compile_expression<'s>(
+    ast: &rtast::Ast<'s>,
+    expression: rtast::Expression<'s>,
+) -> Result<(mir::Expression<'s>, usize), Error> {
+    let symbols = check_ast::check_expression(ast, &expression)?;
+    Ok(build_mir::build_expression(expression, &symbols)?)
+}
+
 fn check_<'s>(
     ast: rtast::Ast<'s>,
     options: &Options,
 ) -> Result<(symbols::Symbols<'s>, mir::Mir<'s>), Error> {
+    let expansions = ast.expansions.clone();
+    let with_expansions = |error: Error| match error {
+        Error::Errors(errors) => Error::Errors(
+            errors.into_iter().map(|error| error.with_expansions(&expansions)).collect(),
+        ),
+        error => error,
+    };
+
     // Check ast
-    let symbols = check_ast::check(&ast)?;
+    let symbols = check_ast::check(&ast).map_err(with_expansions)?;
 
     // Build and check mir
-    let mut mir = build_mir::build_mir(ast, &symbols)?;
-    check_mir::check(&symbols, &mut mir, options)?;
+    let mut mir = build_mir::build_mir(ast, &symbols).map_err(|e| with_expansions(e.into()))?;
+    check_mir::check(&symbols, &mut mir, options).map_err(with_expansions)?;
 
     Ok((symbols, mir))
 }
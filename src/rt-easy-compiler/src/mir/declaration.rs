@@ -1,4 +1,5 @@
 use super::*;
+use rtcore::value::Value;
 
 #[derive(Debug, Clone)]
 pub enum Declaration<'s> {
@@ -6,6 +7,7 @@ pub enum Declaration<'s> {
     Bus(DeclareBus<'s>),
     Memory(DeclareMemory<'s>),
     RegisterArray(DeclareRegisterArray<'s>),
+    Constant(DeclareConstant<'s>),
 }
 
 impl Declaration<'_> {
@@ -15,13 +17,23 @@ impl Declaration<'_> {
             Self::Bus(n) => n.span,
             Self::Memory(n) => n.span,
             Self::RegisterArray(n) => n.span,
+            Self::Constant(n) => n.span,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct DeclareRegister<'s> {
-    pub registers: Vec<Register<'s>>,
+    pub registers: Vec<DeclareRegisterItem<'s>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeclareRegisterItem<'s> {
+    pub ident: Spanned<Ident<'s>>,
+    pub range: Option<Spanned<BitRange>>,
+    pub kind: RegisterKind,
+    pub init: Option<Value>,
     pub span: Span,
 }
 
@@ -63,3 +75,16 @@ pub struct DeclareRegisterArrayItem<'s> {
     pub range: Option<Spanned<BitRange>>,
     pub len: usize,
 }
+
+/// Constants are folded into the expressions, their declarations only keep the values.
+#[derive(Debug, Clone)]
+pub struct DeclareConstant<'s> {
+    pub constants: Vec<Constant<'s>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Constant<'s> {
+    pub ident: Spanned<Ident<'s>>,
+    pub value: Value,
+}
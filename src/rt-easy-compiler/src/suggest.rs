@@ -0,0 +1,33 @@
+//! "Did you mean" suggestions for unknown symbols and labels.
+
+use std::cmp;
+
+/// Returns the candidate closest to `name`: A case-insensitive match or the candidate with the
+/// smallest edit distance (transpositions count as one edit), if it is close enough. Names shorter
+/// than three characters only match case-insensitively, since every other name of the same
+/// length would be close enough. Ties are broken by name, so the result does not depend on the
+/// order of `candidates`.
+pub fn closest<'a, T>(
+    name: &str,
+    candidates: impl IntoIterator<Item = (&'a str, T)>,
+) -> Option<(&'a str, T)> {
+    let len = name.chars().count();
+    let max_distance = if len < 3 { 0 } else { cmp::min(cmp::max(1, len / 3), len - 1) };
+
+    let mut best: Option<(usize, &'a str, T)> = None;
+    for (candidate, value) in candidates {
+        let distance = if candidate.eq_ignore_ascii_case(name) {
+            0
+        } else {
+            strsim::damerau_levenshtein(name, candidate)
+        };
+        if distance > max_distance {
+            continue;
+        }
+        if best.as_ref().map_or(true, |(d, c, _)| (distance, candidate) < (*d, *c)) {
+            best = Some((distance, candidate, value));
+        }
+    }
+
+    best.map(|(_, candidate, value)| (candidate, value))
+}
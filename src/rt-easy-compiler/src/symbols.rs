@@ -1,18 +1,32 @@
-use crate::{CompilerError, CompilerErrorKind};
-use std::collections::{HashMap, HashSet};
-use std::fmt;
+use crate::check_ast::CheckExpr;
+use crate::check_mir::const_eval::Evaluate;
+use crate::{build_mir, suggest, CompilerError, CompilerErrorKind, InternalError};
+use rtast::{BitRange, BitRangeExpr, ConstUsize, Span, Spanned};
+use rtcore::value::Value;
+use std::collections::hash_map::{Entry, HashMap};
+use std::{cmp, fmt};
 
 const MAX_BIT_RANGE_SIZE: usize = u16::MAX as usize;
 const MAX_BIT_RANGE_SIZE_ADDRESS_REGISTER: usize = 64;
 
+/// Constant expressions are evaluated with at least this many bits, so that intermediate results
+/// (e.g. `WIDTH + WIDTH`) do not overflow.
+const MIN_CONSTANT_EVAL_SIZE: usize = 64;
+
 #[derive(Debug, Default)]
 pub struct Symbols<'s> {
     symbols: HashMap<rtast::Ident<'s>, Symbol<'s>>,
-    labels: HashSet<rtast::Label<'s>>,
+    /// The span of the first declaration of every symbol.
+    declared_at: HashMap<rtast::Ident<'s>, Span>,
+    constants: HashMap<rtast::Ident<'s>, Value>,
+    labels: HashMap<rtast::Label<'s>, Span>,
 }
 
 impl<'s> Symbols<'s> {
-    pub fn build(ast: &rtast::Ast<'s>, error_sink: &mut impl FnMut(CompilerError)) -> Self {
+    pub fn build(
+        ast: &rtast::Ast<'s>,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) -> Result<Self, InternalError> {
         let mut symbols = Self::default();
 
         // Check declarations
@@ -20,76 +34,27 @@ impl<'s> Symbols<'s> {
             match declaration {
                 rtast::Declaration::Register(declare_register) => {
                     for reg in &declare_register.registers {
-                        if symbols
-                            .symbols
-                            .insert(
-                                reg.ident.node,
-                                Symbol::Register(reg.range.map(|s| s.node), declare_register.kind),
-                            )
-                            .is_some()
-                        {
-                            error_sink(CompilerError::new(
-                                CompilerErrorKind::DuplicateSymbol(reg.ident.node.0.to_string()),
-                                reg.ident.span,
-                            ));
-                        }
-
-                        if let Some(range) = reg.range {
-                            let size = range.node.size();
-                            if size > MAX_BIT_RANGE_SIZE {
-                                error_sink(CompilerError::new(
-                                    CompilerErrorKind::BitRangeTooWide {
-                                        max_size: MAX_BIT_RANGE_SIZE,
-                                        size,
-                                    },
-                                    range.span,
-                                ));
-                            }
+                        let range = symbols.declared_range(reg.range, error_sink);
+                        if let Some(init) = &reg.init {
+                            let size = range.unwrap_or_default().size();
+                            symbols.check_initial_value(init, size, error_sink);
                         }
+                        symbols.insert(
+                            reg.ident,
+                            Symbol::Register(range, declare_register.kind),
+                            error_sink,
+                        );
                     }
                 }
                 rtast::Declaration::Bus(declare_bus) => {
                     for bus in &declare_bus.buses {
-                        if symbols
-                            .symbols
-                            .insert(
-                                bus.ident.node,
-                                Symbol::Bus(bus.range.map(|s| s.node), declare_bus.kind),
-                            )
-                            .is_some()
-                        {
-                            error_sink(CompilerError::new(
-                                CompilerErrorKind::DuplicateSymbol(bus.ident.node.0.to_string()),
-                                bus.ident.span,
-                            ));
-                        }
-
-                        if let Some(range) = bus.range {
-                            let size = range.node.size();
-                            if size > MAX_BIT_RANGE_SIZE {
-                                error_sink(CompilerError::new(
-                                    CompilerErrorKind::BitRangeTooWide {
-                                        max_size: MAX_BIT_RANGE_SIZE,
-                                        size,
-                                    },
-                                    range.span,
-                                ));
-                            }
-                        }
+                        let range = symbols.declared_range(bus.range, error_sink);
+                        symbols.insert(bus.ident, Symbol::Bus(range, declare_bus.kind), error_sink);
                     }
                 }
                 rtast::Declaration::Memory(declare_memory) => {
                     for memory in &declare_memory.memories {
-                        if symbols
-                            .symbols
-                            .insert(memory.ident.node, Symbol::Memory(memory.range))
-                            .is_some()
-                        {
-                            error_sink(CompilerError::new(
-                                CompilerErrorKind::DuplicateSymbol(memory.ident.node.0.to_string()),
-                                memory.ident.span,
-                            ));
-                        }
+                        symbols.insert(memory.ident, Symbol::Memory(memory.range), error_sink);
 
                         for (mem_reg, is_ar) in [
                             (&memory.range.address_register, true),
@@ -115,11 +80,9 @@ impl<'s> Symbols<'s> {
                                     },
                                     mem_reg.span,
                                 )),
-                                None => error_sink(CompilerError::new(
-                                    CompilerErrorKind::SymbolNotFound(
-                                        &[SymbolType::Register],
-                                        mem_reg.node.0.to_string(),
-                                    ),
+                                None => error_sink(symbols.symbol_not_found(
+                                    &[SymbolType::Register],
+                                    mem_reg.node,
                                     mem_reg.span,
                                 )),
                             }
@@ -128,7 +91,17 @@ impl<'s> Symbols<'s> {
                 }
                 rtast::Declaration::RegisterArray(declare_register_array) => {
                     for reg_array in &declare_register_array.register_arrays {
-                        if !reg_array.len.is_power_of_two() {
+                        let range = symbols.declared_range(reg_array.range, error_sink);
+                        let len = match symbols.const_usize(reg_array.len.node, reg_array.len.span)
+                        {
+                            Ok(len) => len,
+                            Err(e) => {
+                                error_sink(e);
+                                1
+                            }
+                        };
+
+                        if !len.is_power_of_two() {
                             error_sink(CompilerError::new(
                                 CompilerErrorKind::RegArrayLenNotPowerOfTwo(
                                     reg_array.ident.node.0.to_string(),
@@ -137,63 +110,78 @@ impl<'s> Symbols<'s> {
                             ));
                         }
 
-                        if symbols
-                            .symbols
-                            .insert(
-                                reg_array.ident.node,
-                                Symbol::RegisterArray {
-                                    range: reg_array.range.map(|s| s.node),
-                                    len: reg_array.len,
-                                },
-                            )
-                            .is_some()
-                        {
-                            error_sink(CompilerError::new(
-                                CompilerErrorKind::DuplicateSymbol(
-                                    reg_array.ident.node.0.to_string(),
-                                ),
-                                reg_array.ident.span,
-                            ));
-                        }
+                        symbols.insert(
+                            reg_array.ident,
+                            Symbol::RegisterArray { range, len },
+                            error_sink,
+                        );
+                    }
+                }
+                rtast::Declaration::Constant(declare_constant) => {
+                    for constant in &declare_constant.constants {
+                        // Evaluate before inserting, a constant can only reference constants
+                        // declared before it
+                        let value = symbols.evaluate_constant(&constant.value, error_sink)?;
 
-                        if let Some(range) = reg_array.range {
-                            let size = range.node.size();
-                            if size > MAX_BIT_RANGE_SIZE {
-                                error_sink(CompilerError::new(
-                                    CompilerErrorKind::BitRangeTooWide {
-                                        max_size: MAX_BIT_RANGE_SIZE,
-                                        size,
-                                    },
-                                    range.span,
-                                ));
-                            }
-                        }
+                        symbols.insert(constant.ident, Symbol::Constant, error_sink);
+                        symbols
+                            .constants
+                            .insert(constant.ident.node, value.unwrap_or_else(|| Value::zero(1)));
                     }
                 }
             }
         }
 
         // Check labels
-        for statement in &ast.statements {
-            if let Some(label) = statement.label {
-                if !symbols.labels.insert(label.node) {
-                    error_sink(CompilerError::new(
-                        CompilerErrorKind::DuplicateLabel(label.node.0.to_string()),
-                        label.span,
-                    ));
-                }
-            }
+        for label in ast.statements.iter().filter_map(|statement| statement.label) {
+            symbols.insert_label(label, error_sink);
         }
         if let Some(label) = ast.trailing_label {
-            if !symbols.labels.insert(label.node) {
-                error_sink(CompilerError::new(
+            symbols.insert_label(label, error_sink);
+        }
+
+        Ok(symbols)
+    }
+
+    /// Inserts a symbol, a duplicate replaces the previous symbol and is reported.
+    fn insert(
+        &mut self,
+        ident: Spanned<rtast::Ident<'s>>,
+        symbol: Symbol<'s>,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) {
+        self.symbols.insert(ident.node, symbol);
+        match self.declared_at.entry(ident.node) {
+            Entry::Occupied(first) => error_sink(
+                CompilerError::new(
+                    CompilerErrorKind::DuplicateSymbol(ident.node.0.to_string()),
+                    ident.span,
+                )
+                .with_related(*first.get(), "first declared here"),
+            ),
+            Entry::Vacant(entry) => {
+                entry.insert(ident.span);
+            }
+        }
+    }
+
+    fn insert_label(
+        &mut self,
+        label: Spanned<rtast::Label<'s>>,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) {
+        match self.labels.entry(label.node) {
+            Entry::Occupied(first) => error_sink(
+                CompilerError::new(
                     CompilerErrorKind::DuplicateLabel(label.node.0.to_string()),
                     label.span,
-                ));
+                )
+                .with_related(*first.get(), "first defined here"),
+            ),
+            Entry::Vacant(entry) => {
+                entry.insert(label.span);
             }
         }
-
-        symbols
     }
 
     pub fn symbol(&self, ident: rtast::Ident<'s>) -> Option<Symbol<'s>> {
@@ -201,7 +189,199 @@ impl<'s> Symbols<'s> {
     }
 
     pub fn contains_label(&self, label: rtast::Label<'s>) -> bool {
-        self.labels.contains(&label)
+        self.labels.contains_key(&label)
+    }
+
+    /// Creates a `SymbolNotFound` error with a suggestion for a similar name, if there is one.
+    pub fn symbol_not_found(
+        &self,
+        expected: &'static [SymbolType],
+        ident: rtast::Ident<'s>,
+        span: Span,
+    ) -> CompilerError {
+        let error = CompilerError::new(
+            CompilerErrorKind::SymbolNotFound(expected, ident.0.to_string()),
+            span,
+        );
+
+        // A symbol of the expected type
+        let candidates = self
+            .symbols
+            .iter()
+            .filter(|(_, symbol)| expected.contains(&symbol.type_()))
+            .map(|(ident, symbol)| (ident.0, symbol.type_()));
+        if let Some((name, _)) = suggest::closest(ident.0, candidates) {
+            return error.with_help(format!("did you mean \"{}\"?", name));
+        }
+
+        // A symbol of another type or a label
+        let candidates = self
+            .symbols
+            .iter()
+            .map(|(ident, symbol)| (ident.0, symbol.type_().to_string()))
+            .chain(self.labels.keys().map(|label| (label.0, "label".to_string())));
+        match suggest::closest(ident.0, candidates) {
+            Some((name, type_)) => {
+                error.with_help(format!("there is a {} named \"{}\"", type_, name))
+            }
+            None => error,
+        }
+    }
+
+    /// Creates a `LabelNotFound` error with a suggestion for a similar name, if there is one.
+    pub fn label_not_found(&self, label: Spanned<rtast::Label<'s>>) -> CompilerError {
+        let error = CompilerError::new(
+            CompilerErrorKind::LabelNotFound(label.node.0.to_string()),
+            label.span,
+        );
+
+        // Labels of other macro expansions are not visible
+        let candidates = self
+            .labels
+            .keys()
+            .filter(|candidate| candidate.1 == label.node.1)
+            .map(|candidate| (candidate.0, ()));
+        if let Some((name, _)) = suggest::closest(label.node.0, candidates) {
+            return error.with_help(format!("did you mean \"{}\"?", name));
+        }
+
+        let candidates = self.symbols.iter().map(|(ident, symbol)| (ident.0, symbol.type_()));
+        match suggest::closest(label.node.0, candidates) {
+            Some((name, type_)) => {
+                error.with_help(format!("there is a {} named \"{}\", but no label", type_, name))
+            }
+            None => error,
+        }
+    }
+
+    /// Returns the value of the constant `ident`.
+    pub fn constant(&self, ident: rtast::Ident<'s>) -> Option<&Value> {
+        self.constants.get(&ident)
+    }
+
+    /// Resolves the constants in `range`.
+    pub fn bit_range(
+        &self,
+        range: Spanned<BitRangeExpr<'s>>,
+    ) -> Result<Spanned<BitRange>, CompilerError> {
+        Ok(Spanned {
+            node: BitRange {
+                msb: self.const_usize(range.node.msb, range.span)?,
+                lsb: match range.node.lsb {
+                    Some(lsb) => Some(self.const_usize(lsb, range.span)?),
+                    None => None,
+                },
+            },
+            span: range.span,
+        })
+    }
+
+    /// Resolves `value` to a number. `span` is used for errors.
+    pub fn const_usize(&self, value: ConstUsize<'s>, span: Span) -> Result<usize, CompilerError> {
+        let ident = match value {
+            ConstUsize::Number(value) => return Ok(value),
+            ConstUsize::Constant(ident) => ident,
+        };
+
+        match (self.symbol(ident), self.constant(ident)) {
+            (Some(Symbol::Constant), Some(value)) => usize::from_str_radix(&value.as_bin(false), 2)
+                .map_err(|_| {
+                    CompilerError::new(
+                        CompilerErrorKind::ConstantTooLarge(ident.0.to_string()),
+                        span,
+                    )
+                }),
+            (Some(symbol), _) => Err(CompilerError::new(
+                CompilerErrorKind::WrongSymbolType {
+                    expected: &[SymbolType::Constant],
+                    found: symbol.type_(),
+                },
+                span,
+            )),
+            (None, _) => Err(self.symbol_not_found(&[SymbolType::Constant], ident, span)),
+        }
+    }
+
+    /// Resolves and checks the bit range of a register, bus or register array declaration.
+    fn declared_range(
+        &self,
+        range: Option<Spanned<BitRangeExpr<'s>>>,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) -> Option<BitRange> {
+        let range = match self.bit_range(range?) {
+            Ok(range) => range,
+            Err(e) => {
+                error_sink(e);
+                return None;
+            }
+        };
+
+        let size = range.node.size();
+        if size > MAX_BIT_RANGE_SIZE {
+            error_sink(CompilerError::new(
+                CompilerErrorKind::BitRangeTooWide { max_size: MAX_BIT_RANGE_SIZE, size },
+                range.span,
+            ));
+        }
+
+        Some(range.node)
+    }
+
+    /// Checks that the initial value of a register is constant and fits into `size` bits.
+    fn check_initial_value(
+        &self,
+        init: &rtast::Expression<'s>,
+        size: usize,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) {
+        let res = init.check_expr(self, error_sink);
+        if !res.constant {
+            error_sink(CompilerError::new(
+                CompilerErrorKind::ExpectedConstantExpression,
+                init.span(),
+            ));
+        }
+        if let Some(value_size) = res.size {
+            if value_size > size {
+                error_sink(CompilerError::new(
+                    CompilerErrorKind::InitialValueDoesNotFit { size, value_size },
+                    init.span(),
+                ));
+            }
+        }
+    }
+
+    /// Checks and evaluates the value of a constant declaration.
+    ///
+    /// Returns `Ok(None)` if the expression contains errors, they are reported to `error_sink`.
+    fn evaluate_constant(
+        &self,
+        expression: &rtast::Expression<'s>,
+        error_sink: &mut impl FnMut(CompilerError),
+    ) -> Result<Option<Value>, InternalError> {
+        let mut has_errors = false;
+        let res = expression.check_expr(self, &mut |e| {
+            has_errors = true;
+            error_sink(e);
+        });
+        if has_errors {
+            return Ok(None);
+        }
+        if !res.constant {
+            error_sink(CompilerError::new(
+                CompilerErrorKind::ExpectedConstantExpression,
+                expression.span(),
+            ));
+            return Ok(None);
+        }
+
+        let (expression, size) = build_mir::build_expression(expression.clone(), self)?;
+        let mut value = expression
+            .evaluate(cmp::max(size, MIN_CONSTANT_EVAL_SIZE))
+            .ok_or_else(|| InternalError("failed to evaluate constant".to_string()))?;
+        value.remove_leading_zeros();
+
+        Ok(Some(value))
     }
 }
 
@@ -211,6 +391,7 @@ pub enum Symbol<'s> {
     Bus(Option<rtast::BitRange>, rtast::BusKind),
     Memory(rtast::MemoryRange<'s>),
     RegisterArray { range: Option<rtast::BitRange>, len: usize },
+    Constant,
 }
 
 impl Symbol<'_> {
@@ -220,6 +401,7 @@ impl Symbol<'_> {
             Self::Bus(_, _) => SymbolType::Bus,
             Self::Memory(_) => SymbolType::Memory,
             Self::RegisterArray { .. } => SymbolType::RegisterArray,
+            Self::Constant => SymbolType::Constant,
         }
     }
 }
@@ -230,6 +412,7 @@ pub enum SymbolType {
     Bus,
     Memory,
     RegisterArray,
+    Constant,
 }
 
 impl fmt::Display for SymbolType {
@@ -239,6 +422,7 @@ impl fmt::Display for SymbolType {
             Self::Bus => write!(f, "bus"),
             Self::Memory => write!(f, "memory"),
             Self::RegisterArray => write!(f, "register array"),
+            Self::Constant => write!(f, "constant"),
         }
     }
 }
@@ -21,6 +21,7 @@ pub fn size_binary_op(lhs: usize, rhs: usize, op: BinaryOperator) -> usize {
         | BinaryOperator::Or
         | BinaryOperator::Nor
         | BinaryOperator::Xor => cmp::max(lhs, rhs),
+        BinaryOperator::Mul => lhs + rhs,
     }
 }
 
@@ -39,6 +40,7 @@ pub fn ctx_size_binary_op(lhs: usize, rhs: usize, op: BinaryOperator) -> CtxSize
         | BinaryOperator::Or
         | BinaryOperator::Nor
         | BinaryOperator::Xor => CtxSize::Inherit,
+        BinaryOperator::Mul => CtxSize::Size(lhs + rhs),
     }
 }
 
@@ -49,7 +51,8 @@ pub fn is_fixed_size_binary_op(op: BinaryOperator) -> bool {
         | BinaryOperator::Le
         | BinaryOperator::Lt
         | BinaryOperator::Ge
-        | BinaryOperator::Gt => true,
+        | BinaryOperator::Gt
+        | BinaryOperator::Mul => true,
         BinaryOperator::Add
         | BinaryOperator::Sub
         | BinaryOperator::And
@@ -124,6 +127,10 @@ pub fn concat_info(concat: &Concat<'_>, symbols: &Symbols<'_>) -> ConcatInfo {
             ConcatPart::RegBus(reg_bus) => match symbols.symbol(reg_bus.ident.node) {
                 Some(Symbol::Register(..)) => info.contains_clocked = true,
                 Some(Symbol::Bus(..)) => info.contains_unclocked = true,
+                Some(Symbol::Constant) => {
+                    info.contains_non_lvalue = true;
+                    info.contains_number_non_bit_string = true;
+                }
                 _ => (),
             },
             ConcatPart::RegisterArray(_) => info.contains_clocked = true,
@@ -0,0 +1,113 @@
+mod util;
+
+use rt_easy_compiler::{CompilerErrorKind, Error, SymbolType};
+
+#[test]
+fn constant() {
+    const SOURCE: &str = r#"
+        declare constant WIDTH = 16, MSB = WIDTH - 1, LEN = 2 * 4
+        declare constant MASK = 0x00FF, DOUBLE = WIDTH + WIDTH
+        declare register A(MSB:0), B(MSB:8), C(DOUBLE)
+        declare bus BUS(MSB:0)
+        declare register array ARR(MSB:0)[LEN]
+
+        A <- B(MSB:8) and MASK, BUS <- WIDTH;
+        ARR[2] <- A(MSB) + DOUBLE;
+        if A(MSB:8) = MASK then nop fi;
+    "#;
+
+    util::check(SOURCE);
+}
+
+type IsExpected = fn(&CompilerErrorKind) -> bool;
+
+#[test]
+fn constant_errors() {
+    const SOURCES: &[(&str, IsExpected)] = &[
+        (
+            r#"
+                declare register A(3:0)
+                declare constant C = A + 1
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::ExpectedConstantExpression),
+        ),
+        (
+            r#"
+                declare constant C = D + 1, D = 1
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::SymbolNotFound(_, name) if name == "D"),
+        ),
+        (
+            r#"
+                declare register A(3:0)
+                declare register B(A:0)
+            "#,
+            |kind| {
+                matches!(
+                    kind,
+                    CompilerErrorKind::WrongSymbolType {
+                        expected: [SymbolType::Constant],
+                        found: SymbolType::Register,
+                    }
+                )
+            },
+        ),
+        (
+            r#"
+                declare constant C = 0xFFFFFFFFFFFFFFFFFF
+                declare register A(C:0)
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::ConstantTooLarge(name) if name == "C"),
+        ),
+        (
+            r#"
+                declare constant C = 3
+                declare register A(3:0)
+
+                C <- A;
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::AssignmentLhsContainsANonLvalue),
+        ),
+        (
+            r#"
+                declare constant C = 3
+                declare register A(3:0)
+
+                A <- C(1:0);
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::WrongSymbolType { .. }),
+        ),
+        (
+            r#"
+                declare constant C = 3
+                declare register A(3:0)
+
+                A <- A(1:0).C;
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::ConcatContainsNumberNonBitString),
+        ),
+        (
+            r#"
+                declare constant C = 3
+                declare register array ARR(3:0)[C]
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::RegArrayLenNotPowerOfTwo(_)),
+        ),
+        (
+            r#"
+                declare constant C = 3
+                declare register C(3:0)
+            "#,
+            |kind| matches!(kind, CompilerErrorKind::DuplicateSymbol(_)),
+        ),
+    ];
+
+    for (source, expected) in SOURCES {
+        match util::check_err(source) {
+            Error::Errors(errors) => {
+                assert!(errors.iter().any(|e| expected(&e.kind)), "{}: {:?}", source, errors)
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
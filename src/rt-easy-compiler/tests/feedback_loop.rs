@@ -51,3 +51,67 @@ fn no_feeback_loop_bus_to_bus() {
 
     util::check(SOURCE);
 }
+
+#[test]
+fn feedback_loop_cycle() {
+    const SOURCE: &'static str = "declare bus A, B, C, D\nD <- 1, A <- C, B <- A, C <- B;";
+
+    let error = util::check_err(SOURCE);
+    match &error {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            let related = errors[0]
+                .related
+                .iter()
+                .map(|related| related.message.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                related,
+                [
+                    "(1) assigns bus \"A\", read by (2)",
+                    "(2) assigns bus \"B\", read by (3)",
+                    "(3) assigns bus \"C\", read by (1)",
+                ]
+            );
+            assert_eq!(
+                errors[0].notes,
+                ["the cycle is: bus \"A\" -> bus \"B\" -> bus \"C\" -> bus \"A\""]
+            );
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    assert_eq!(
+        error.pretty_print(SOURCE, None, false),
+        r#" --> 2:1
+  |
+2 |    D <- 1, A <- C, B <- A, C <- B;
+  |    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |            ------ (1) assigns bus "A", read by (2)
+  |                    ------ (2) assigns bus "B", read by (3)
+  |                            ------ (3) assigns bus "C", read by (1)
+  |
+  = statement has a feedback loop [E028]
+  = note: buses take their new value in the same clock cycle, so a bus must not depend on itself
+  = note: the cycle is: bus "A" -> bus "B" -> bus "C" -> bus "A"
+  = help: store one of the values in a register to break the loop"#
+    );
+}
+
+#[test]
+fn feedback_loop_cycle_condition() {
+    const SOURCE: &'static str = r#"
+        declare bus B
+        if B(0) then B <- 0 fi;
+    "#;
+
+    match util::check_err(SOURCE) {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].related.len(), 2);
+            assert_eq!(errors[0].related[1].message, "(2) condition, controls (1)");
+            assert_eq!(errors[0].notes, ["the cycle is: bus \"B\" -> condition -> bus \"B\""]);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
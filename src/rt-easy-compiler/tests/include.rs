@@ -0,0 +1,66 @@
+use rt_easy_compiler::{CompilerErrorKind, Error};
+use rtcore::{common::FileId, files::Files};
+
+#[test]
+fn error_in_included_file() {
+    let mut files = Files::new();
+    let root = files.add("main.rt", "include \"decl.rt\"\ndeclare register X\nX <- 1;");
+    files.add("decl.rt", "declare register X(3:0)");
+
+    let ast = parser::parse_files(&files, root).unwrap();
+    let error = rt_easy_compiler::check(ast, &Default::default()).unwrap_err();
+    match &error {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(errors[0].kind, CompilerErrorKind::DuplicateSymbol(_)));
+            assert_eq!(errors[0].span.file, FileId(0));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    assert_eq!(
+        error.pretty_print_files(&files, false),
+        r#" --> main.rt:2:18
+  |
+2 |    declare register X
+  |                     ^
+  |
+ ::: decl.rt:1:18
+  |
+1 |    declare register X(3:0)
+  |                     - first declared here
+  |
+  = duplicate symbol "X" [E001]"#
+    );
+}
+
+#[test]
+fn errors_in_multiple_files() {
+    let mut files = Files::new();
+    let root = files.add("main.rt", "include \"lib/decl.rt\"\nX <- Y;");
+    files.add("lib/decl.rt", "declare register X(3:0), Z(70000:0)");
+
+    let ast = parser::parse_files(&files, root).unwrap();
+    let error = rt_easy_compiler::check(ast, &Default::default()).unwrap_err();
+    let printed = error.pretty_print_files(&files, false);
+
+    let main = printed.find("--> main.rt:2:6").expect(&printed);
+    let lib = printed.find("--> lib/decl.rt:1:27").expect(&printed);
+    assert!(main < lib);
+}
+
+#[test]
+fn error_in_included_macro() {
+    let mut files = Files::new();
+    let root = files.add("main.rt", "include \"lib.rt\"\nCLEAR;");
+    files.add("lib.rt", "declare register X(3:0)\ndefine CLEAR { X <- 42; }");
+
+    let ast = parser::parse_files(&files, root).unwrap();
+    let error = rt_easy_compiler::check(ast, &Default::default()).unwrap_err();
+    let printed = error.pretty_print_files(&files, false);
+
+    let body = printed.find("--> lib.rt:2:16").expect(&printed);
+    let call_site = printed.find("--> main.rt:2:1").expect(&printed);
+    assert!(body < call_site);
+    assert!(printed.ends_with("= in this expansion of macro \"CLEAR\""));
+}
@@ -0,0 +1,67 @@
+mod util;
+
+use rt_easy_compiler::{CompilerErrorKind, Error};
+
+#[test]
+fn expanded_labels_are_hygienic() {
+    util::check(
+        r#"
+        declare register A(7:0)
+        define WAIT { LOOP: if A <> 0 then A <- A - 1, goto LOOP fi; }
+        WAIT; WAIT;
+        LOOP: goto LOOP;
+        "#,
+    );
+}
+
+#[test]
+fn expanded_labels_are_not_visible_outside() {
+    match util::check_err("define M { nop; L: nop; } M; goto L;") {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(errors[0].kind, CompilerErrorKind::LabelNotFound(_)));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn error_in_expansion() {
+    const SOURCE: &str = "declare register A(3:0)
+define SET(R) { R <- 42; }
+define INIT { SET(A); }
+INIT;";
+
+    let error = util::check_err(SOURCE);
+    match &error {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].expansions.len(), 2);
+            assert_eq!(errors[0].expansions[0].name, "SET");
+            assert_eq!(errors[0].expansions[1].name, "INIT");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    assert_eq!(
+        error.pretty_print(SOURCE, None, false),
+        r#" --> 2:17
+  |
+2 |    define SET(R) { R <- 42; }
+  |                    ^^^^^^^
+  |
+  = right-hand side is too wide: 6 > 4 [E011]
+ --> 3:15
+  |
+3 |    define INIT { SET(A); }
+  |                  ^^^^^^
+  |
+  = in this expansion of macro "SET"
+ --> 4:1
+  |
+4 |    INIT;
+  |    ^^^^
+  |
+  = in this expansion of macro "INIT""#
+    );
+}
@@ -0,0 +1,41 @@
+mod util;
+
+use rt_easy_compiler::{CompilerErrorKind, Error};
+
+#[test]
+fn mul() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), B(3:0), X(11:0)
+        declare bus C(1:0)
+
+        X <- A * B;
+        X <- (A * B) + 3;
+        X <- sxt A * sxt B;
+        switch A * C {
+            case 1 * 2: nop
+            default: nop
+        };
+    "#;
+
+    util::check(SOURCE);
+}
+
+#[test]
+fn mul_too_wide() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), B(3:0), X(10:0)
+
+        X <- A * B;
+    "#;
+
+    match util::check_err(SOURCE) {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(
+                errors[0].kind,
+                CompilerErrorKind::AssignmentDoesNotFit { lhs_size: 11, rhs_size: 12 }
+            ));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
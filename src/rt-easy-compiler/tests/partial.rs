@@ -0,0 +1,45 @@
+use rt_easy_compiler::{CompilerErrorKind, Error};
+
+#[test]
+fn partial() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), B(7:0)
+
+        A <- ;
+        B <- A + 1, goto LOOP;
+        LOOP: A <- C;
+    "#;
+
+    let ast = match parser::parse(SOURCE) {
+        Ok(_) => panic!("expected syntax error"),
+        Err(e) => *e.partial.expect("expected partial ast"),
+    };
+
+    match rt_easy_compiler::check_partial(ast, &Default::default()) {
+        Err(Error::Errors(errors)) => {
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+            match &errors[0].kind {
+                CompilerErrorKind::SymbolNotFound(_, name) => assert_eq!(name, "C"),
+                other => panic!("unexpected error: {:?}", other),
+            }
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn partial_missing_label() {
+    const SOURCE: &str = r#"
+        declare register A(7:0)
+
+        LOOP: A <- ;
+        A <- A + 1, goto LOOP;
+    "#;
+
+    let ast = match parser::parse(SOURCE) {
+        Ok(_) => panic!("expected syntax error"),
+        Err(e) => *e.partial.expect("expected partial ast"),
+    };
+
+    rt_easy_compiler::check_partial(ast, &Default::default()).unwrap();
+}
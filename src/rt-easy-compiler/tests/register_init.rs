@@ -0,0 +1,54 @@
+mod util;
+
+use rt_easy_compiler::{CompilerErrorKind, Error};
+
+#[test]
+fn register_init() {
+    const SOURCE: &str = r#"
+        declare constant INIT = 3
+        declare register PC(7:0) = 0x10, A(3:0) = INIT + 1, B = 1, C(7:0)
+        declare output OUT(3:0) = 0b1010
+
+        PC <- PC + 1;
+    "#;
+
+    util::check(SOURCE);
+}
+
+#[test]
+fn register_init_errors() {
+    const SOURCES: &[&str] = &[
+        r#"
+            declare register PC(3:0) = 0x10
+        "#,
+        r#"
+            declare register PC(3:0) = 20
+        "#,
+        r#"
+            declare register A(3:0), PC(3:0) = A
+        "#,
+    ];
+
+    match util::check_err(SOURCES[0]) {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(
+                errors[0].kind,
+                CompilerErrorKind::InitialValueDoesNotFit { size: 4, value_size: 5 }
+            ));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    match util::check_err(SOURCES[1]) {
+        Error::Errors(errors) => {
+            assert!(matches!(errors[0].kind, CompilerErrorKind::InitialValueDoesNotFit { .. }));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    match util::check_err(SOURCES[2]) {
+        Error::Errors(errors) => {
+            assert!(matches!(errors[0].kind, CompilerErrorKind::ExpectedConstantExpression));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
@@ -0,0 +1,73 @@
+mod util;
+
+#[test]
+fn duplicate_symbol() {
+    const SOURCE: &str = "declare register A, B\ndeclare bus A\nA <- 1;";
+
+    assert_eq!(
+        util::check_err(SOURCE).pretty_print(SOURCE, None, false),
+        r#" --> 2:13
+  |
+1 |    declare register A, B
+  |                     - first declared here
+2 |    declare bus A
+  |                ^
+  |
+  = duplicate symbol "A" [E001]"#
+    );
+}
+
+#[test]
+fn duplicate_label() {
+    const SOURCE: &str = "declare register A(1:0)\nL: A <- 1;\nA <- 2;\nL: A <- 3;";
+
+    assert_eq!(
+        util::check_err(SOURCE).pretty_print(SOURCE, None, false),
+        r#" --> 4:1
+  |
+2 |    L: A <- 1;
+  |    - first defined here
+...
+4 |    L: A <- 3;
+  |    ^
+  |
+  = duplicate label "L" [E004]"#
+    );
+}
+
+#[test]
+fn double_assign() {
+    const SOURCE: &str = "declare register A(7:0)\nA(3:0) <- 1, A(7:4) <- 2, A(0) <- 1;";
+
+    assert_eq!(
+        util::check_err(SOURCE).pretty_print(SOURCE, None, false),
+        r#" --> 2:1
+  |
+2 |    A(3:0) <- 1, A(7:4) <- 2, A(0) <- 1;
+  |    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |    ----------- first assigned here
+  |                              --------- assigned again here
+  |
+  = register "A" is assigned more than once [E025]
+  = help: assign disjoint bit ranges or move one assignment into another statement"#
+    );
+}
+
+#[test]
+fn double_goto() {
+    const SOURCE: &str = "declare register A\nL: A <- 1, goto L, goto L;";
+
+    assert_eq!(
+        util::check_err(SOURCE).pretty_print(SOURCE, None, false),
+        r#" --> 2:4
+  |
+2 |    L: A <- 1, goto L, goto L;
+  |       ^^^^^^^^^^^^^^^^^^^^^^
+  |               ------ first goto
+  |                       ------ second goto
+  |
+  = statement contains multiple gotos on at least one possible execution path [E026]
+  = note: only one goto can be executed per clock cycle
+  = help: use if or switch to select one of the gotos"#
+    );
+}
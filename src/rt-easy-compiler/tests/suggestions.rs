@@ -0,0 +1,65 @@
+mod util;
+
+use rt_easy_compiler::{CompilerErrorKind, Error};
+
+fn help(source: &str) -> Vec<String> {
+    match util::check_err(source) {
+        Error::Errors(errors) => {
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+            match &errors[0].kind {
+                CompilerErrorKind::SymbolNotFound(..) | CompilerErrorKind::LabelNotFound(_) => (),
+                other => panic!("unexpected error: {:?}", other),
+            }
+            errors[0].help.clone()
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn typo() {
+    assert_eq!(help("declare register COUNTER(7:0)\nCOUTNER <- 1;"), ["did you mean \"COUNTER\"?"]);
+    assert_eq!(help("declare register ABC, B\nABC <- ABD;"), ["did you mean \"ABC\"?"]);
+}
+
+#[test]
+fn typo_label() {
+    assert_eq!(help("declare register A\nLOOP: A <- 1, goto LOPO;"), ["did you mean \"LOOP\"?"]);
+}
+
+#[test]
+fn wrong_kind() {
+    assert_eq!(
+        help("declare register AR, DR\ndeclare memory MEM(AR, DR)\nAR <- MEMS;"),
+        ["there is a memory named \"MEM\""]
+    );
+    assert_eq!(help("declare register A\nLOOP: A <- LOOP;"), ["there is a label named \"LOOP\""]);
+    assert_eq!(
+        help("declare register AR, DR, COUNT\nCOUNT <- 0, goto COUNT;"),
+        ["there is a register named \"COUNT\", but no label"]
+    );
+}
+
+#[test]
+fn no_suggestion() {
+    assert!(help("declare register COUNTER(7:0)\nCOUNTER <- X;").is_empty());
+    assert!(help("declare register A\nA <- 1, goto END;").is_empty());
+    assert!(help("declare register A\nA <- Q;").is_empty());
+    assert!(help("declare register AB\nAB <- AC;").is_empty());
+}
+
+#[test]
+fn suggestion_in_pretty_print() {
+    const SOURCE: &str = "declare bus INPUT_BUS(7:0)\nINPUT_BUS <- INPUT_BSU;";
+
+    assert_eq!(
+        util::check_err(SOURCE).pretty_print(SOURCE, None, false),
+        r#" --> 2:14
+  |
+2 |    INPUT_BUS <- INPUT_BSU;
+  |                 ^^^^^^^^^
+  |
+  = no register or bus named "INPUT_BSU" found [E005]
+  = help: did you mean "INPUT_BUS"?"#
+    );
+}
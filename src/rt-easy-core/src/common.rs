@@ -19,6 +19,7 @@ pub enum BinaryOperator {
     Gt,
     Add,
     Sub,
+    Mul,
     And,
     Nand,
     Or,
@@ -30,6 +31,7 @@ impl BinaryOperator {
     pub fn precedence(&self) -> u32 {
         use BinaryOperator::*;
         match self {
+            Mul => 9,
             Add | Sub => 8,
             Le | Lt | Ge | Gt => 7,
             Eq | Ne => 6,
@@ -44,7 +46,7 @@ impl BinaryOperator {
     pub fn associativity(&self) -> OperatorAssociativity {
         use BinaryOperator::*;
         match self {
-            Eq | Ne | Le | Lt | Ge | Gt | Add | Sub | And | Nand | Or | Nor | Xor => {
+            Eq | Ne | Le | Lt | Ge | Gt | Add | Sub | Mul | And | Nand | Or | Nor | Xor => {
                 OperatorAssociativity::Left
             }
         }
@@ -63,6 +65,7 @@ impl fmt::Display for BinaryOperator {
             Gt => write!(f, ">"),
             Add => write!(f, "+"),
             Sub => write!(f, "-"),
+            Mul => write!(f, "*"),
             And => write!(f, "and"),
             Nand => write!(f, "nand"),
             Or => write!(f, "or"),
@@ -84,8 +87,8 @@ impl UnaryOperator {
     pub fn precedence(&self) -> u32 {
         use UnaryOperator::*;
         match self {
-            Sign | Neg => 10,
-            Sxt => 9,
+            Sign | Neg => 11,
+            Sxt => 10,
             Not => 5,
         }
     }
@@ -234,20 +237,37 @@ impl CtxSize {
     }
 }
 
+/// Identifies a source file in a [`Files`](crate::files::Files) set.
+///
+/// Code parsed from a single source always belongs to the default file `FileId(0)`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(pub usize);
+
+/// Identifies a macro expansion. Spans of code that was produced by expanding a macro carry the id
+/// of that expansion, the ast records the call site of every expansion.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExpansionId(pub usize);
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
+    pub file: FileId,
+    pub expansion: Option<ExpansionId>,
 }
 
 impl Span {
     pub fn dummy() -> Self {
-        Self { start: 0, end: 0 }
+        Self { start: 0, end: 0, file: FileId::default(), expansion: None }
     }
 
     pub fn range(self) -> Range<usize> {
         self.start..self.end
     }
+
+    pub fn with_file(self, file: FileId) -> Self {
+        Self { file, ..self }
+    }
 }
 
 impl PartialOrd for Span {
@@ -258,16 +278,17 @@ impl PartialOrd for Span {
 
 impl Ord for Span {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.start.cmp(&other.start) {
-            Ordering::Equal => self.end.cmp(&other.end),
-            ordering => ordering,
-        }
+        self.file
+            .cmp(&other.file)
+            .then_with(|| self.start.cmp(&other.start))
+            .then_with(|| self.end.cmp(&other.end))
+            .then_with(|| self.expansion.cmp(&other.expansion))
     }
 }
 
 impl From<Range<usize>> for Span {
     fn from(range: Range<usize>) -> Self {
-        Self { start: range.start, end: range.end }
+        Self { start: range.start, end: range.end, file: FileId::default(), expansion: None }
     }
 }
 
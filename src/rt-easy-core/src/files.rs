@@ -0,0 +1,93 @@
+use crate::common::FileId;
+
+/// A set of named source files.
+///
+/// Files are identified by their [`FileId`], which is also stored in every
+/// [`Span`](crate::common::Span). File names use `/` as separator, included files are resolved
+/// relative to the including file (see [`resolve_path`]).
+#[derive(Debug, Default, Clone)]
+pub struct Files {
+    files: Vec<File>,
+}
+
+#[derive(Debug, Clone)]
+pub struct File {
+    pub name: String,
+    pub source: String,
+}
+
+impl Files {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file to the set. If a file with the same name already exists, its source is
+    /// replaced and the existing id is returned.
+    pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        let name = name.into();
+        let source = source.into();
+
+        match self.find(&name) {
+            Some(id) => {
+                self.files[id.0].source = source;
+                id
+            }
+            None => {
+                self.files.push(File { name, source });
+                FileId(self.files.len() - 1)
+            }
+        }
+    }
+
+    pub fn get(&self, id: FileId) -> Option<&File> {
+        self.files.get(id.0)
+    }
+
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files.iter().position(|file| file.name == name).map(FileId)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &File)> + '_ {
+        self.files.iter().enumerate().map(|(idx, file)| (FileId(idx), file))
+    }
+}
+
+/// Resolves `path` relative to the directory of the file `from`.
+///
+/// Absolute paths are kept as is. `.` and `..` components are removed where possible, e.g.
+/// `../common/decl.rt` included from `src/main.rt` resolves to `common/decl.rt`.
+pub fn resolve_path(from: &str, path: &str) -> String {
+    let from = from.replace('\\', "/");
+    let path = path.replace('\\', "/");
+
+    let joined = if path.starts_with('/') {
+        path
+    } else {
+        match from.rfind('/') {
+            Some(idx) => format!("{}/{}", &from[..idx], path),
+            None => path,
+        }
+    };
+
+    let mut components: Vec<&str> = Vec::new();
+    for component in joined.split('/') {
+        match component {
+            "." => (),
+            "" if !components.is_empty() => (),
+            ".." if matches!(components.last(), Some(last) if *last != ".." && !last.is_empty()) => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+
+    components.join("/")
+}
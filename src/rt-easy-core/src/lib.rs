@@ -1,6 +1,7 @@
 #![deny(rust_2018_idioms)]
 
 pub mod common;
+pub mod files;
 pub mod util;
 
 pub use value;
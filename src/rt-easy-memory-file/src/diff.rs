@@ -0,0 +1,98 @@
+use crate::{MemoryFile, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Differences between two memories, sorted by address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDiff {
+    ar_size: usize,
+    dr_size: usize,
+    entries: Vec<DiffEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub address: Value,
+    pub kind: DiffKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The address is only set in the new memory.
+    Added { new: Value },
+    /// The address is set in both memories.
+    Changed { old: Value, new: Value },
+    /// The address is only set in the old memory.
+    Cleared { old: Value },
+}
+
+impl MemoryFile {
+    /// Compares `self` (old) with `new`. Unset addresses read as zero, so an address that is unset
+    /// in one memory and zero in the other is not a difference. Memories of different sizes are
+    /// compared as if they were zero extended to the larger size.
+    pub fn diff(&self, new: &MemoryFile) -> MemoryDiff {
+        let ar_size = self.ar_size.max(new.ar_size);
+        let dr_size = self.dr_size.max(new.dr_size);
+        let extend = |value: &Value, size: usize| {
+            let mut value = value.clone();
+            value.extend_zero(size);
+            value
+        };
+        let old_data = self
+            .data
+            .iter()
+            .map(|(address, value)| (extend(address, ar_size), extend(value, dr_size)))
+            .collect::<HashMap<_, _>>();
+        let new_data = new
+            .data
+            .iter()
+            .map(|(address, value)| (extend(address, ar_size), extend(value, dr_size)))
+            .collect::<HashMap<_, _>>();
+
+        let addresses = old_data.keys().chain(new_data.keys()).collect::<BTreeSet<_>>();
+        let entries = addresses
+            .into_iter()
+            .filter_map(|address| {
+                let kind = match (old_data.get(address), new_data.get(address)) {
+                    (Some(old), Some(new)) if old != new => {
+                        DiffKind::Changed { old: old.clone(), new: new.clone() }
+                    }
+                    (None, Some(new)) if !new.is_zero() => DiffKind::Added { new: new.clone() },
+                    (Some(old), None) if !old.is_zero() => DiffKind::Cleared { old: old.clone() },
+                    _ => return None,
+                };
+                Some(DiffEntry { address: address.clone(), kind })
+            })
+            .collect();
+
+        MemoryDiff { ar_size, dr_size, entries }
+    }
+}
+
+impl MemoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[DiffEntry] {
+        &self.entries
+    }
+}
+
+/// One line per address, e.g. `0A: 01 -> 02`. Unset values are written as `unset`.
+impl fmt::Display for MemoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = |value: &Value, size: usize| format!("{:0>1$}", value.as_hex(), (size + 3) / 4);
+
+        for entry in &self.entries {
+            let (old, new) = match &entry.kind {
+                DiffKind::Added { new } => ("unset".to_string(), hex(new, self.dr_size)),
+                DiffKind::Changed { old, new } => (hex(old, self.dr_size), hex(new, self.dr_size)),
+                DiffKind::Cleared { old } => (hex(old, self.dr_size), "unset".to_string()),
+            };
+            writeln!(f, "{}: {} -> {}", hex(&entry.address, self.ar_size), old, new)?;
+        }
+
+        Ok(())
+    }
+}
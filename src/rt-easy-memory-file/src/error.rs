@@ -0,0 +1,166 @@
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// Line of the offending text, starting at 1. Binary sources have no lines, the line is always
+    /// 1 and the column is the byte offset plus 1.
+    pub line: usize,
+    /// Column of the offending text, starting at 1.
+    pub column: usize,
+    /// The offending text. Bytes of binary sources are written as hex.
+    pub text: String,
+    /// Byte range of the offending text in the source.
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidUtf8,
+    BadHeader,
+    UnknownRadix,
+    SizeMismatch { expected: (usize, usize), actual: (usize, usize) },
+    AddressTooWide { ar_size: usize },
+    ValueTooWide { dr_size: usize },
+    BadDigit,
+    InvalidSyntax,
+    ChecksumMismatch,
+    IncompleteWord { word_bytes: usize },
+    WordTooSmall { word_bytes: usize },
+    UnknownLabel,
+    DuplicateLabel,
+    CountTooLarge,
+    EndOfMemory,
+    TooManyValues,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, source: &str, span: Range<usize>) -> Self {
+        let before = &source[..span.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+        let text = source[span.clone()].to_string();
+
+        Self { kind, line, column, text, span }
+    }
+
+    pub(crate) fn binary(kind: ParseErrorKind, source: &[u8], span: Range<usize>) -> Self {
+        let text = source[span.clone()]
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self { kind, line: 1, column: span.start + 1, text, span }
+    }
+
+    /// Renders the error with the offending line of `source`. Binary sources are not shown.
+    pub fn pretty_print(
+        &self,
+        source: &[u8],
+        file_name: Option<&str>,
+        ansi_colors: bool,
+    ) -> String {
+        let message = self.kind.to_string();
+        let mut error = pretty_error::Error::new(&message).with_ansi_colors(ansi_colors);
+
+        // The text of binary sources is written as hex and never matches the source
+        let is_text = source.get(self.span.clone()) == Some(self.text.as_bytes());
+        match std::str::from_utf8(source) {
+            Ok(source) if is_text && self.kind != ParseErrorKind::InvalidUtf8 => {
+                let span = if self.span.start >= source.len() {
+                    pretty_error::Span::Eoi
+                } else {
+                    pretty_error::Span::Range(self.span.clone())
+                };
+                error = error.with_source(source, span);
+                if let Some(file_name) = file_name {
+                    error = error.with_file_name(file_name);
+                }
+                error.to_string()
+            }
+            _ => {
+                let location = format!("at byte {}: {}", self.span.start, self.text);
+                error.with_note(&location).to_string()
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.kind, self.line, self.column)?;
+        if !self.text.is_empty() {
+            write!(f, ": \"{}\"", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidUtf8 => write!(f, "memory file is not valid utf-8"),
+            ParseErrorKind::BadHeader => write!(f, "invalid header"),
+            ParseErrorKind::UnknownRadix => write!(f, "unknown radix"),
+            ParseErrorKind::SizeMismatch { expected, actual } => write!(
+                f,
+                "invalid memory size. expected: (AR: {}, DR: {}), actual: (AR: {}, DR: {})",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            ParseErrorKind::AddressTooWide { ar_size } => {
+                write!(f, "address does not fit into the address register ({} bits)", ar_size)
+            }
+            ParseErrorKind::ValueTooWide { dr_size } => {
+                write!(f, "value does not fit into the data register ({} bits)", dr_size)
+            }
+            ParseErrorKind::BadDigit => write!(f, "invalid digit"),
+            ParseErrorKind::InvalidSyntax => write!(f, "invalid syntax"),
+            ParseErrorKind::ChecksumMismatch => write!(f, "checksum mismatch"),
+            ParseErrorKind::IncompleteWord { word_bytes } => {
+                write!(f, "incomplete word, expected {} bytes per word", word_bytes)
+            }
+            ParseErrorKind::WordTooSmall { word_bytes } => {
+                write!(f, "words of {} bytes can not hold the data register", word_bytes)
+            }
+            ParseErrorKind::UnknownLabel => write!(f, "unknown label"),
+            ParseErrorKind::DuplicateLabel => write!(f, "duplicate label"),
+            ParseErrorKind::CountTooLarge => {
+                write!(f, "count exceeds the addresses left in the memory")
+            }
+            ParseErrorKind::EndOfMemory => write!(f, "no address left in the memory"),
+            ParseErrorKind::TooManyValues => write!(f, "more values than addresses in the range"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportError {
+    /// `Options::word_bytes` is smaller than the data register.
+    WordTooSmall { word_bytes: usize },
+    /// An address does not fit into the address range of the format.
+    AddressOutOfRange,
+    /// A dense format would contain more than `max` bytes or words.
+    TooLarge { len: u64, max: u64 },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::WordTooSmall { word_bytes } => {
+                write!(f, "words of {} bytes can not hold the data register", word_bytes)
+            }
+            ExportError::AddressOutOfRange => {
+                write!(f, "address does not fit into the address range of the format")
+            }
+            ExportError::TooLarge { len, max } => {
+                write!(f, "memory would take {} bytes or words, at most {} are supported", len, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
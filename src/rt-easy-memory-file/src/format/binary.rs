@@ -0,0 +1,42 @@
+use super::{bytes_from_words, check_dense_len, import_word_bytes, words_from_bytes, Options};
+use crate::error::{ExportError, ParseError, ParseErrorKind::*};
+use crate::MemoryFile;
+
+pub fn import(
+    source: &[u8],
+    ar_size: usize,
+    dr_size: usize,
+    options: &Options,
+) -> Result<MemoryFile, ParseError> {
+    let word_bytes = import_word_bytes(source, dr_size, options)?;
+    let incomplete = source.len() % word_bytes;
+    if incomplete != 0 {
+        let span = source.len() - incomplete..source.len();
+        return Err(ParseError::binary(IncompleteWord { word_bytes }, source, span));
+    }
+
+    words_from_bytes(
+        source.iter().enumerate().map(|(offset, byte)| (offset as u64, *byte, offset..offset + 1)),
+        ar_size,
+        dr_size,
+        word_bytes,
+        options.endianness,
+    )
+    .map_err(|(kind, span)| ParseError::binary(kind, source, span))
+}
+
+/// The image starts at address zero, unset words are zero.
+pub fn export(mem: &MemoryFile, options: &Options) -> Result<Vec<u8>, ExportError> {
+    let bytes = bytes_from_words(mem, options)?;
+
+    let len = match bytes.last() {
+        Some((offset, _)) => check_dense_len(offset.saturating_add(1))?,
+        None => return Ok(Vec::new()),
+    };
+    let mut result = vec![0; len];
+    for (offset, byte) in bytes {
+        result[offset as usize] = byte;
+    }
+
+    Ok(result)
+}
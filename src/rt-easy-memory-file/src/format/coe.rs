@@ -0,0 +1,93 @@
+use super::{address_count, check_dense_len, from_utf8, Locator, Radix};
+use crate::error::{ExportError, ParseError, ParseErrorKind::*};
+use crate::{MemoryFile, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub fn import(source: &[u8], ar_size: usize, dr_size: usize) -> Result<MemoryFile, ParseError> {
+    let source = from_utf8(source)?;
+
+    // Blank comment lines, statements are terminated by semicolons
+    let text = source
+        .split_inclusive('\n')
+        .map(|line| match line.trim_start().starts_with(';') {
+            true => line.replace(|c: char| c != '\n', " "),
+            false => line.to_string(),
+        })
+        .collect::<String>();
+    let locator = Locator::new(source, &text);
+
+    let mut radix = Radix::Dec;
+    let mut vector = None;
+    for statement in text.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (key, value) =
+            statement.split_once('=').ok_or_else(|| locator.error(InvalidSyntax, statement))?;
+        match key.trim().to_ascii_lowercase().as_str() {
+            "memory_initialization_radix" => {
+                radix = match value.trim() {
+                    "2" => Radix::Bin,
+                    "8" => Radix::Oct,
+                    "10" => Radix::Dec,
+                    "16" => Radix::Hex,
+                    _ => return Err(locator.error(UnknownRadix, value.trim())),
+                }
+            }
+            "memory_initialization_vector" => vector = Some(value),
+            _ => return Err(locator.error(InvalidSyntax, key.trim())),
+        }
+    }
+
+    // The vector starts at address zero
+    let values = vector
+        .ok_or_else(|| locator.error_at_end(InvalidSyntax))?
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| Ok((value, locator.value(radix, value, dr_size)?)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(count) = address_count(ar_size) {
+        if let Some((value, _)) = values.get(count as usize) {
+            return Err(locator.error(AddressTooWide { ar_size }, value));
+        }
+    }
+
+    let mut address = Value::zero(ar_size);
+    let mut data = HashMap::new();
+    for (_, value) in values {
+        let next = &address + Value::one(ar_size);
+        data.insert(address, value);
+        address = next;
+    }
+
+    Ok(MemoryFile { ar_size, dr_size, data })
+}
+
+/// The vector starts at address zero, unset words are zero.
+pub fn export(mem: &MemoryFile) -> Result<String, ExportError> {
+    let mut result = String::new();
+    writeln!(result, "memory_initialization_radix=16;").unwrap();
+    write!(result, "memory_initialization_vector=").unwrap();
+
+    let last = match mem.data.keys().max() {
+        Some(last) => last,
+        None => {
+            writeln!(result, ";").unwrap();
+            return Ok(result);
+        }
+    };
+    let last_u64 = last.to_u64().ok_or(ExportError::AddressOutOfRange)?;
+    check_dense_len(last_u64.saturating_add(1))?;
+    let zero = Value::zero(mem.dr_size);
+    let mut address = Value::zero(mem.ar_size);
+    loop {
+        let value = mem.data.get(&address).unwrap_or(&zero);
+        write!(result, "\n{}", Radix::Hex.format(value, mem.dr_size)).unwrap();
+        if address == *last {
+            break;
+        }
+        result.push(',');
+        address = address + Value::one(mem.ar_size);
+    }
+    writeln!(result, ";").unwrap();
+
+    Ok(result)
+}
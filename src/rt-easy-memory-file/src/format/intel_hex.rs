@@ -0,0 +1,127 @@
+use super::{bytes_from_words, from_utf8, import_word_bytes, words_from_bytes, Locator, Options};
+use crate::error::{ExportError, ParseError, ParseErrorKind::*};
+use crate::{span_of, MemoryFile};
+use std::fmt::Write;
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+const BYTES_PER_RECORD: usize = 16;
+
+pub fn import(
+    source: &[u8],
+    ar_size: usize,
+    dr_size: usize,
+    options: &Options,
+) -> Result<MemoryFile, ParseError> {
+    let word_bytes = import_word_bytes(source, dr_size, options)?;
+    let source = from_utf8(source)?;
+    let locator = Locator::new(source, source);
+
+    let mut bytes = Vec::new();
+    let mut base = 0u64;
+    for line in source.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // Decode record
+        let hex = line.strip_prefix(':').ok_or_else(|| locator.error(InvalidSyntax, line))?;
+        if hex.len() % 2 != 0 {
+            return Err(locator.error(InvalidSyntax, line));
+        }
+        let record = (0..hex.len())
+            .step_by(2)
+            .map(|idx| {
+                let digits = hex.get(idx..idx + 2).ok_or_else(|| locator.error(BadDigit, line))?;
+                u8::from_str_radix(digits, 16).map_err(|_| locator.error(BadDigit, digits))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if record.len() < 5 || record.len() != record[0] as usize + 5 {
+            return Err(locator.error(InvalidSyntax, line));
+        }
+        if record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(locator.error(ChecksumMismatch, line));
+        }
+
+        let offset = u64::from(u16::from_be_bytes([record[1], record[2]]));
+        let data = &record[4..record.len() - 1];
+        match record[3] {
+            DATA => {
+                let data_start = span_of(source, hex).start + 8;
+                bytes.extend(data.iter().enumerate().map(|(idx, byte)| {
+                    // The offset wraps around inside of a segment
+                    let span = data_start + idx * 2..data_start + idx * 2 + 2;
+                    (base + ((offset + idx as u64) & 0xFFFF), *byte, span)
+                }));
+            }
+            END_OF_FILE => break,
+            EXTENDED_SEGMENT_ADDRESS if data.len() == 2 => {
+                base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 4;
+            }
+            EXTENDED_LINEAR_ADDRESS if data.len() == 2 => {
+                base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 16;
+            }
+            START_SEGMENT_ADDRESS | START_LINEAR_ADDRESS => (),
+            _ => return Err(locator.error(InvalidSyntax, line)),
+        }
+    }
+
+    words_from_bytes(bytes, ar_size, dr_size, word_bytes, options.endianness)
+        .map_err(|(kind, span)| ParseError::new(kind, source, span))
+}
+
+pub fn export(mem: &MemoryFile, options: &Options) -> Result<Vec<u8>, ExportError> {
+    let bytes = bytes_from_words(mem, options)?;
+    if bytes.last().map_or(false, |(offset, _)| *offset > u64::from(u32::MAX)) {
+        return Err(ExportError::AddressOutOfRange);
+    }
+
+    let mut result = String::new();
+    let mut upper = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let start = bytes[idx].0;
+
+        // Switch to the segment of the next byte
+        if start >> 16 != upper {
+            upper = start >> 16;
+            write_record(&mut result, EXTENDED_LINEAR_ADDRESS, 0, &(upper as u16).to_be_bytes());
+        }
+
+        // Collect consecutive bytes in the same segment
+        let mut data = Vec::with_capacity(BYTES_PER_RECORD);
+        while idx < bytes.len()
+            && data.len() < BYTES_PER_RECORD
+            && bytes[idx].0 == start + data.len() as u64
+            && bytes[idx].0 >> 16 == upper
+        {
+            data.push(bytes[idx].1);
+            idx += 1;
+        }
+
+        write_record(&mut result, DATA, start as u16, &data);
+    }
+    write_record(&mut result, END_OF_FILE, 0, &[]);
+
+    Ok(result.into_bytes())
+}
+
+fn write_record(result: &mut String, kind: u8, offset: u16, data: &[u8]) {
+    let mut record = vec![data.len() as u8];
+    record.extend(offset.to_be_bytes());
+    record.push(kind);
+    record.extend(data);
+    record.push(record.iter().fold(0u8, |sum, byte| sum.wrapping_sub(*byte)));
+
+    result.push(':');
+    for byte in record {
+        write!(result, "{:02X}", byte).unwrap();
+    }
+    result.push('\n');
+}
@@ -0,0 +1,184 @@
+use super::{address_count, from_utf8, strip_comments, Locator, Radix};
+use crate::error::{ExportError, ParseError, ParseErrorKind::*};
+use crate::{MemoryFile, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub fn import(source: &[u8], ar_size: usize, dr_size: usize) -> Result<MemoryFile, ParseError> {
+    let source = from_utf8(source)?;
+    let text = strip_comments(source, "--", Some(("%", "%"))).to_ascii_uppercase();
+    let locator = Locator::new(source, &text);
+
+    // Split into header and content
+    let (header, content) = match text.split_once("CONTENT") {
+        Some((header, content)) => (header, Some(content)),
+        None => (text.as_str(), None),
+    };
+
+    // Parse header
+    let mut width = None;
+    let mut depth = None;
+    let mut address_radix = Radix::Hex;
+    let mut data_radix = Radix::Hex;
+    for statement in header.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (key, value) =
+            statement.split_once('=').ok_or_else(|| locator.error(BadHeader, statement))?;
+        let value = value.trim();
+        match key.trim() {
+            "WIDTH" => {
+                let parsed = value.parse::<usize>().map_err(|_| locator.error(BadHeader, value))?;
+                if parsed != dr_size {
+                    let kind =
+                        SizeMismatch { expected: (ar_size, dr_size), actual: (ar_size, parsed) };
+                    return Err(locator.error(kind, statement));
+                }
+                width = Some(parsed);
+            }
+            "DEPTH" => {
+                let parsed = value.parse::<u64>().map_err(|_| locator.error(BadHeader, value))?;
+                if address_count(ar_size).map_or(false, |count| parsed > count) {
+                    return Err(locator.error(AddressTooWide { ar_size }, statement));
+                }
+                depth = Some(parsed);
+            }
+            "ADDRESS_RADIX" => address_radix = parse_radix(value, &locator)?,
+            "DATA_RADIX" => data_radix = parse_radix(value, &locator)?,
+            _ => return Err(locator.error(BadHeader, statement)),
+        }
+    }
+    let (_, depth) = width.zip(depth).ok_or_else(|| locator.error(BadHeader, header.trim()))?;
+
+    // Content is enclosed by `BEGIN` and `END`
+    let content = content.ok_or_else(|| locator.error_at_end(InvalidSyntax))?;
+    let content = content
+        .trim_start()
+        .strip_prefix("BEGIN")
+        .ok_or_else(|| locator.error(InvalidSyntax, content.trim()))?;
+    let (content, end) =
+        content.rsplit_once("END").ok_or_else(|| locator.error_at_end(InvalidSyntax))?;
+    if !matches!(end.trim(), ";" | "") {
+        return Err(locator.error(InvalidSyntax, end.trim()));
+    }
+
+    // Parse content
+    let mut data = HashMap::new();
+    for entry in content.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (addresses, values) =
+            entry.split_once(':').ok_or_else(|| locator.error(InvalidSyntax, entry))?;
+        let values = values
+            .split_whitespace()
+            .map(|value| parse_value(value, data_radix, dr_size, &locator))
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(locator.error(InvalidSyntax, entry));
+        }
+
+        // A single address is followed by values for consecutive addresses, a range is filled by
+        // repeating the values.
+        let addresses = addresses.trim();
+        let address = |part: &str| -> Result<u64, ParseError> {
+            let value = address_radix.parse(part).map_err(|()| locator.error(BadDigit, part))?;
+            value
+                .to_u64()
+                .filter(|address| *address < depth)
+                .ok_or_else(|| locator.error(AddressTooWide { ar_size }, part))
+        };
+        let (start, end) = match addresses.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+            Some(range) => {
+                let (start, end) = range
+                    .split_once("..")
+                    .ok_or_else(|| locator.error(InvalidSyntax, addresses))?;
+                let (start, end) = (address(start.trim())?, address(end.trim())?);
+                if start > end {
+                    return Err(locator.error(InvalidSyntax, addresses));
+                }
+                (start, end)
+            }
+            None => {
+                let start = address(addresses)?;
+                let end = start + values.len() as u64 - 1;
+                if end >= depth {
+                    return Err(locator.error(AddressTooWide { ar_size }, entry));
+                }
+                (start, end)
+            }
+        };
+
+        for (address, value) in (start..=end).zip(values.iter().cycle()) {
+            // Fits, because the depth fits into the address register
+            data.insert(Value::from_u64(address, ar_size), value.clone());
+        }
+    }
+
+    Ok(MemoryFile { ar_size, dr_size, data })
+}
+
+/// Consecutive addresses with the same value are merged into ranges.
+pub fn export(mem: &MemoryFile) -> Result<String, ExportError> {
+    let depth = address_count(mem.ar_size).ok_or(ExportError::AddressOutOfRange)?;
+
+    let mut result = String::new();
+    writeln!(result, "WIDTH={};", mem.dr_size).unwrap();
+    writeln!(result, "DEPTH={};", depth).unwrap();
+    writeln!(result).unwrap();
+    writeln!(result, "ADDRESS_RADIX=HEX;").unwrap();
+    writeln!(result, "DATA_RADIX=HEX;").unwrap();
+    writeln!(result).unwrap();
+    writeln!(result, "CONTENT BEGIN").unwrap();
+
+    let data = mem.sorted_data();
+    let mut idx = 0;
+    while idx < data.len() {
+        let (start, value) = data[idx];
+        let mut end = start;
+        while idx + 1 < data.len()
+            && data[idx + 1].1 == value
+            && *data[idx + 1].0 == end + Value::one(mem.ar_size)
+        {
+            idx += 1;
+            end = data[idx].0;
+        }
+        idx += 1;
+
+        let value = Radix::Hex.format(value, mem.dr_size);
+        if start == end {
+            writeln!(result, "\t{} : {};", start.as_hex(), value).unwrap();
+        } else {
+            writeln!(result, "\t[{}..{}] : {};", start.as_hex(), end.as_hex(), value).unwrap();
+        }
+    }
+
+    writeln!(result, "END;").unwrap();
+
+    Ok(result)
+}
+
+fn parse_radix(radix: &str, locator: &Locator<'_>) -> Result<Radix, ParseError> {
+    match radix {
+        "BIN" => Ok(Radix::Bin),
+        "OCT" => Ok(Radix::Oct),
+        "DEC" | "UNS" => Ok(Radix::Dec),
+        "HEX" => Ok(Radix::Hex),
+        _ => Err(locator.error(UnknownRadix, radix)),
+    }
+}
+
+/// Negative decimal values are stored in two's complement.
+fn parse_value(
+    value: &str,
+    radix: Radix,
+    dr_size: usize,
+    locator: &Locator<'_>,
+) -> Result<Value, ParseError> {
+    match value.strip_prefix('-') {
+        Some(magnitude) if radix == Radix::Dec => {
+            let negative = -locator.value(radix, magnitude, dr_size)?;
+            // The magnitude must be representable as a negative number
+            if !negative.is_zero() && negative.as_bin(true).starts_with('0') {
+                return Err(locator.error(ValueTooWide { dr_size }, value));
+            }
+            Ok(negative)
+        }
+        _ => locator.value(radix, value, dr_size),
+    }
+}
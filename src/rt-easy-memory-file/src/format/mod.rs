@@ -0,0 +1,436 @@
+mod binary;
+mod coe;
+mod intel_hex;
+mod mif;
+mod readmem;
+
+use crate::error::{ExportError, ParseError, ParseErrorKind};
+use crate::{span_of, MemoryFile, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The rt easy memory file format (`.rtmem`).
+    RtEasy,
+    /// Intel HEX (`.hex`, `.ihex`).
+    IntelHex,
+    /// Raw binary image (`.bin`).
+    Binary,
+    /// Verilog `$readmemh` (`.memh`).
+    ReadMemH,
+    /// Verilog `$readmemb` (`.memb`).
+    ReadMemB,
+    /// Altera Memory Initialization File (`.mif`).
+    Mif,
+    /// Xilinx Coefficient File (`.coe`).
+    Coe,
+}
+
+impl Format {
+    pub const ALL: [Format; 7] = [
+        Format::RtEasy,
+        Format::IntelHex,
+        Format::Binary,
+        Format::ReadMemH,
+        Format::ReadMemB,
+        Format::Mif,
+        Format::Coe,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::RtEasy => "rtmem",
+            Format::IntelHex => "ihex",
+            Format::Binary => "bin",
+            Format::ReadMemH => "readmemh",
+            Format::ReadMemB => "readmemb",
+            Format::Mif => "mif",
+            Format::Coe => "coe",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rtmem" => Some(Format::RtEasy),
+            "hex" | "ihex" => Some(Format::IntelHex),
+            "bin" => Some(Format::Binary),
+            "memh" => Some(Format::ReadMemH),
+            "memb" => Some(Format::ReadMemB),
+            "mif" => Some(Format::Mif),
+            "coe" => Some(Format::Coe),
+            _ => None,
+        }
+    }
+
+    /// Byte oriented formats store each word in `Options::word_bytes` bytes.
+    pub fn is_byte_oriented(&self) -> bool {
+        matches!(self, Format::IntelHex | Format::Binary)
+    }
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::ALL.iter().copied().find(|format| format.name().eq_ignore_ascii_case(s)).ok_or(())
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Endianness::Little
+    }
+}
+
+impl FromStr for Endianness {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "little" | "le" => Ok(Endianness::Little),
+            "big" | "be" => Ok(Endianness::Big),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Options for byte oriented formats, ignored by all other formats.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Byte order of the bytes of a word.
+    pub endianness: Endianness,
+    /// Number of bytes per word. Defaults to the smallest number of bytes that can hold the data
+    /// register.
+    pub word_bytes: Option<usize>,
+}
+
+impl MemoryFile {
+    /// Imports a memory file in the given format. The sizes must not be zero and must match the
+    /// header of formats that contain the sizes.
+    pub fn import(
+        format: Format,
+        source: &[u8],
+        ar_size: usize,
+        dr_size: usize,
+        options: &Options,
+    ) -> Result<Self, ParseError> {
+        match format {
+            Format::RtEasy => {
+                let source = from_utf8(source)?;
+                let mem = MemoryFile::parse(source)?;
+                if mem.ar_size() != ar_size || mem.dr_size() != dr_size {
+                    let header = source.lines().next().unwrap_or_default();
+                    let kind = ParseErrorKind::SizeMismatch {
+                        expected: (ar_size, dr_size),
+                        actual: (mem.ar_size(), mem.dr_size()),
+                    };
+                    return Err(ParseError::new(kind, source, span_of(source, header)));
+                }
+                Ok(mem)
+            }
+            Format::IntelHex => intel_hex::import(source, ar_size, dr_size, options),
+            Format::Binary => binary::import(source, ar_size, dr_size, options),
+            Format::ReadMemH => readmem::import(source, ar_size, dr_size, Radix::Hex),
+            Format::ReadMemB => readmem::import(source, ar_size, dr_size, Radix::Bin),
+            Format::Mif => mif::import(source, ar_size, dr_size),
+            Format::Coe => coe::import(source, ar_size, dr_size),
+        }
+    }
+
+    /// Exports the memory file in the given format.
+    pub fn export(&self, format: Format, options: &Options) -> Result<Vec<u8>, ExportError> {
+        match format {
+            Format::RtEasy => Ok(self.to_string().into_bytes()),
+            Format::IntelHex => intel_hex::export(self, options),
+            Format::Binary => binary::export(self, options),
+            Format::ReadMemH => Ok(readmem::export(self, Radix::Hex).into_bytes()),
+            Format::ReadMemB => Ok(readmem::export(self, Radix::Bin).into_bytes()),
+            Format::Mif => mif::export(self).map(String::into_bytes),
+            Format::Coe => coe::export(self).map(String::into_bytes),
+        }
+    }
+
+    /// Data sorted by address.
+    fn sorted_data(&self) -> Vec<(&Value, &Value)> {
+        let mut data = self.data.iter().collect::<Vec<_>>();
+        data.sort_by(|a, b| a.0.cmp(b.0));
+        data
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Util
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Radix {
+    Bin,
+    Oct,
+    Dec,
+    Hex,
+}
+
+impl Radix {
+    /// Parses an unsigned number. Underscores are allowed as separators.
+    pub(crate) fn parse(&self, s: &str) -> Result<Value, ()> {
+        let s = s.replace('_', "");
+        let mut value = match self {
+            Radix::Bin => Value::parse_bin(&s)?,
+            Radix::Oct => {
+                let bin = s
+                    .chars()
+                    .map(|c| c.to_digit(8).map(|digit| format!("{:03b}", digit)).ok_or(()))
+                    .collect::<Result<String, _>>()?;
+                Value::parse_bin(&bin)?
+            }
+            Radix::Dec => Value::parse_dec(&s)?,
+            Radix::Hex => Value::parse_hex(&s)?,
+        };
+        value.remove_leading_zeros();
+        Ok(value)
+    }
+
+    /// Formats the value with leading zeros for the given size.
+    fn format(&self, value: &Value, size: usize) -> String {
+        match self {
+            Radix::Bin => value.as_bin(true),
+            Radix::Oct => {
+                let bin = format!("{:0>1$}", value.as_bin(true), (size + 2) / 3 * 3);
+                (0..bin.len())
+                    .step_by(3)
+                    .map(|idx| {
+                        char::from_digit(u32::from_str_radix(&bin[idx..idx + 3], 2).unwrap(), 8)
+                            .unwrap()
+                    })
+                    .collect()
+            }
+            Radix::Dec => value.as_dec(),
+            Radix::Hex => format!("{:0>1$}", value.as_hex(), (size + 3) / 4),
+        }
+    }
+}
+
+/// Extends `value` to `size` bits, fails if it does not fit.
+pub(crate) fn fit(mut value: Value, size: usize) -> Result<Value, ()> {
+    value.remove_leading_zeros();
+    if value.size() > size {
+        return Err(());
+    }
+    value.extend_zero(size);
+    Ok(value)
+}
+
+/// Maximum number of bytes of a binary image and of words of a COE file. Both formats fill the gaps
+/// between the addresses, so a single word at a high address would take a lot of memory.
+const MAX_DENSE_LEN: u64 = 1 << 24;
+
+/// Fails if a dense format would contain `len` bytes or words.
+fn check_dense_len(len: u64) -> Result<usize, ExportError> {
+    if len > MAX_DENSE_LEN {
+        return Err(ExportError::TooLarge { len, max: MAX_DENSE_LEN });
+    }
+    Ok(len as usize)
+}
+
+/// Number of addresses, `None` if it does not fit into an `u64`.
+fn address_count(ar_size: usize) -> Option<u64> {
+    if ar_size < 64 {
+        Some(1 << ar_size)
+    } else {
+        None
+    }
+}
+
+fn word_bytes(dr_size: usize, options: &Options) -> Result<usize, ()> {
+    let min = (dr_size + 7) / 8;
+    match options.word_bytes {
+        Some(word_bytes) if word_bytes < min => Err(()),
+        Some(word_bytes) => Ok(word_bytes),
+        None => Ok(min),
+    }
+}
+
+fn import_word_bytes(
+    source: &[u8],
+    dr_size: usize,
+    options: &Options,
+) -> Result<usize, ParseError> {
+    word_bytes(dr_size, options).map_err(|()| {
+        let kind = ParseErrorKind::WordTooSmall { word_bytes: options.word_bytes.unwrap_or(0) };
+        ParseError::binary(kind, source, 0..0)
+    })
+}
+
+fn from_utf8(source: &[u8]) -> Result<&str, ParseError> {
+    std::str::from_utf8(source).map_err(|e| {
+        let start = e.valid_up_to();
+        let end = start + e.error_len().unwrap_or(source.len() - start);
+        ParseError::binary(ParseErrorKind::InvalidUtf8, source, start..end)
+    })
+}
+
+/// Creates errors for slices of `text`, which has the same byte positions as `source`.
+pub(crate) struct Locator<'a> {
+    source: &'a str,
+    text: &'a str,
+}
+
+impl<'a> Locator<'a> {
+    pub(crate) fn new(source: &'a str, text: &'a str) -> Self {
+        debug_assert_eq!(source.len(), text.len());
+        Self { source, text }
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind, part: &str) -> ParseError {
+        ParseError::new(kind, self.source, span_of(self.text, part))
+    }
+
+    pub(crate) fn error_at_end(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, self.source, self.source.len()..self.source.len())
+    }
+
+    pub(crate) fn address(
+        &self,
+        radix: Radix,
+        part: &str,
+        ar_size: usize,
+    ) -> Result<Value, ParseError> {
+        let value = radix.parse(part).map_err(|()| self.error(ParseErrorKind::BadDigit, part))?;
+        fit(value, ar_size)
+            .map_err(|()| self.error(ParseErrorKind::AddressTooWide { ar_size }, part))
+    }
+
+    pub(crate) fn value(
+        &self,
+        radix: Radix,
+        part: &str,
+        dr_size: usize,
+    ) -> Result<Value, ParseError> {
+        let value = radix.parse(part).map_err(|()| self.error(ParseErrorKind::BadDigit, part))?;
+        fit(value, dr_size).map_err(|()| self.error(ParseErrorKind::ValueTooWide { dr_size }, part))
+    }
+}
+
+/// Packs the bytes at the given byte offsets into words. Errors contain the source span of the
+/// offending word.
+fn words_from_bytes(
+    bytes: impl IntoIterator<Item = (u64, u8, Range<usize>)>,
+    ar_size: usize,
+    dr_size: usize,
+    word_bytes: usize,
+    endianness: Endianness,
+) -> Result<MemoryFile, (ParseErrorKind, Range<usize>)> {
+    let mut words = BTreeMap::new();
+    for (offset, byte, span) in bytes {
+        let address = offset / word_bytes as u64;
+        let (word, word_span) =
+            words.entry(address).or_insert_with(|| (vec![0; word_bytes], span.clone()));
+        word[(offset % word_bytes as u64) as usize] = byte;
+        *word_span = word_span.start.min(span.start)..word_span.end.max(span.end);
+    }
+
+    let data = words
+        .into_iter()
+        .map(|(address, (mut word, span))| {
+            let address = fit(Value::from_u64(address, 64), ar_size)
+                .map_err(|()| (ParseErrorKind::AddressTooWide { ar_size }, span.clone()))?;
+
+            if endianness == Endianness::Big {
+                word.reverse();
+            }
+            let hex = word.iter().rev().map(|byte| format!("{:02X}", byte)).collect::<String>();
+            let value = fit(Value::parse_hex(&hex).unwrap(), dr_size)
+                .map_err(|()| (ParseErrorKind::ValueTooWide { dr_size }, span))?;
+
+            Ok((address, value))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(MemoryFile { ar_size, dr_size, data })
+}
+
+/// Unpacks the words into bytes at the given byte offsets, sorted by offset.
+fn bytes_from_words(mem: &MemoryFile, options: &Options) -> Result<Vec<(u64, u8)>, ExportError> {
+    let word_bytes = word_bytes(mem.dr_size, options)
+        .map_err(|()| ExportError::WordTooSmall { word_bytes: options.word_bytes.unwrap_or(0) })?;
+
+    let mut bytes = Vec::with_capacity(mem.data.len() * word_bytes);
+    for (address, value) in mem.sorted_data() {
+        let offset = address
+            .to_u64()
+            .and_then(|address| address.checked_mul(word_bytes as u64))
+            .ok_or(ExportError::AddressOutOfRange)?;
+
+        let hex = format!("{:0>1$}", value.as_hex(), word_bytes * 2);
+        let mut word = (0..word_bytes)
+            .rev()
+            .map(|idx| u8::from_str_radix(&hex[idx * 2..idx * 2 + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        if options.endianness == Endianness::Big {
+            word.reverse();
+        }
+
+        for (idx, byte) in word.into_iter().enumerate() {
+            let offset = offset.checked_add(idx as u64).ok_or(ExportError::AddressOutOfRange)?;
+            bytes.push((offset, byte));
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Replaces comments with whitespace, keeping the byte positions of all other text.
+fn strip_comments(source: &str, line_comment: &str, block_comment: Option<(&str, &str)>) -> String {
+    fn blank(result: &mut String, comment: &str) {
+        result.extend(comment.chars().flat_map(|c| {
+            let len = if c == '\n' { 0 } else { c.len_utf8() };
+            std::iter::repeat(' ').take(len).chain((c == '\n').then(|| '\n'))
+        }));
+    }
+
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    loop {
+        let line_start = rest.find(line_comment);
+        let block_start = block_comment.and_then(|(start, _)| rest.find(start));
+
+        match (line_start, block_start) {
+            (Some(line_start), block_start) if block_start.map_or(true, |b| line_start < b) => {
+                result += &rest[..line_start];
+                let end = rest[line_start..].find('\n').map_or(rest.len(), |end| line_start + end);
+                blank(&mut result, &rest[line_start..end]);
+                rest = &rest[end..];
+            }
+            (_, Some(block_start)) => {
+                let (start, end) = block_comment.unwrap();
+                result += &rest[..block_start];
+                let comment = &rest[block_start..];
+                let len = match comment[start.len()..].find(end) {
+                    Some(idx) => start.len() + idx + end.len(),
+                    None => comment.len(),
+                };
+                blank(&mut result, &comment[..len]);
+                rest = &comment[len..];
+            }
+            _ => {
+                result += rest;
+                return result;
+            }
+        }
+    }
+}
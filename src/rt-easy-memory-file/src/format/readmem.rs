@@ -0,0 +1,47 @@
+use super::{from_utf8, strip_comments, Locator, Radix};
+use crate::error::ParseError;
+use crate::{MemoryFile, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Addresses (`@` followed by the address) are always hexadecimal, the data has the given radix.
+pub fn import(
+    source: &[u8],
+    ar_size: usize,
+    dr_size: usize,
+    radix: Radix,
+) -> Result<MemoryFile, ParseError> {
+    let source = from_utf8(source)?;
+    let text = strip_comments(source, "//", Some(("/*", "*/")));
+    let locator = Locator::new(source, &text);
+
+    let mut current_address = Value::zero(ar_size);
+    let mut data = HashMap::new();
+    for word in text.split_whitespace() {
+        match word.strip_prefix('@') {
+            Some(address) => current_address = locator.address(Radix::Hex, address, ar_size)?,
+            None => {
+                data.insert(current_address.clone(), locator.value(radix, word, dr_size)?);
+                current_address = current_address + Value::one(ar_size);
+            }
+        }
+    }
+
+    Ok(MemoryFile { ar_size, dr_size, data })
+}
+
+pub fn export(mem: &MemoryFile, radix: Radix) -> String {
+    let mut result = String::new();
+
+    let mut current_address = Value::zero(mem.ar_size);
+    for (address, value) in mem.sorted_data() {
+        if *address != current_address {
+            writeln!(result, "@{}", address.as_hex()).unwrap();
+        }
+        writeln!(result, "{}", radix.format(value, mem.dr_size)).unwrap();
+
+        current_address = address + Value::one(mem.ar_size);
+    }
+
+    result
+}
@@ -1,8 +1,17 @@
 #![deny(rust_2018_idioms)]
 
+mod diff;
+mod error;
+mod format;
+mod parse;
+
 use std::collections::HashMap;
 use std::fmt;
+use std::ops::Range;
 
+pub use self::diff::{DiffEntry, DiffKind, MemoryDiff};
+pub use self::error::{ExportError, ParseError, ParseErrorKind};
+pub use self::format::{Endianness, Format, Options};
 pub use rtcore::value::Value;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,70 +74,10 @@ impl MemoryFile {
     }
 }
 
-impl MemoryFile {
-    pub fn parse(source: &str) -> Result<Self, ()> {
-        // Split to lines
-        let mut lines = source.lines().map(|line| {
-            // Remove comment
-            let line = match line.split_once('#') {
-                Some((line, _comment)) => line,
-                None => line,
-            };
-
-            // Trim
-            line.trim()
-        });
-
-        // Parse header
-        let header = lines.next().ok_or(())?;
-        let mut parts = header.split(' ');
-        let parse_fn = match parts.next() {
-            Some("B") | Some("b") => Value::parse_bin,
-            Some("H") | Some("h") => Value::parse_hex,
-            _ => return Err(()),
-        };
-        let ar_size = match parts.next() {
-            Some(ar_size) => ar_size.parse().map_err(|_| ())?,
-            None => return Err(()),
-        };
-        let dr_size = match parts.next() {
-            Some(dr_size) => dr_size.parse().map_err(|_| ())?,
-            None => return Err(()),
-        };
-
-        // Parse data
-        let mut current_address = Value::zero(ar_size);
-        let mut data = HashMap::new();
-        for line in lines {
-            // Skip empty lines
-            if line.is_empty() {
-                continue;
-            }
-
-            // Parse as address or data
-            if line.ends_with(':') {
-                let mut v = parse_fn(&line[0..line.len() - 1]).map_err(|_| ())?;
-                if v.size() > ar_size {
-                    return Err(());
-                }
-                v.extend_zero(ar_size);
-
-                current_address = v;
-            } else {
-                let mut v = parse_fn(line).map_err(|_| ())?;
-                if v.size() > dr_size {
-                    return Err(());
-                }
-                v.extend_zero(dr_size);
-
-                data.insert(current_address.clone(), v);
-                current_address = current_address + Value::one(ar_size);
-            }
-        }
-
-        // Memory file
-        Ok(Self { ar_size, dr_size, data })
-    }
+/// Byte range of `part`, which must be a slice of `source` (or of a copy with the same layout).
+fn span_of(source: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - source.as_ptr() as usize;
+    start..start + part.len()
 }
 
 impl fmt::Display for MemoryFile {
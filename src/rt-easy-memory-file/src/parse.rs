@@ -0,0 +1,311 @@
+use crate::error::{ParseError, ParseErrorKind::*};
+use crate::format::{fit, Locator, Radix};
+use crate::{MemoryFile, Value};
+use rtcore::value::SignedValue;
+use std::collections::HashMap;
+
+impl MemoryFile {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let locator = Locator::new(source, source);
+
+        // Split to lines
+        let mut lines = source.lines().map(|line| {
+            // Remove comment
+            let line = match line.split_once('#') {
+                Some((line, _comment)) => line,
+                None => line,
+            };
+
+            // Trim
+            line.trim()
+        });
+
+        // Parse header
+        let header =
+            lines.next().ok_or_else(|| locator.error(BadHeader, &source[source.len()..]))?;
+        let mut parts = header.split(' ');
+        let radix = match parts.next() {
+            Some("B") | Some("b") => Radix::Bin,
+            Some("H") | Some("h") => Radix::Hex,
+            Some("") | None => return Err(locator.error(BadHeader, header)),
+            Some(radix) => return Err(locator.error(UnknownRadix, radix)),
+        };
+        let mut parse_size = || match parts.next() {
+            Some(size) => match size.parse() {
+                Ok(size) if size > 0 => Ok(size),
+                _ => Err(locator.error(BadHeader, size)),
+            },
+            None => Err(locator.error(BadHeader, header)),
+        };
+        let ar_size = parse_size()?;
+        let dr_size = parse_size()?;
+
+        // Parse data
+        let mut parser = DataParser {
+            locator,
+            ar_size,
+            dr_size,
+            current_address: Some(Value::zero(ar_size)),
+            labels: HashMap::new(),
+            data: Vec::new(),
+        };
+        for line in lines {
+            parser.line(line, radix)?;
+        }
+
+        // Memory file
+        let data = parser.resolve()?;
+        Ok(Self { ar_size, dr_size, data })
+    }
+}
+
+/// A value or a reference to a label (including the `@`), which is resolved after all lines are
+/// parsed.
+#[derive(Debug, Clone)]
+enum Data<'a> {
+    Value(Value),
+    Label(&'a str),
+}
+
+struct DataParser<'a> {
+    locator: Locator<'a>,
+    ar_size: usize,
+    dr_size: usize,
+    /// `None` after the last address of the memory.
+    current_address: Option<Value>,
+    labels: HashMap<&'a str, Value>,
+    data: Vec<(Value, Data<'a>)>,
+}
+
+impl<'a> DataParser<'a> {
+    fn line(&mut self, line: &'a str, radix: Radix) -> Result<(), ParseError> {
+        let tokens = self.tokenize(line, radix)?;
+
+        let mut tokens = tokens.into_iter();
+        while let Some((radix, token)) = tokens.next() {
+            match token {
+                "fill" => {
+                    let (count, count_token) = self.count(tokens.next(), token)?;
+                    let (radix, value) = tokens.next().ok_or_else(|| self.missing(token))?;
+                    let value = self.value(radix, value)?;
+                    self.check_space(count, 1, count_token)?;
+                    for _ in 0..count {
+                        self.push(value.clone());
+                    }
+                }
+                "repeat" => {
+                    let (count, count_token) = self.count(tokens.next(), token)?;
+                    let values = self.values(tokens.by_ref(), token)?;
+                    self.check_space(count, values.len(), count_token)?;
+                    for _ in 0..count {
+                        for value in &values {
+                            self.push(value.clone());
+                        }
+                    }
+                }
+                _ => match token.strip_suffix(':') {
+                    Some(label) if label.starts_with('@') => {
+                        self.label_name(label)?;
+                        let address = self.next_address(label)?.clone();
+                        if self.labels.insert(label, address).is_some() {
+                            return Err(self.locator.error(DuplicateLabel, label));
+                        }
+                    }
+                    Some(range) if range.contains("..") => {
+                        let (start, end) = range.split_once("..").unwrap();
+                        let start = self.locator.address(radix, start, self.ar_size)?;
+                        let end = self.locator.address(radix, end, self.ar_size)?;
+                        if start > end {
+                            return Err(self.locator.error(InvalidSyntax, range));
+                        }
+
+                        // Repeat the values until the range is filled
+                        let rest = tokens.by_ref().collect::<Vec<_>>();
+                        let values = self.values(rest.iter().copied(), token)?;
+                        let last =
+                            (&end - &start).to_u64().map_or(usize::MAX, |last| last as usize);
+                        if let Some((_, value)) = rest.get(last.saturating_add(1)) {
+                            return Err(self.locator.error(TooManyValues, value));
+                        }
+                        self.current_address = Some(start);
+                        for value in values.iter().cycle() {
+                            let done = self.current_address.as_ref() == Some(&end);
+                            self.push(value.clone());
+                            if done {
+                                break;
+                            }
+                        }
+                    }
+                    Some(address) => {
+                        self.current_address =
+                            Some(self.locator.address(radix, address, self.ar_size)?);
+                    }
+                    None => {
+                        let value = self.value(radix, token)?;
+                        self.next_address(token)?;
+                        self.push(value);
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splits the line into tokens. A radix prefix (`'b`, `'o`, `'d` or `'h`) switches the radix
+    /// for the rest of the line, it may stand alone or be attached to the next token.
+    fn tokenize(
+        &self,
+        line: &'a str,
+        mut radix: Radix,
+    ) -> Result<Vec<(Radix, &'a str)>, ParseError> {
+        let mut tokens = Vec::new();
+        for token in line.split_whitespace() {
+            let token = match token.strip_prefix('\'') {
+                Some(rest) => {
+                    let (prefix, rest) = match rest.char_indices().nth(1) {
+                        Some((idx, _)) => (&token[..idx + 1], &rest[idx..]),
+                        None => (token, ""),
+                    };
+                    radix = match prefix {
+                        "'b" | "'B" => Radix::Bin,
+                        "'o" | "'O" => Radix::Oct,
+                        "'d" | "'D" => Radix::Dec,
+                        "'h" | "'H" => Radix::Hex,
+                        _ => return Err(self.locator.error(UnknownRadix, prefix)),
+                    };
+                    rest
+                }
+                None => token,
+            };
+
+            if !token.is_empty() {
+                tokens.push((radix, token));
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    /// Remaining values of a line, at least one.
+    fn values(
+        &self,
+        tokens: impl Iterator<Item = (Radix, &'a str)>,
+        directive: &str,
+    ) -> Result<Vec<Data<'a>>, ParseError> {
+        let values =
+            tokens.map(|(radix, token)| self.value(radix, token)).collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(self.missing(directive));
+        }
+        Ok(values)
+    }
+
+    /// Signed values are stored in two's complement.
+    fn value(&self, radix: Radix, token: &'a str) -> Result<Data<'a>, ParseError> {
+        if token.starts_with('@') {
+            self.label_name(token)?;
+            return Ok(Data::Label(token));
+        }
+
+        let value = match token.strip_prefix('-') {
+            Some(magnitude) => {
+                let magnitude = self.number(radix, magnitude, token)?;
+                let is_zero = magnitude.is_zero();
+                // Negative values down to -2^(dr_size - 1) have the sign bit set
+                SignedValue::Negative(magnitude).into_twos_complement(self.dr_size).ok().filter(
+                    |value| is_zero || !value.slice(self.dr_size - 1..).to_value().is_zero(),
+                )
+            }
+            None => SignedValue::Positive(self.number(radix, token, token)?)
+                .into_twos_complement(self.dr_size)
+                .ok(),
+        };
+        match value {
+            Some(value) => Ok(Data::Value(value)),
+            None => Err(self.locator.error(ValueTooWide { dr_size: self.dr_size }, token)),
+        }
+    }
+
+    fn number(&self, radix: Radix, digits: &str, token: &str) -> Result<Value, ParseError> {
+        radix.parse(digits).map_err(|()| self.locator.error(BadDigit, token))
+    }
+
+    /// Counts of directives are always decimal.
+    fn count(
+        &self,
+        token: Option<(Radix, &'a str)>,
+        directive: &str,
+    ) -> Result<(u64, &'a str), ParseError> {
+        let (_, token) = token.ok_or_else(|| self.missing(directive))?;
+        let count = token.parse().map_err(|_| self.locator.error(BadDigit, token))?;
+        Ok((count, token))
+    }
+
+    /// Fails if `count` times `len` values do not fit into the addresses from the current address
+    /// to the end of the memory.
+    fn check_space(&self, count: u64, len: usize, token: &str) -> Result<(), ParseError> {
+        let needed = u128::from(count) * len as u128;
+        if needed == 0 {
+            return Ok(());
+        }
+        // Number of addresses left minus one, none are left after the end of the memory
+        let last = self.current_address.as_ref().map(|address| {
+            (Value::filled(self.ar_size) - address).to_u64().map_or(u128::MAX, u128::from)
+        });
+        match last {
+            Some(last) if needed - 1 <= last => Ok(()),
+            _ => Err(self.locator.error(CountTooLarge, token)),
+        }
+    }
+
+    fn label_name(&self, label: &str) -> Result<(), ParseError> {
+        let name = &label[1..];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(self.locator.error(InvalidSyntax, label));
+        }
+        Ok(())
+    }
+
+    fn missing(&self, directive: &str) -> ParseError {
+        self.locator.error(InvalidSyntax, directive)
+    }
+
+    /// The current address, fails after the last address of the memory.
+    fn next_address(&self, token: &str) -> Result<&Value, ParseError> {
+        self.current_address.as_ref().ok_or_else(|| self.locator.error(EndOfMemory, token))
+    }
+
+    /// Stores the value at the current address, which must not be after the end of the memory.
+    fn push(&mut self, value: Data<'a>) {
+        let address = self.current_address.take().expect("address after the end of the memory");
+        let next = &address + Value::one(self.ar_size);
+        // The address wraps to zero after the last address
+        if !next.is_zero() {
+            self.current_address = Some(next);
+        }
+        self.data.push((address, value));
+    }
+
+    /// Replaces labels by their address.
+    fn resolve(self) -> Result<HashMap<Value, Value>, ParseError> {
+        let dr_size = self.dr_size;
+        self.data
+            .into_iter()
+            .map(|(address, data)| {
+                let value = match data {
+                    Data::Value(value) => value,
+                    Data::Label(label) => {
+                        let address = self
+                            .labels
+                            .get(label)
+                            .ok_or_else(|| self.locator.error(UnknownLabel, label))?;
+                        fit(address.clone(), dr_size)
+                            .map_err(|()| self.locator.error(ValueTooWide { dr_size }, label))?
+                    }
+                };
+                Ok((address, value))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,42 @@
+use rt_easy_memory_file::{DiffKind, MemoryFile, Value};
+
+#[test]
+fn diff() {
+    let old = MemoryFile::parse("H 4 8\n0: 1 2 3 0\nA: 5").unwrap();
+    let new = MemoryFile::parse("H 4 8\n0: 1 7 0\n5: 0 2A").unwrap();
+
+    let diff = old.diff(&new);
+    let entries = diff
+        .entries()
+        .iter()
+        .map(|entry| (entry.address.as_hex(), entry.kind.clone()))
+        .collect::<Vec<_>>();
+    let value = |hex| {
+        let mut value = Value::parse_hex(hex).unwrap();
+        value.extend_zero(8);
+        value
+    };
+    assert_eq!(
+        entries,
+        [
+            ("1".to_string(), DiffKind::Changed { old: value("2"), new: value("7") }),
+            ("2".to_string(), DiffKind::Changed { old: value("3"), new: value("0") }),
+            ("6".to_string(), DiffKind::Added { new: value("2A") }),
+            ("A".to_string(), DiffKind::Cleared { old: value("5") }),
+        ]
+    );
+    assert_eq!(diff.to_string(), "1: 02 -> 07\n2: 03 -> 00\n6: unset -> 2A\nA: 05 -> unset\n");
+
+    assert!(old.diff(&old).is_empty());
+    assert!(new.diff(&new.clone()).is_empty());
+    assert_eq!(old.diff(&old).to_string(), "");
+}
+
+#[test]
+fn diff_sizes() {
+    let old = MemoryFile::parse("H 4 8\n1: FF").unwrap();
+    let new = MemoryFile::parse("H 8 16\n1: FF\n10: 1").unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.to_string(), "10: unset -> 0001\n");
+}
@@ -0,0 +1,119 @@
+use rt_easy_memory_file::{Format, MemoryFile, ParseError, ParseErrorKind};
+
+fn parse_error(source: &str) -> ParseError {
+    MemoryFile::parse(source).unwrap_err()
+}
+
+fn import_error(format: Format, source: &[u8], ar_size: usize, dr_size: usize) -> ParseError {
+    MemoryFile::import(format, source, ar_size, dr_size, &Default::default()).unwrap_err()
+}
+
+#[test]
+fn header() {
+    let error = parse_error("");
+    assert_eq!(error.kind, ParseErrorKind::BadHeader);
+    assert_eq!((error.line, error.column), (1, 1));
+
+    let error = parse_error("X 4 8");
+    assert_eq!(error.kind, ParseErrorKind::UnknownRadix);
+    assert_eq!((error.line, error.column, error.text.as_str()), (1, 1, "X"));
+
+    let error = parse_error("H 4 a8 # comment");
+    assert_eq!(error.kind, ParseErrorKind::BadHeader);
+    assert_eq!((error.line, error.column, error.text.as_str()), (1, 5, "a8"));
+
+    let error = parse_error("H 4");
+    assert_eq!(error.kind, ParseErrorKind::BadHeader);
+    assert_eq!(error.text, "H 4");
+}
+
+#[test]
+fn data() {
+    let error = parse_error("H 4 8\n\n  1G");
+    assert_eq!(error.kind, ParseErrorKind::BadDigit);
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 3, "1G"));
+
+    let error = parse_error("B 4 8\n1\n11111:\n1");
+    assert_eq!(error.kind, ParseErrorKind::AddressTooWide { ar_size: 4 });
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, "11111"));
+
+    let error = parse_error("H 4 8\n1FF # too wide");
+    assert_eq!(error.kind, ParseErrorKind::ValueTooWide { dr_size: 8 });
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, "1FF"));
+    assert_eq!(
+        error.to_string(),
+        "value does not fit into the data register (8 bits) at 2:1: \"1FF\""
+    );
+}
+
+#[test]
+fn formats() {
+    let error = import_error(Format::RtEasy, b"H 4 16\n0", 4, 8);
+    assert_eq!(error.kind, ParseErrorKind::SizeMismatch { expected: (4, 8), actual: (4, 16) });
+    assert_eq!(error.text, "H 4 16");
+
+    let error = import_error(Format::ReadMemH, b"// comment\n00\n@1F 0", 4, 8);
+    assert_eq!(error.kind, ParseErrorKind::AddressTooWide { ar_size: 4 });
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 2, "1F"));
+
+    let error = import_error(Format::IntelHex, b":0100000042BD\n:01000100FFFE\n", 4, 8);
+    assert_eq!(error.kind, ParseErrorKind::ChecksumMismatch);
+    assert_eq!(error.line, 2);
+
+    let error = import_error(Format::Mif, b"WIDTH=8;\nDEPTH=16;\nDATA_RADIX=SEX;\n", 4, 8);
+    assert_eq!(error.kind, ParseErrorKind::UnknownRadix);
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 12, "SEX"));
+
+    let error = import_error(Format::Coe, b"memory_initialization_vector=1,2,X;", 4, 8);
+    assert_eq!(error.kind, ParseErrorKind::BadDigit);
+    assert_eq!((error.column, error.text.as_str()), (34, "X"));
+
+    let error = import_error(Format::Binary, &[1, 2, 3], 4, 16);
+    assert_eq!(error.kind, ParseErrorKind::IncompleteWord { word_bytes: 2 });
+    assert_eq!((error.column, error.text.as_str()), (3, "03"));
+}
+
+#[test]
+fn pretty_print() {
+    let source = "H 4 8\n2A\n1FF\n";
+    let error = parse_error(source);
+    let rendered = error.pretty_print(source.as_bytes(), Some("memory.rtmem"), false);
+    assert!(rendered.contains("value does not fit into the data register (8 bits)"));
+    assert!(rendered.contains("memory.rtmem:3:1"));
+    assert!(rendered.contains("1FF"));
+
+    let error = import_error(Format::Binary, &[1, 2, 3], 4, 16);
+    let rendered = error.pretty_print(&[1, 2, 3], None, false);
+    assert!(rendered.contains("at byte 2: 03"));
+}
+
+#[test]
+fn labels() {
+    let error = parse_error("H 4 8\n@a: 1\n@b @a:");
+    assert_eq!(error.kind, ParseErrorKind::DuplicateLabel);
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 4, "@a"));
+
+    let error = parse_error("H 4 8\n1 @missing");
+    assert_eq!(error.kind, ParseErrorKind::UnknownLabel);
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "@missing"));
+
+    let error = parse_error("H 4 8\nE: fill 3 1");
+    assert_eq!(error.kind, ParseErrorKind::CountTooLarge);
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 9, "3"));
+
+    let error = parse_error("H 2 8\n3: 1 2 3");
+    assert_eq!(error.kind, ParseErrorKind::EndOfMemory);
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 6, "2"));
+
+    let error = parse_error("H 2 8\n0..3: 1 2 3 4 5 6");
+    assert_eq!(error.kind, ParseErrorKind::TooManyValues);
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 15, "5"));
+
+    let error = parse_error("H 2 8\n3: 1 @end:");
+    assert_eq!(error.kind, ParseErrorKind::EndOfMemory);
+    assert_eq!(error.text, "@end");
+
+    let error = parse_error("H 4 8\n'x 1");
+    assert_eq!(error.kind, ParseErrorKind::UnknownRadix);
+    assert_eq!(error.text, "'x");
+}
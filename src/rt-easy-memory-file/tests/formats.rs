@@ -0,0 +1,282 @@
+use rt_easy_memory_file::{Endianness, ExportError, Format, MemoryFile, Options, Value};
+use std::collections::HashMap;
+
+fn mem(ar_size: usize, dr_size: usize, data: &[(&str, &str)]) -> MemoryFile {
+    MemoryFile::new(
+        ar_size,
+        dr_size,
+        data.iter()
+            .map(|(addr, value)| {
+                (Value::parse_hex(addr).unwrap(), Value::parse_hex(value).unwrap())
+            })
+            .collect::<HashMap<_, _>>(),
+    )
+    .unwrap()
+}
+
+fn import(format: Format, source: &str, ar_size: usize, dr_size: usize) -> MemoryFile {
+    MemoryFile::import(format, source.as_bytes(), ar_size, dr_size, &Default::default()).unwrap()
+}
+
+fn export(mem: &MemoryFile, format: Format) -> String {
+    String::from_utf8(mem.export(format, &Default::default()).unwrap()).unwrap()
+}
+
+#[test]
+fn format_names() {
+    for format in Format::ALL {
+        assert_eq!(format.name().parse::<Format>(), Ok(format));
+    }
+    assert_eq!(Format::from_extension("HEX"), Some(Format::IntelHex));
+    assert_eq!(Format::from_extension("rtmem"), Some(Format::RtEasy));
+    assert_eq!(Format::from_extension("txt"), None);
+}
+
+#[test]
+fn round_trip() {
+    let mems = [
+        mem(4, 8, &[]),
+        mem(4, 8, &[("0", "17"), ("1", "0"), ("2", "3"), ("A", "FF")]),
+        mem(8, 12, &[("3", "ABC"), ("4", "1"), ("F0", "FFF")]),
+        mem(20, 32, &[("0", "DEADBEEF"), ("400", "1")]),
+    ];
+    let options = [
+        Options::default(),
+        Options { endianness: Endianness::Big, word_bytes: None },
+        Options { endianness: Endianness::Little, word_bytes: Some(4) },
+    ];
+
+    for mem in &mems {
+        for format in Format::ALL {
+            for options in &options {
+                let exported = mem.export(format, options).unwrap();
+                let imported =
+                    MemoryFile::import(format, &exported, mem.ar_size(), mem.dr_size(), options)
+                        .unwrap();
+
+                // Dense formats fill the gaps with zeros
+                let expected = match format {
+                    Format::Binary | Format::Coe => fill_gaps(mem),
+                    _ => mem.clone(),
+                };
+                assert_eq!(imported, expected, "{} {:?}", format, options);
+            }
+        }
+    }
+}
+
+fn fill_gaps(mem: &MemoryFile) -> MemoryFile {
+    let mut data = mem.data().clone();
+    if let Some(last) = mem.data().keys().max() {
+        let mut address = Value::zero(mem.ar_size());
+        while address <= *last {
+            data.entry(address.clone()).or_insert_with(|| Value::zero(mem.dr_size()));
+            address = address + Value::one(mem.ar_size());
+        }
+    }
+    MemoryFile::new(mem.ar_size(), mem.dr_size(), data).unwrap()
+}
+
+#[test]
+fn intel_hex() {
+    let source = r#":0300300002337A1E
+:020000021000EC
+:010005007783
+:00000001FF
+"#;
+    assert_eq!(
+        import(Format::IntelHex, source, 20, 8),
+        mem(20, 8, &[("30", "2"), ("31", "33"), ("32", "7A"), ("10005", "77")])
+    );
+
+    // Address does not fit address register
+    assert!(MemoryFile::import(Format::IntelHex, source.as_bytes(), 16, 8, &Default::default())
+        .is_err());
+
+    // Checksum mismatch
+    assert!(MemoryFile::import(Format::IntelHex, b":0100000001FF\n", 8, 8, &Default::default())
+        .is_err());
+
+    // Extended linear address
+    assert_eq!(
+        export(&mem(24, 8, &[("1", "AB"), ("10000", "CD")]), Format::IntelHex),
+        ":01000100AB53\n:020000040001F9\n:01000000CD32\n:00000001FF\n"
+    );
+}
+
+#[test]
+fn byte_order() {
+    let mem = mem(4, 16, &[("0", "1234"), ("1", "ABCD")]);
+
+    let little = mem.export(Format::Binary, &Default::default()).unwrap();
+    assert_eq!(little, [0x34, 0x12, 0xCD, 0xAB]);
+
+    let options = Options { endianness: Endianness::Big, word_bytes: None };
+    let big = mem.export(Format::Binary, &options).unwrap();
+    assert_eq!(big, [0x12, 0x34, 0xAB, 0xCD]);
+
+    let options = Options { endianness: Endianness::Big, word_bytes: Some(3) };
+    let padded = mem.export(Format::Binary, &options).unwrap();
+    assert_eq!(padded, [0x00, 0x12, 0x34, 0x00, 0xAB, 0xCD]);
+
+    // Intel HEX addresses are byte offsets
+    assert_eq!(export(&mem, Format::IntelHex), ":040000003412CDAB3E\n:00000001FF\n");
+}
+
+#[test]
+fn binary_invalid() {
+    let options = Options::default();
+
+    // Not a multiple of the word size
+    assert!(MemoryFile::import(Format::Binary, &[1, 2, 3], 4, 16, &options).is_err());
+
+    // Word does not fit data register
+    assert!(MemoryFile::import(Format::Binary, &[0xFF], 4, 4, &options).is_err());
+
+    // Too many words for address register
+    assert!(MemoryFile::import(Format::Binary, &[0; 5], 2, 8, &options).is_err());
+
+    // Word size too small for data register
+    let options = Options { endianness: Endianness::Little, word_bytes: Some(1) };
+    assert!(MemoryFile::import(Format::Binary, &[0; 4], 4, 16, &options).is_err());
+}
+
+#[test]
+fn export_invalid() {
+    let export = |mem: &MemoryFile, format, options| mem.export(format, options).unwrap_err();
+    let options = Options::default();
+
+    // Word size too small for data register
+    let word_bytes = Options { endianness: Endianness::Little, word_bytes: Some(1) };
+    assert_eq!(
+        export(&mem(4, 16, &[("0", "1")]), Format::Binary, &word_bytes),
+        ExportError::WordTooSmall { word_bytes: 1 }
+    );
+
+    // Dense formats are limited in size
+    let high = mem(40, 8, &[("FFFFFFFFFF", "1")]);
+    for format in [Format::Binary, Format::Coe] {
+        assert!(matches!(export(&high, format, &options), ExportError::TooLarge { .. }));
+    }
+    assert!(mem(24, 8, &[("FFFFFF", "1")]).export(Format::Binary, &options).is_ok());
+
+    // Intel HEX addresses have 32 bits, MIF depths must fit into 64 bits
+    assert_eq!(export(&high, Format::IntelHex, &options), ExportError::AddressOutOfRange);
+    assert_eq!(export(&mem(64, 8, &[]), Format::Mif, &options), ExportError::AddressOutOfRange);
+}
+
+#[test]
+fn readmem() {
+    let source = r#"// Comment
+@10 DE_AD /* block
+comment */ 00BE
+ef
+@0
+1
+"#;
+    assert_eq!(
+        import(Format::ReadMemH, source, 8, 16),
+        mem(8, 16, &[("10", "DEAD"), ("11", "BE"), ("12", "EF"), ("0", "1")])
+    );
+
+    let source = "0101\n@2 1111_0000\n";
+    assert_eq!(import(Format::ReadMemB, source, 4, 8), mem(4, 8, &[("0", "5"), ("2", "F0")]));
+
+    assert_eq!(
+        export(&mem(4, 8, &[("0", "5"), ("1", "6"), ("A", "F0")]), Format::ReadMemB),
+        "00000101\n00000110\n@A\n11110000\n"
+    );
+    assert_eq!(export(&mem(4, 12, &[("1", "5")]), Format::ReadMemH), "@1\n005\n");
+}
+
+#[test]
+fn mif() {
+    let source = r#"-- Comment
+WIDTH = 8;
+DEPTH = 16;
+ADDRESS_RADIX = DEC; % block comment %
+DATA_RADIX = HEX;
+
+CONTENT
+BEGIN
+    0 : 1A;
+    [2..4] : FF;
+    10 : 1 2 3;
+END;
+"#;
+    assert_eq!(
+        import(Format::Mif, source, 4, 8),
+        mem(
+            4,
+            8,
+            &[
+                ("0", "1A"),
+                ("2", "FF"),
+                ("3", "FF"),
+                ("4", "FF"),
+                ("A", "1"),
+                ("B", "2"),
+                ("C", "3"),
+            ]
+        )
+    );
+
+    let source = "WIDTH=8;DEPTH=4;DATA_RADIX=DEC;CONTENT BEGIN 0 : -1 -128 127; END;";
+    assert_eq!(
+        import(Format::Mif, source, 2, 8),
+        mem(2, 8, &[("0", "FF"), ("1", "80"), ("2", "7F")])
+    );
+
+    // Width mismatch, negative value too small, address exceeds depth
+    for source in [
+        "WIDTH=16;DEPTH=4;CONTENT BEGIN 0 : 1; END;",
+        "WIDTH=8;DEPTH=4;DATA_RADIX=DEC;CONTENT BEGIN 0 : -129; END;",
+        "WIDTH=8;DEPTH=4;CONTENT BEGIN 4 : 1; END;",
+    ] {
+        assert!(
+            MemoryFile::import(Format::Mif, source.as_bytes(), 2, 8, &Default::default()).is_err()
+        );
+    }
+
+    assert_eq!(
+        export(&mem(4, 8, &[("0", "1A"), ("2", "FF"), ("3", "FF"), ("4", "FF")]), Format::Mif),
+        r#"WIDTH=8;
+DEPTH=16;
+
+ADDRESS_RADIX=HEX;
+DATA_RADIX=HEX;
+
+CONTENT BEGIN
+	0 : 1A;
+	[2..4] : FF;
+END;
+"#
+    );
+}
+
+#[test]
+fn coe() {
+    let source = r#"; Comment
+memory_initialization_radix=2;
+memory_initialization_vector=
+0001, 0010,
+1111;
+"#;
+    assert_eq!(import(Format::Coe, source, 2, 4), mem(2, 4, &[("0", "1"), ("1", "2"), ("2", "F")]));
+
+    assert_eq!(
+        export(&mem(4, 8, &[("0", "1A"), ("2", "FF")]), Format::Coe),
+        "memory_initialization_radix=16;\nmemory_initialization_vector=\n1A,\n00,\nFF;\n"
+    );
+}
+
+#[test]
+fn sizes() {
+    // Sizes must match the header
+    assert!(MemoryFile::import(Format::RtEasy, b"H 4 8", 4, 16, &Default::default()).is_err());
+    assert_eq!(import(Format::RtEasy, "H 4 8", 4, 8), MemoryFile::empty(4, 8));
+
+    // Value does not fit
+    assert!(MemoryFile::import(Format::ReadMemH, b"1FF", 4, 8, &Default::default()).is_err());
+    assert!(MemoryFile::import(Format::ReadMemH, b"@1F 0", 4, 8, &Default::default()).is_err());
+}
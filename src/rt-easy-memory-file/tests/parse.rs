@@ -118,3 +118,115 @@ fn comment() {
         );
     }
 }
+
+#[test]
+fn radix_prefix() {
+    let sources = [
+        r###"H 4 8
+'d 10 255
+'b110: 'o17"###,
+        r###"H 4 8
+A 'hFF
+6: 'b1111"###,
+        r###"B 4 8
+'h A FF 6: 'd15"###,
+    ];
+
+    for source in sources {
+        let mem = MemoryFile::parse(source).unwrap();
+        assert_eq!(mem, hex(4, 8, &[("0", "A"), ("1", "FF"), ("6", "F")]));
+    }
+}
+
+#[test]
+fn signed() {
+    let mem = MemoryFile::parse("H 4 8\n'd -1 -128 127 -0\n-2").unwrap();
+    assert_eq!(mem, hex(4, 8, &[("0", "FF"), ("1", "80"), ("2", "7F"), ("3", "0"), ("4", "FE")]));
+
+    assert!(MemoryFile::parse("H 4 8\n'd 256").is_err());
+    assert!(MemoryFile::parse("H 4 8\n'd -257").is_err());
+    assert!(MemoryFile::parse("H 4 8\n'd -129").is_err());
+    assert!(MemoryFile::parse("H 4 8\n'd -200").is_err());
+    assert!(MemoryFile::parse("H 4 8\n'd -255").is_err());
+}
+
+#[test]
+fn fill_and_repeat() {
+    let mem = MemoryFile::parse("H 4 8\nfill 3 FF\nrepeat 2 1 2\n'd fill 2 -1").unwrap();
+    assert_eq!(
+        mem,
+        hex(
+            4,
+            8,
+            &[
+                ("0", "FF"),
+                ("1", "FF"),
+                ("2", "FF"),
+                ("3", "1"),
+                ("4", "2"),
+                ("5", "1"),
+                ("6", "2"),
+                ("7", "FF"),
+                ("8", "FF"),
+            ]
+        )
+    );
+
+    assert!(MemoryFile::parse("H 4 8\nfill 3").is_err());
+    assert!(MemoryFile::parse("H 4 8\nrepeat 2").is_err());
+    assert!(MemoryFile::parse("H 4 8\nfill A 1").is_err());
+
+    // The count is limited by the addresses left
+    let mem = MemoryFile::parse("H 2 8\n1: fill 3 5").unwrap();
+    assert_eq!(mem, hex(2, 8, &[("1", "5"), ("2", "5"), ("3", "5")]));
+    assert!(MemoryFile::parse("H 2 8\n1: fill 4 5").is_err());
+    assert!(MemoryFile::parse("H 2 8\nrepeat 2 1 2 3").is_err());
+    assert!(MemoryFile::parse("H 64 8\nFFFFFFFFFFFFFFFF: fill 1 0").is_ok());
+    assert!(MemoryFile::parse("H 64 8\nFFFFFFFFFFFFFFFF: fill 2 0").is_err());
+    assert!(MemoryFile::parse("H 4 8\nfill 18446744073709551615 0").is_err());
+}
+
+#[test]
+fn address_range() {
+    let mem = MemoryFile::parse("H 4 8\n2..5: 1 2\n6").unwrap();
+    assert_eq!(mem, hex(4, 8, &[("2", "1"), ("3", "2"), ("4", "1"), ("5", "2"), ("6", "6")]));
+
+    let mem = MemoryFile::parse("H 2 8\n0..3: 7").unwrap();
+    assert_eq!(mem, hex(2, 8, &[("0", "7"), ("1", "7"), ("2", "7"), ("3", "7")]));
+
+    assert!(MemoryFile::parse("H 4 8\n5..2: 1").is_err());
+    assert!(MemoryFile::parse("H 4 8\n2..5:").is_err());
+}
+
+#[test]
+fn labels() {
+    let source = r###"H 4 8
+@start: @data @end  # forward references
+A:
+@data: 1 2 3
+@end:
+@start"###;
+
+    let mem = MemoryFile::parse(source).unwrap();
+    assert_eq!(
+        mem,
+        hex(4, 8, &[("0", "A"), ("1", "D"), ("A", "1"), ("B", "2"), ("C", "3"), ("D", "0")])
+    );
+
+    // Addresses must fit into the data register
+    assert!(MemoryFile::parse("H 8 4\n10:\n@label: @label").is_err());
+}
+
+#[test]
+fn display_round_trip() {
+    let mem = MemoryFile::parse("H 4 8\n'd -1 fill 2 7\n9..A: @x\n@x:").unwrap();
+    assert_eq!(MemoryFile::parse(&mem.to_string()).unwrap(), mem);
+}
+
+fn hex(ar_size: usize, dr_size: usize, data: &[(&str, &str)]) -> MemoryFile {
+    let data = data
+        .iter()
+        .map(|(addr, value)| (Value::parse_hex(addr).unwrap(), Value::parse_hex(value).unwrap()))
+        .collect();
+    MemoryFile::new(ar_size, dr_size, data).unwrap()
+}
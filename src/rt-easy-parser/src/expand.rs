@@ -0,0 +1,189 @@
+use crate::syntax::{Item, Macro, MacroCall};
+use crate::visit::{VisitorMut, Walk};
+use rtast::{Expansion, ExpansionId, Ident, Label, Span, Statement};
+use std::collections::{HashMap, HashSet};
+use std::{error::Error as StdError, fmt};
+
+#[derive(Debug)]
+pub struct ExpandError {
+    pub kind: ExpandErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum ExpandErrorKind {
+    DuplicateMacro(String),
+    MacroNotFound(String),
+    ArgumentCountMismatch { name: String, expected: usize, found: usize },
+    RecursiveMacro(String),
+    DuplicateStatementLabel(String),
+}
+
+impl fmt::Display for ExpandErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateMacro(name) => write!(f, "duplicate macro \"{}\"", name),
+            Self::MacroNotFound(name) => write!(f, "no macro named \"{}\" found", name),
+            Self::ArgumentCountMismatch { name, expected, found } => write!(
+                f,
+                "macro \"{}\" takes {} argument(s) but {} were supplied",
+                name, expected, found
+            ),
+            Self::RecursiveMacro(name) => write!(f, "macro \"{}\" calls itself", name),
+            Self::DuplicateStatementLabel(name) => write!(
+                f,
+                "the first statement of macro \"{}\" is already labeled, the call can not have a label",
+                name
+            ),
+        }
+    }
+}
+
+impl StdError for ExpandErrorKind {}
+
+/// Expands all macro calls in `items`.
+///
+/// Returns the resulting statements and the expansions their spans refer to. Calls that can not be
+/// expanded are skipped and reported as errors.
+pub fn expand<'s>(
+    macros: &[Macro<'s>],
+    items: Vec<Item<'s>>,
+) -> (Vec<Statement<'s>>, Vec<Expansion<'s>>, Vec<ExpandError>) {
+    let mut expander = Expander {
+        macros: HashMap::new(),
+        expansions: Vec::new(),
+        stack: Vec::new(),
+        errors: Vec::new(),
+    };
+    for macro_ in macros {
+        if expander.macros.contains_key(macro_.name.node.0) {
+            expander.errors.push(ExpandError {
+                kind: ExpandErrorKind::DuplicateMacro(macro_.name.node.0.to_string()),
+                span: macro_.name.span,
+            });
+        } else {
+            expander.macros.insert(macro_.name.node.0, macro_);
+        }
+    }
+
+    let mut statements = Vec::new();
+    expander.expand_items(items, &mut statements);
+
+    (statements, expander.expansions, expander.errors)
+}
+
+struct Expander<'m, 's> {
+    macros: HashMap<&'s str, &'m Macro<'s>>,
+    expansions: Vec<Expansion<'s>>,
+    stack: Vec<&'s str>,
+    errors: Vec<ExpandError>,
+}
+
+impl<'s> Expander<'_, 's> {
+    fn expand_items(&mut self, items: Vec<Item<'s>>, statements: &mut Vec<Statement<'s>>) {
+        for item in items {
+            match item {
+                Item::Statement(statement) => statements.push(statement),
+                Item::MacroCall(call) => {
+                    if let Err(error) = self.expand_call(call, statements) {
+                        self.errors.push(error);
+                    }
+                }
+            }
+        }
+    }
+
+    fn expand_call(
+        &mut self,
+        call: MacroCall<'s>,
+        statements: &mut Vec<Statement<'s>>,
+    ) -> Result<(), ExpandError> {
+        let name = call.name.node.0;
+        let error = |kind| Err(ExpandError { kind, span: call.span });
+
+        let macro_ = match self.macros.get(name) {
+            Some(macro_) => *macro_,
+            None => {
+                return Err(ExpandError {
+                    kind: ExpandErrorKind::MacroNotFound(name.to_string()),
+                    span: call.name.span,
+                })
+            }
+        };
+        if macro_.params.len() != call.args.len() {
+            return error(ExpandErrorKind::ArgumentCountMismatch {
+                name: name.to_string(),
+                expected: macro_.params.len(),
+                found: call.args.len(),
+            });
+        }
+        if self.stack.contains(&name) {
+            return error(ExpandErrorKind::RecursiveMacro(name.to_string()));
+        }
+
+        // Instantiate the body
+        let expansion = ExpansionId(self.expansions.len());
+        self.expansions.push(Expansion { name: call.name.node, call_site: call.span });
+        let mut items = macro_.items.clone();
+        items.walk(&mut Instantiate {
+            expansion,
+            args: macro_
+                .params
+                .iter()
+                .map(|param| param.node.0)
+                .zip(call.args.iter().map(|arg| arg.node))
+                .collect(),
+            labels: macro_
+                .items
+                .iter()
+                .filter_map(|item| item.label())
+                .map(|label| label.node.0)
+                .collect(),
+        });
+
+        // Expand
+        let start = statements.len();
+        self.stack.push(name);
+        self.expand_items(items, statements);
+        self.stack.pop();
+
+        // The label of the call belongs to the first statement of the body
+        if let Some(label) = call.label {
+            match statements.get_mut(start) {
+                Some(first) if first.label.is_some() => {
+                    return error(ExpandErrorKind::DuplicateStatementLabel(name.to_string()));
+                }
+                Some(first) => first.label = Some(label),
+                None => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Instantiates a macro body: Replaces the parameters with the arguments, renames the labels
+/// declared in the body and assigns the expansion to all spans.
+struct Instantiate<'s> {
+    expansion: ExpansionId,
+    args: HashMap<&'s str, Ident<'s>>,
+    labels: HashSet<&'s str>,
+}
+
+impl<'s> VisitorMut<'s> for Instantiate<'s> {
+    fn visit_span(&mut self, span: &mut Span) {
+        span.expansion = Some(self.expansion);
+    }
+
+    fn visit_ident(&mut self, ident: &mut Ident<'s>) {
+        if let Some(arg) = self.args.get(ident.0) {
+            *ident = *arg;
+        }
+    }
+
+    fn visit_label(&mut self, label: &mut Label<'s>) {
+        if self.labels.contains(label.0) {
+            *label = Label(label.0, Some(self.expansion));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::syntax::{Item, Macro, Module};
+use crate::visit::{SetFile, Walk};
+use crate::{expand, lex, parser, Token};
+use rtast::{Ast, Declaration, FileId, Include};
+use rtcore::files::{resolve_path, Files};
+use std::collections::HashSet;
+use std::{error::Error as StdError, fmt};
+
+/// A parser error together with the file it occurred in.
+#[derive(Debug)]
+pub struct FileError {
+    pub file: FileId,
+    pub error: toktok::Error<Token>,
+}
+
+/// The errors of all files together with the ast that could be recovered.
+#[derive(Debug)]
+pub struct FileErrors<'s> {
+    pub errors: Vec<FileError>,
+    /// The recovered ast. `None` if a declaration, macro definition or included file was lost.
+    pub partial: Option<Box<Ast<'s>>>,
+}
+
+#[derive(Debug)]
+enum IncludeError {
+    FileNotFound(String),
+    Cycle(String),
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileNotFound(name) => write!(f, "file not found: {}", name),
+            Self::Cycle(name) => write!(f, "cyclic include of {}", name),
+        }
+    }
+}
+
+impl StdError for IncludeError {}
+
+/// Adds all files that are (transitively) included by `root` to `files`.
+///
+/// `read` is called with the resolved name of every included file that is not yet part of
+/// `files`. Files that can not be read or parsed are skipped, the errors are reported by
+/// [`parse_files`](crate::parse_files).
+pub fn collect_files(
+    files: &mut Files,
+    root: FileId,
+    mut read: impl FnMut(&str) -> Option<String>,
+) {
+    let mut visited = HashSet::new();
+    let mut queue = vec![root];
+
+    while let Some(file) = queue.pop() {
+        if !visited.insert(file) {
+            continue;
+        }
+
+        let paths = match files.get(file) {
+            Some(current) => match include_paths(&current.source) {
+                Some(paths) => {
+                    paths.into_iter().map(|path| resolve_path(&current.name, &path)).collect()
+                }
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+
+        for name in paths {
+            match files.find(&name) {
+                Some(id) => queue.push(id),
+                None => {
+                    if let Some(source) = read(&name) {
+                        queue.push(files.add(name, source));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parses `root` and all files it includes.
+///
+/// The declarations, macros and statements of included files are placed in front of the ones of the
+/// including file, in the order of the includes. Every file is included at most once.
+pub fn parse_files(files: &Files, root: FileId) -> Result<Ast<'_>, FileErrors<'_>> {
+    let mut resolver = Resolver {
+        files,
+        included: HashSet::from([root]),
+        stack: vec![root],
+        declarations: Vec::new(),
+        macros: Vec::new(),
+        items: Vec::new(),
+        errors: Vec::new(),
+        is_incomplete: false,
+    };
+
+    let mut module = match resolver.parse_file(root, false) {
+        Some(module) => module,
+        None => return Err(FileErrors { errors: resolver.errors, partial: None }),
+    };
+    resolver.resolve_includes(root, &module.includes);
+    resolver.declarations.append(&mut module.declarations);
+    resolver.macros.append(&mut module.macros);
+    resolver.items.append(&mut module.items);
+
+    let (statements, expansions, expand_errors) = expand::expand(&resolver.macros, resolver.items);
+    for e in expand_errors {
+        resolver.errors.push(FileError {
+            file: e.span.file,
+            error: toktok::Error::new_custom(e.span.range(), Box::new(e.kind)),
+        });
+    }
+
+    let ast = Ast {
+        includes: module.includes,
+        declarations: resolver.declarations,
+        statements,
+        trailing_label: module.trailing_label,
+        expansions,
+    };
+    if resolver.errors.is_empty() {
+        Ok(ast)
+    } else {
+        let partial = if resolver.is_incomplete { None } else { Some(Box::new(ast)) };
+        Err(FileErrors { errors: resolver.errors, partial })
+    }
+}
+
+struct Resolver<'s> {
+    files: &'s Files,
+    included: HashSet<FileId>,
+    stack: Vec<FileId>,
+    declarations: Vec<Declaration<'s>>,
+    macros: Vec<Macro<'s>>,
+    items: Vec<Item<'s>>,
+    errors: Vec<FileError>,
+    is_incomplete: bool,
+}
+
+impl<'s> Resolver<'s> {
+    fn resolve_includes(&mut self, file: FileId, includes: &[Include<'s>]) {
+        let from = &self.files.get(file).expect("file must exist").name;
+
+        for include in includes {
+            let name = resolve_path(from, include.path.node);
+            let error = |kind| FileError {
+                file,
+                error: toktok::Error::new_custom(include.path.span.range(), Box::new(kind)),
+            };
+
+            let id = match self.files.find(&name) {
+                Some(id) => id,
+                None => {
+                    self.errors.push(error(IncludeError::FileNotFound(name)));
+                    self.is_incomplete = true;
+                    continue;
+                }
+            };
+            if self.stack.contains(&id) {
+                self.errors.push(error(IncludeError::Cycle(name)));
+                continue;
+            }
+            if !self.included.insert(id) {
+                continue;
+            }
+
+            let module = match self.parse_file(id, true) {
+                Some(module) => module,
+                None => continue,
+            };
+            self.stack.push(id);
+            self.resolve_includes(id, &module.includes);
+            self.stack.pop();
+
+            self.declarations.extend(module.declarations);
+            self.macros.extend(module.macros);
+            self.items.extend(module.items);
+        }
+    }
+
+    /// Parses a single file. Included files can not end with a label.
+    fn parse_file(&mut self, file: FileId, is_include: bool) -> Option<Module<'s>> {
+        let source = &self.files.get(file).expect("file must exist").source;
+
+        let (tokens, lex_errors) = lex(source);
+        self.errors.extend(lex_errors.into_iter().map(|error| FileError { file, error }));
+
+        let state = toktok::State::new(source, &tokens);
+        let result =
+            if is_include { parser::module_include_eoi(state) } else { parser::module_eoi(state) };
+        let mut module = match result {
+            Ok((_, module)) => module,
+            Err(error) => {
+                self.errors.push(FileError { file, error: error.into() });
+                self.is_incomplete = true;
+                return None;
+            }
+        };
+
+        module.walk(&mut SetFile(file));
+        self.errors.extend(module.errors.drain(..).map(|error| FileError { file, error }));
+        self.is_incomplete |= module.is_incomplete;
+
+        Some(module)
+    }
+}
+
+/// Returns the paths of the includes at the start of `source` or `None` if they can not be
+/// parsed.
+fn include_paths(source: &str) -> Option<Vec<String>> {
+    let (tokens, _) = lex(source);
+    let state = toktok::State::new(source, &tokens);
+    let (_, includes) = parser::includes(state).ok()?;
+
+    Some(includes.into_iter().map(|include| include.path.node.to_string()).collect())
+}
@@ -1,4 +1,5 @@
 use logos::{Lexer, Logos};
+use std::fmt;
 
 #[derive(Logos, Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub enum Token {
@@ -35,6 +36,8 @@ pub enum Token {
     LiteralNumberDec,
     #[regex("\"[01]+\"")]
     LiteralNumberBitString,
+    #[regex("\"[^\"\r\n]*\"", priority = 1)]
+    LiteralString,
 
     #[token("declare")]
     KeywordDeclare,
@@ -50,6 +53,8 @@ pub enum Token {
     KeywordMemory,
     #[token("array")]
     KeywordArray,
+    #[token("constant")]
+    KeywordConstant,
     #[token("nop")]
     KeywordNop,
     #[token("goto")]
@@ -74,6 +79,10 @@ pub enum Token {
     KeywordDefault,
     #[token("assert")]
     KeywordAssert,
+    #[token("include")]
+    KeywordInclude,
+    #[token("define")]
+    KeywordDefine,
 
     // Binary Operators
     #[token("=")]
@@ -92,6 +101,8 @@ pub enum Token {
     OperatorAddition,
     #[token("-")]
     OperatorSubtraction,
+    #[token("*")]
+    OperatorMultiplication,
     #[token("and")]
     OperatorAnd,
     #[token("nand")]
@@ -126,6 +137,29 @@ fn is_valid_ident(lex: &mut Lexer<'_, Token>) -> bool {
         && lex.slice().chars().any(|c| c != '_')
 }
 
+/// Returns `true` if `slice` would be an identifier if it was uppercase.
+pub fn is_lowercase_ident(slice: &str) -> bool {
+    let mut chars = slice.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && slice.chars().any(|c| c.is_ascii_lowercase())
+}
+
+#[derive(Debug)]
+pub struct LowercaseIdentError(pub String);
+
+impl fmt::Display for LowercaseIdentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "identifiers must be uppercase, did you mean \"{}\"?",
+            self.0.to_ascii_uppercase()
+        )
+    }
+}
+
+impl std::error::Error for LowercaseIdentError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,32 +1,93 @@
 #![deny(rust_2018_idioms)]
 
+mod expand;
+mod include;
 mod lexer;
 mod parser;
+mod recovery;
+mod syntax;
+mod visit;
 
+pub use self::include::{collect_files, parse_files, FileError, FileErrors};
 pub use self::lexer::Token;
 
-fn lex(source: &str) -> Result<Vec<toktok::SpannedToken<Token>>, toktok::Error<Token>> {
+/// The syntax errors of a source together with the ast that could be recovered.
+#[derive(Debug)]
+pub struct Errors<'s> {
+    pub errors: Vec<toktok::Error<Token>>,
+    /// The recovered ast. `None` if a declaration or macro definition was lost, semantic checks
+    /// on the recovered ast would report follow-up errors in that case.
+    pub partial: Option<Box<rtast::Ast<'s>>>,
+}
+
+/// Lexes `source`. Lowercase identifiers are reported and lexed as identifiers, so that parsing
+/// can continue.
+fn lex(source: &str) -> (Vec<toktok::SpannedToken<Token>>, Vec<toktok::Error<Token>>) {
     use logos::Logos;
 
     let mut lexer = Token::lexer(source);
     let mut tokens = Vec::new();
+    let mut errors = Vec::new();
     while let Some(token) = lexer.next() {
+        let token = match token {
+            Token::Error if lexer::is_lowercase_ident(lexer.slice()) => {
+                errors.push(toktok::Error::new_custom(
+                    lexer.span(),
+                    Box::new(lexer::LowercaseIdentError(lexer.slice().to_string())),
+                ));
+                Token::Identifier
+            }
+            token => token,
+        };
         tokens.push(toktok::SpannedToken { token, span: lexer.span() });
     }
 
-    Ok(tokens)
+    (tokens, errors)
 }
 
-pub fn parse(source: &str) -> Result<rtast::Ast<'_>, toktok::Error<Token>> {
-    let tokens = lex(source)?;
-    let state = toktok::State::new(source, &tokens);
-    let (_, ast) = parser::ast_eoi(state)?;
+/// Lexes `source` and fails on the first error.
+fn lex_strict(source: &str) -> Result<Vec<toktok::SpannedToken<Token>>, toktok::Error<Token>> {
+    match lex(source) {
+        (tokens, errors) if errors.is_empty() => Ok(tokens),
+        (_, errors) => Err(errors.into_iter().next().unwrap()),
+    }
+}
 
-    Ok(ast)
+/// Parses `source`. The parser recovers from syntax errors at statement and declaration level,
+/// so all syntax errors are reported at once.
+pub fn parse(source: &str) -> Result<rtast::Ast<'_>, Errors<'_>> {
+    let (tokens, mut errors) = lex(source);
+    let mut module = match parser::module_eoi(toktok::State::new(source, &tokens)) {
+        Ok((_, module)) => module,
+        Err(e) => {
+            errors.push(e.into());
+            return Err(Errors { errors, partial: None });
+        }
+    };
+    errors.append(&mut module.errors);
+
+    let (statements, expansions, expand_errors) = expand::expand(&module.macros, module.items);
+    for e in expand_errors {
+        errors.push(toktok::Error::new_custom(e.span.range(), Box::new(e.kind)));
+    }
+
+    let ast = rtast::Ast {
+        includes: module.includes,
+        declarations: module.declarations,
+        statements,
+        trailing_label: module.trailing_label,
+        expansions,
+    };
+    if errors.is_empty() {
+        Ok(ast)
+    } else {
+        let partial = if module.is_incomplete { None } else { Some(Box::new(ast)) };
+        Err(Errors { errors, partial })
+    }
 }
 
 pub fn parse_assignment(source: &str) -> Result<rtast::Assignment<'_>, toktok::Error<Token>> {
-    let tokens = lex(source)?;
+    let tokens = lex_strict(source)?;
     let state = toktok::State::new(source, &tokens);
     let (_, assignment) = parser::assignment_eoi(state)?;
 
@@ -34,13 +95,21 @@ pub fn parse_assignment(source: &str) -> Result<rtast::Assignment<'_>, toktok::E
 }
 
 pub fn parse_assert(source: &str) -> Result<rtast::Assert<'_>, toktok::Error<Token>> {
-    let tokens = lex(source)?;
+    let tokens = lex_strict(source)?;
     let state = toktok::State::new(source, &tokens);
     let (_, assert) = parser::assert_eoi(state)?;
 
     Ok(assert)
 }
 
+pub fn parse_expression(source: &str) -> Result<rtast::Expression<'_>, toktok::Error<Token>> {
+    let tokens = lex_strict(source)?;
+    let state = toktok::State::new(source, &tokens);
+    let (_, expression) = parser::expression_eoi(state)?;
+
+    Ok(expression)
+}
+
 pub fn pretty_print_error(
     error: &toktok::Error<Token>,
     source: &str,
@@ -51,6 +120,21 @@ pub fn pretty_print_error(
         source: Some(source),
         file_name,
         ansi_colors,
+        ..pretty_print_options()
+    };
+    error.pretty_print(&options)
+}
+
+/// The message of the error, without the source.
+pub fn error_message(error: &toktok::Error<Token>) -> String {
+    error.message(&pretty_print_options())
+}
+
+fn pretty_print_options<'a>() -> toktok::PrettyPrintOptions<'a, Token> {
+    toktok::PrettyPrintOptions {
+        source: None,
+        file_name: None,
+        ansi_colors: false,
         rename_token: Some(Box::new(|token: &toktok::TokenOrEoi<Token>| {
             use Token::*;
 
@@ -77,6 +161,7 @@ pub fn pretty_print_error(
                 LiteralNumberHex => "<NUM_HEX>",
                 LiteralNumberDec => "<NUM_DEC>",
                 LiteralNumberBitString => "<NUM_BIT_STRING>",
+                LiteralString => "<STRING>",
 
                 KeywordDeclare => "\"declare\"",
                 KeywordInput => "\"input\"",
@@ -85,6 +170,7 @@ pub fn pretty_print_error(
                 KeywordBus => "\"bus\"",
                 KeywordMemory => "\"memory\"",
                 KeywordArray => "\"array\"",
+                KeywordConstant => "\"constant\"",
                 KeywordNop => "\"nop\"",
                 KeywordGoto => "\"goto\"",
                 KeywordRead => "\"read\"",
@@ -97,6 +183,8 @@ pub fn pretty_print_error(
                 KeywordCase => "\"case\"",
                 KeywordDefault => "\"default\"",
                 KeywordAssert => "\"assert\"",
+                KeywordInclude => "\"include\"",
+                KeywordDefine => "\"define\"",
 
                 OperatorEquality => "\"=\"",
                 OperatorInequality => "\"<>\"",
@@ -106,6 +194,7 @@ pub fn pretty_print_error(
                 OperatorGreater => "\">\"",
                 OperatorAddition => "\"+\"",
                 OperatorSubtraction => "\"-\"",
+                OperatorMultiplication => "\"*\"",
                 OperatorAnd => "\"and\"",
                 OperatorNand => "\"nand\"",
                 OperatorOr => "\"or\"",
@@ -134,6 +223,52 @@ pub fn pretty_print_error(
 
             expected
         })),
-    };
-    error.pretty_print(&options)
+    }
+}
+
+/// Pretty prints all errors in the order they appear in the source.
+pub fn pretty_print_errors(
+    errors: &Errors<'_>,
+    source: &str,
+    file_name: Option<&str>,
+    ansi_colors: bool,
+) -> String {
+    let mut errors = errors.errors.iter().collect::<Vec<_>>();
+    errors.sort_by_key(|error| error_position(error));
+    errors
+        .into_iter()
+        .map(|error| pretty_print_error(error, source, file_name, ansi_colors))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Pretty prints all errors, sorted by file and position.
+pub fn pretty_print_file_errors(
+    errors: &FileErrors<'_>,
+    files: &rtcore::files::Files,
+    ansi_colors: bool,
+) -> String {
+    let mut errors = errors.errors.iter().collect::<Vec<_>>();
+    errors.sort_by_key(|error| (error.file, error_position(&error.error)));
+    errors
+        .into_iter()
+        .map(|error| pretty_print_file_error(error, files, ansi_colors))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn error_position(error: &toktok::Error<Token>) -> usize {
+    match error.span() {
+        toktok::Span::Range(range) => range.start,
+        toktok::Span::Eoi => usize::MAX,
+    }
+}
+
+pub fn pretty_print_file_error(
+    error: &FileError,
+    files: &rtcore::files::Files,
+    ansi_colors: bool,
+) -> String {
+    let file = files.get(error.file).expect("error belongs to an unknown file");
+    pretty_print_error(&error.error, &file.source, Some(&file.name), ansi_colors)
 }
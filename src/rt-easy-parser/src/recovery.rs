@@ -0,0 +1,120 @@
+//! Synchronization points for the error recovery of the parser.
+//!
+//! After a definition or an item failed to parse, the tokens of the erroneous part are skipped up
+//! to and including the next `;` or up to the next `declare`/`define`. Braces are skipped as a
+//! whole, so a broken macro body or switch does not leave a stray `}` behind.
+
+use crate::lexer::Token;
+use toktok::{Error, PResult, Span, SpannedToken, State, TokenOrEoi};
+
+/// Skips an erroneous declaration or macro definition.
+pub fn sync_definition<'s, 't>(state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    match state.first().map(|first| first.token) {
+        Some(Token::KeywordDeclare) | Some(Token::KeywordDefine) => skip(state),
+        _ => no_sync(state),
+    }
+}
+
+/// Skips an erroneous statement or macro call.
+pub fn sync_item<'s, 't>(state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    match state.first().map(|first| first.token) {
+        Some(
+            Token::Identifier
+            | Token::KeywordNop
+            | Token::KeywordGoto
+            | Token::KeywordRead
+            | Token::KeywordWrite
+            | Token::KeywordIf
+            | Token::KeywordSwitch
+            | Token::KeywordAssert,
+        ) if !is_trailing_label(&state) => skip(state),
+        _ => no_sync(state),
+    }
+}
+
+/// Always fails, used to report tokens that neither start a definition nor an item.
+pub fn unexpected<'s, 't>(state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    no_sync(state)
+}
+
+/// Skips unexpected tokens.
+pub fn sync_unexpected<'s, 't>(state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    match state.first() {
+        None => no_sync(state),
+        Some(_) if is_trailing_label(&state) => no_sync(state),
+        Some(_) => skip(state),
+    }
+}
+
+fn is_trailing_label(state: &State<'_, '_, Token>) -> bool {
+    matches!(
+        state.input().tokens(),
+        [SpannedToken { token: Token::Identifier, .. }, SpannedToken { token: Token::Colon, .. }]
+    )
+}
+
+fn skip<'s, 't>(mut state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()>
+where
+    's: 't,
+{
+    let mut depth = 0usize;
+    let mut skipped = 0usize;
+
+    while let Some(first) = state.first() {
+        match first.token {
+            Token::KeywordDeclare | Token::KeywordDefine if depth == 0 && skipped != 0 => break,
+            // The end of a macro body, a stray brace is skipped
+            Token::BraceClose if depth == 0 => {
+                if skipped == 0 {
+                    state = state.split_first().0;
+                }
+                break;
+            }
+            Token::Semicolon if depth == 0 => {
+                state = state.split_first().0;
+                break;
+            }
+            Token::BraceOpen => depth += 1,
+            Token::BraceClose => {
+                depth -= 1;
+                if depth == 0 {
+                    state = state.split_first().0;
+                    if state.first().map(|first| first.token) == Some(Token::Semicolon) {
+                        state = state.split_first().0;
+                    }
+                    break;
+                }
+            }
+            _ => (),
+        }
+
+        state = state.split_first().0;
+        skipped += 1;
+    }
+
+    Ok((state, ()))
+}
+
+fn no_sync<'s, 't>(state: State<'s, 't, Token>) -> PResult<'s, 't, Token, ()> {
+    let error = match state.first() {
+        Some(first) => Error::new_expected(
+            Span::Range(first.span.clone()),
+            Vec::new(),
+            TokenOrEoi::Token(first.token),
+        ),
+        None => Error::new_expected(Span::Eoi, Vec::new(), TokenOrEoi::Eoi),
+    };
+    Err(state.and_error(error))
+}
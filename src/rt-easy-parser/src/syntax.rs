@@ -0,0 +1,178 @@
+//! The syntax tree of a single file, before includes and macros are resolved.
+
+use crate::lexer::Token;
+use rtast::{Declaration, Ident, Include, Label, Span, Spanned, Statement};
+use std::{error::Error as StdError, fmt};
+
+pub type SyntaxError = toktok::Error<Token>;
+
+#[derive(Debug)]
+pub struct Module<'s> {
+    pub includes: Vec<Include<'s>>,
+    pub declarations: Vec<Declaration<'s>>,
+    pub macros: Vec<Macro<'s>>,
+    pub items: Vec<Item<'s>>,
+    pub trailing_label: Option<Spanned<Label<'s>>>,
+    /// The syntax errors the parser recovered from.
+    pub errors: Vec<SyntaxError>,
+    /// Whether a declaration or macro definition may be missing or incomplete because of a syntax
+    /// error.
+    pub is_incomplete: bool,
+}
+
+impl<'s> Module<'s> {
+    pub fn new(
+        includes: Vec<Include<'s>>,
+        elements: Vec<Element<'s>>,
+        trailing_label: Option<Spanned<Label<'s>>>,
+    ) -> Self {
+        let mut module = Self {
+            includes,
+            declarations: Vec::new(),
+            macros: Vec::new(),
+            items: Vec::new(),
+            trailing_label,
+            errors: Vec::new(),
+            is_incomplete: false,
+        };
+
+        for element in elements {
+            match element {
+                Element::Definition(Ok(definition)) => {
+                    if !module.items.is_empty() {
+                        module.error(ModuleError::DefinitionAfterStatement, definition.span());
+                    }
+                    match definition {
+                        Definition::Declaration(declaration) => {
+                            module.declarations.push(declaration)
+                        }
+                        Definition::Macro(macro_, mut errors) => {
+                            module.macros.push(macro_);
+                            module.errors.append(&mut errors);
+                        }
+                    }
+                }
+                Element::Definition(Err(error)) => {
+                    module.errors.push(error);
+                    module.is_incomplete = true;
+                }
+                Element::Item(Ok(item)) => module.items.push(item),
+                Element::Item(Err(error)) => module.errors.push(error),
+                Element::Unexpected(error) => {
+                    // Before the first statement the unexpected tokens are most likely the rest
+                    // of a declaration that was parsed only partially
+                    module.is_incomplete |= module.items.is_empty();
+                    module.errors.push(error);
+                }
+            }
+        }
+
+        module
+    }
+
+    /// An included file, which can not end with a label.
+    pub fn new_include(includes: Vec<Include<'s>>, elements: Vec<Element<'s>>) -> Self {
+        Self::new(includes, elements, None)
+    }
+
+    fn error(&mut self, error: ModuleError, span: Span) {
+        self.errors.push(toktok::Error::new_custom(span.range(), Box::new(error)));
+    }
+}
+
+#[derive(Debug)]
+enum ModuleError {
+    DefinitionAfterStatement,
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefinitionAfterStatement => {
+                write!(f, "declarations and macros must be placed before the first statement")
+            }
+        }
+    }
+}
+
+impl StdError for ModuleError {}
+
+/// A top level element of a module. Erroneous elements are skipped by the parser.
+#[derive(Debug)]
+pub enum Element<'s> {
+    Definition(Result<Definition<'s>, SyntaxError>),
+    Item(Result<Item<'s>, SyntaxError>),
+    /// Tokens that neither start a definition nor an item.
+    Unexpected(SyntaxError),
+}
+
+#[derive(Debug)]
+pub enum Definition<'s> {
+    Declaration(Declaration<'s>),
+    /// A macro together with the syntax errors in its body.
+    Macro(Macro<'s>, Vec<SyntaxError>),
+}
+
+impl<'s> Definition<'s> {
+    pub fn macro_(
+        name: Spanned<Ident<'s>>,
+        params: Vec<Spanned<Ident<'s>>>,
+        items: Vec<Result<Item<'s>, SyntaxError>>,
+        span: Span,
+    ) -> Self {
+        let mut errors = Vec::new();
+        let items = split_errors(items, &mut errors);
+        Self::Macro(Macro { name, params, items, span }, errors)
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Declaration(declaration) => declaration.span(),
+            Self::Macro(macro_, _) => macro_.span,
+        }
+    }
+}
+
+/// `define NAME(PARAM, ...) { ... }`
+#[derive(Debug, Clone)]
+pub struct Macro<'s> {
+    pub name: Spanned<Ident<'s>>,
+    pub params: Vec<Spanned<Ident<'s>>>,
+    pub items: Vec<Item<'s>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum Item<'s> {
+    Statement(Statement<'s>),
+    MacroCall(MacroCall<'s>),
+}
+
+impl<'s> Item<'s> {
+    pub fn label(&self) -> Option<Spanned<Label<'s>>> {
+        match self {
+            Self::Statement(statement) => statement.label,
+            Self::MacroCall(call) => call.label,
+        }
+    }
+}
+
+/// `NAME(ARG, ...);`, the span covers the name and the arguments.
+#[derive(Debug, Clone)]
+pub struct MacroCall<'s> {
+    pub label: Option<Spanned<Label<'s>>>,
+    pub name: Spanned<Ident<'s>>,
+    pub args: Vec<Spanned<Ident<'s>>>,
+    pub span: Span,
+}
+
+fn split_errors<T>(results: Vec<Result<T, SyntaxError>>, errors: &mut Vec<SyntaxError>) -> Vec<T> {
+    let mut oks = Vec::new();
+    for result in results {
+        match result {
+            Ok(ok) => oks.push(ok),
+            Err(error) => errors.push(error),
+        }
+    }
+    oks
+}
@@ -0,0 +1,344 @@
+//! Mutable traversal of the syntax tree.
+//!
+//! Used to assign file ids to the spans of a parsed file (the generated parser only knows byte
+//! offsets) and to instantiate macro bodies.
+
+use crate::syntax::{Item, MacroCall, Module};
+use rtast::*;
+
+pub trait VisitorMut<'s> {
+    fn visit_span(&mut self, _span: &mut Span) {}
+    fn visit_ident(&mut self, _ident: &mut Ident<'s>) {}
+    fn visit_label(&mut self, _label: &mut Label<'s>) {}
+}
+
+pub trait Walk<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>);
+}
+
+impl<'s> Walk<'s> for Span {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        v.visit_span(self);
+    }
+}
+
+impl<'s> Walk<'s> for Ident<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        v.visit_ident(self);
+    }
+}
+
+impl<'s> Walk<'s> for Label<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        v.visit_label(self);
+    }
+}
+
+impl<'s, T: Walk<'s>> Walk<'s> for Spanned<T> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.node.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s, T: Walk<'s>> Walk<'s> for Option<T> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        if let Some(node) = self {
+            node.walk(v);
+        }
+    }
+}
+
+impl<'s, T: Walk<'s>> Walk<'s> for Vec<T> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        for node in self {
+            node.walk(v);
+        }
+    }
+}
+
+impl<'s, T: Walk<'s>> Walk<'s> for Box<T> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        (**self).walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Module<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        for include in &mut self.includes {
+            include.path.span.walk(v);
+            include.span.walk(v);
+        }
+        self.declarations.walk(v);
+        for macro_ in &mut self.macros {
+            macro_.name.walk(v);
+            macro_.params.walk(v);
+            macro_.items.walk(v);
+            macro_.span.walk(v);
+        }
+        self.items.walk(v);
+        self.trailing_label.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Item<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Statement(n) => n.walk(v),
+            Self::MacroCall(n) => n.walk(v),
+        }
+    }
+}
+
+impl<'s> Walk<'s> for MacroCall<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        // The name refers to a macro, not to a symbol
+        self.label.walk(v);
+        self.name.span.walk(v);
+        self.args.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Declaration<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Register(n) => {
+                n.registers.walk(v);
+                n.span.walk(v);
+            }
+            Self::Bus(n) => {
+                n.buses.walk(v);
+                n.span.walk(v);
+            }
+            Self::Memory(n) => {
+                n.memories.walk(v);
+                n.span.walk(v);
+            }
+            Self::RegisterArray(n) => {
+                n.register_arrays.walk(v);
+                n.span.walk(v);
+            }
+            Self::Constant(n) => {
+                n.constants.walk(v);
+                n.span.walk(v);
+            }
+        }
+    }
+}
+
+impl<'s> Walk<'s> for DeclareRegisterItem<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.range.walk(v);
+        self.init.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for DeclareRegisterArrayItem<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.range.walk(v);
+        self.len.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for DeclareConstantItem<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.value.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Memory<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.range.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for MemoryRange<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.address_register.walk(v);
+        self.data_register.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for BitRangeExpr<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.msb.walk(v);
+        self.lsb.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for ConstUsize<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Number(_) => (),
+            Self::Constant(ident) => ident.walk(v),
+        }
+    }
+}
+
+impl<'s> Walk<'s> for RegBus<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.range.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for RegisterArray<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.ident.walk(v);
+        self.index.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Concat<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.parts.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for ConcatPart<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::RegBus(n) => n.walk(v),
+            Self::RegisterArray(n) => n.walk(v),
+            Self::Number(n) => n.span.walk(v),
+        }
+    }
+}
+
+impl<'s> Walk<'s> for Statement<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.label.walk(v);
+        self.operations.walk(v);
+        self.span.walk(v);
+        self.span_semicolon.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Operations<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.operations.walk(v);
+        self.operations_post.walk(v);
+        self.span.walk(v);
+        self.span_pipe.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Operation<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Nop(n) => n.span.walk(v),
+            Self::Goto(n) => {
+                n.label.walk(v);
+                n.span.walk(v);
+            }
+            Self::Write(n) => {
+                n.ident.walk(v);
+                n.span.walk(v);
+            }
+            Self::Read(n) => {
+                n.ident.walk(v);
+                n.span.walk(v);
+            }
+            Self::If(n) => {
+                n.condition.walk(v);
+                n.operations_if.walk(v);
+                n.operations_else.walk(v);
+                n.span.walk(v);
+            }
+            Self::Switch(n) => {
+                n.expression.walk(v);
+                n.clauses.walk(v);
+                n.span.walk(v);
+            }
+            Self::Assignment(n) => n.walk(v),
+            Self::Assert(n) => {
+                n.condition.walk(v);
+                n.span.walk(v);
+            }
+        }
+    }
+}
+
+impl<'s> Walk<'s> for Clause<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match &mut self.clause {
+            Either::Left(case) => {
+                case.value.walk(v);
+                case.span.walk(v);
+            }
+            Either::Right(default) => default.span.walk(v),
+        }
+        self.operations.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Assignment<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        self.lhs.walk(v);
+        self.rhs.walk(v);
+        self.span.walk(v);
+    }
+}
+
+impl<'s> Walk<'s> for Lvalue<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::RegBus(n) => n.walk(v),
+            Self::RegisterArray(n) => n.walk(v),
+            Self::Concat(n) => n.walk(v),
+        }
+    }
+}
+
+impl<'s> Walk<'s> for Expression<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Atom(n) => n.walk(v),
+            Self::BinaryTerm(n) => {
+                n.lhs.walk(v);
+                n.rhs.walk(v);
+                n.operator.span.walk(v);
+                n.span.walk(v);
+            }
+            Self::UnaryTerm(n) => {
+                n.expression.walk(v);
+                n.operator.span.walk(v);
+                n.span.walk(v);
+            }
+        }
+    }
+}
+
+impl<'s> Walk<'s> for Atom<'s> {
+    fn walk(&mut self, v: &mut impl VisitorMut<'s>) {
+        match self {
+            Self::Concat(n) => n.walk(v),
+            Self::RegBus(n) => n.walk(v),
+            Self::RegisterArray(n) => n.walk(v),
+            Self::Number(n) => n.span.walk(v),
+        }
+    }
+}
+
+/// Assigns the file to all visited spans.
+pub struct SetFile(pub FileId);
+
+impl VisitorMut<'_> for SetFile {
+    fn visit_span(&mut self, span: &mut Span) {
+        span.file = self.0;
+    }
+}
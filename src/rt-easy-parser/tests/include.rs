@@ -0,0 +1,147 @@
+use rtast::{Declaration, FileId};
+use rtcore::files::{resolve_path, Files};
+
+fn files(files: &[(&str, &str)]) -> Files {
+    let mut result = Files::new();
+    for (name, source) in files {
+        result.add(*name, *source);
+    }
+    result
+}
+
+fn declared_idents<'s>(declarations: &[Declaration<'s>]) -> Vec<(&'s str, FileId)> {
+    declarations
+        .iter()
+        .flat_map(|declaration| match declaration {
+            Declaration::Register(declare) => declare
+                .registers
+                .iter()
+                .map(|reg| (reg.ident.node.0, reg.ident.span.file))
+                .collect(),
+            Declaration::Bus(declare) => {
+                declare.buses.iter().map(|bus| (bus.ident.node.0, bus.ident.span.file)).collect()
+            }
+            Declaration::Constant(declare) => {
+                declare.constants.iter().map(|c| (c.ident.node.0, c.ident.span.file)).collect()
+            }
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+#[test]
+fn include() {
+    let files = files(&[
+        (
+            "src/main.rt",
+            r#"include "decl.rt"
+            include "../common/width.rt"
+            declare register X(WIDTH:0)
+            X <- X + 1;"#,
+        ),
+        ("src/decl.rt", r#"include "../common/width.rt" declare bus B(7:0)"#),
+        ("common/width.rt", "declare constant WIDTH = 8"),
+    ]);
+
+    let ast = rt_easy_parser::parse_files(&files, FileId(0)).unwrap();
+    assert_eq!(
+        declared_idents(&ast.declarations),
+        vec![("WIDTH", FileId(2)), ("B", FileId(1)), ("X", FileId(0))]
+    );
+    assert_eq!(ast.statements.len(), 1);
+    assert_eq!(ast.statements[0].span.file, FileId(0));
+}
+
+#[test]
+fn include_bit_string_name() {
+    let files = files(&[("main.rt", r#"include "01" X <- 1;"#), ("01", "declare register X")]);
+
+    let ast = rt_easy_parser::parse_files(&files, FileId(0)).unwrap();
+    assert_eq!(declared_idents(&ast.declarations), vec![("X", FileId(1))]);
+}
+
+#[test]
+fn include_not_found() {
+    let files = files(&[("main.rt", r#"include "missing.rt" nop;"#)]);
+
+    let errors = rt_easy_parser::parse_files(&files, FileId(0)).unwrap_err();
+    assert_eq!(errors.errors.len(), 1);
+    assert_eq!(errors.errors[0].file, FileId(0));
+    assert!(errors.partial.is_none());
+    assert_eq!(
+        rt_easy_parser::pretty_print_file_errors(&errors, &files, false),
+        r#" --> main.rt:1:9
+  |
+1 |    include "missing.rt" nop;
+  |            ^^^^^^^^^^^^
+  |
+  = file not found: missing.rt"#
+    );
+}
+
+#[test]
+fn include_cycle() {
+    let files = files(&[
+        ("main.rt", r#"include "a.rt" nop;"#),
+        ("a.rt", r#"include "b.rt""#),
+        ("b.rt", r#"include "a.rt""#),
+    ]);
+
+    let errors = rt_easy_parser::parse_files(&files, FileId(0)).unwrap_err();
+    assert_eq!(errors.errors.len(), 1);
+    assert_eq!(errors.errors[0].file, FileId(2));
+}
+
+#[test]
+fn include_statements() {
+    let files = files(&[
+        ("main.rt", r#"include "a.rt" include "b.rt" X <- 3;"#),
+        ("a.rt", r#"include "b.rt" declare register X X <- 1;"#),
+        ("b.rt", "X <- 0;"),
+    ]);
+
+    let ast = rt_easy_parser::parse_files(&files, FileId(0)).unwrap();
+    let statement_files =
+        ast.statements.iter().map(|statement| statement.span.file).collect::<Vec<_>>();
+    assert_eq!(statement_files, vec![FileId(2), FileId(1), FileId(0)]);
+}
+
+#[test]
+fn include_trailing_label() {
+    let files = files(&[("main.rt", r#"include "lib.rt" nop;"#), ("lib.rt", "nop; END:")]);
+
+    let errors = rt_easy_parser::parse_files(&files, FileId(0)).unwrap_err();
+    assert_eq!(errors.errors.len(), 1);
+    assert_eq!(errors.errors[0].file, FileId(1));
+}
+
+#[test]
+fn collect_files() {
+    let mut files = files(&[("src/main.rt", r#"include "a.rt" include "../b.rt" nop;"#)]);
+
+    let mut read = Vec::new();
+    rt_easy_parser::collect_files(&mut files, FileId(0), |name| {
+        read.push(name.to_string());
+        match name {
+            "src/a.rt" => Some(r#"include "../b.rt" include "c.rt""#.to_string()),
+            "b.rt" => Some("declare register B".to_string()),
+            _ => None,
+        }
+    });
+
+    read.sort();
+    assert_eq!(read, vec!["b.rt", "src/a.rt", "src/c.rt"]);
+    assert_eq!(files.len(), 3);
+    assert!(rt_easy_parser::parse_files(&files, FileId(0)).is_err());
+}
+
+#[test]
+fn resolve() {
+    assert_eq!(resolve_path("main.rt", "lib.rt"), "lib.rt");
+    assert_eq!(resolve_path("src/main.rt", "./lib.rt"), "src/lib.rt");
+    assert_eq!(resolve_path("src/main.rt", "../common/decl.rt"), "common/decl.rt");
+    assert_eq!(resolve_path("src\\main.rt", "lib\\decl.rt"), "src/lib/decl.rt");
+    assert_eq!(resolve_path("/home/src/main.rt", "lib.rt"), "/home/src/lib.rt");
+    assert_eq!(resolve_path("src/main.rt", "/lib/decl.rt"), "/lib/decl.rt");
+    assert_eq!(resolve_path("main.rt", "../lib.rt"), "../lib.rt");
+}
@@ -0,0 +1,48 @@
+mod util;
+
+#[test]
+fn lowercase_ident() {
+    const SOURCE: &str = "declare register counter(7:0)\nCOUNTER <- Counter + 1;";
+
+    let errors = util::parse_err(SOURCE);
+    assert_eq!(
+        rt_easy_parser::pretty_print_errors(&errors, SOURCE, None, false),
+        r#" --> 1:18
+  |
+1 |    declare register counter(7:0)
+  |                     ^^^^^^^
+  |
+  = identifiers must be uppercase, did you mean "COUNTER"?
+
+ --> 2:12
+  |
+2 |    COUNTER <- Counter + 1;
+  |               ^^^^^^^
+  |
+  = identifiers must be uppercase, did you mean "COUNTER"?"#
+    );
+
+    // Lowercase identifiers are parsed as identifiers
+    let partial = errors.partial.unwrap();
+    assert_eq!(partial.declarations.len(), 1);
+    assert_eq!(partial.statements.len(), 1);
+}
+
+#[test]
+fn lowercase_ident_expression() {
+    let error = rt_easy_parser::parse_expression("A + b").unwrap_err();
+    assert_eq!(
+        rt_easy_parser::pretty_print_error(&error, "A + b", None, false),
+        r#" --> 1:5
+  |
+1 |    A + b
+  |        ^
+  |
+  = identifiers must be uppercase, did you mean "B"?"#
+    );
+}
+
+#[test]
+fn keywords_are_not_identifiers() {
+    util::parse("declare register A\nif A then nop else nop fi;");
+}
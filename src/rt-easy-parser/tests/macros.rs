@@ -0,0 +1,84 @@
+use rtast::{ExpansionId, Label, Operation};
+
+fn labels<'s>(ast: &rtast::Ast<'s>) -> Vec<Option<Label<'s>>> {
+    ast.statements.iter().map(|statement| statement.label.map(|label| label.node)).collect()
+}
+
+#[test]
+fn expand() {
+    let ast = rt_easy_parser::parse(
+        r#"
+        declare register AR(7:0), PC(7:0)
+        define INC(R) { R <- R + 1; }
+        define FETCH { AR <- PC; INC(PC); }
+        START: FETCH; INC(AR); goto START;
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(ast.statements.len(), 4);
+    assert_eq!(labels(&ast)[0], Some(Label("START", None)));
+    assert_eq!(ast.expansions.len(), 3);
+    assert_eq!(ast.expansions[0].name.0, "FETCH");
+    assert_eq!(ast.expansions[1].name.0, "INC");
+    assert_eq!(ast.expansions[2].name.0, "INC");
+
+    // Spans point into the macro body, the nested call site into the outer body
+    assert_eq!(ast.statements[0].span.expansion, Some(ExpansionId(0)));
+    assert_eq!(ast.statements[1].span.expansion, Some(ExpansionId(1)));
+    assert_eq!(ast.expansions[1].call_site.expansion, Some(ExpansionId(0)));
+    assert_eq!(ast.expansions[2].call_site.expansion, None);
+    assert_eq!(ast.statements[3].span.expansion, None);
+
+    // Arguments replace the parameters
+    match &ast.statements[1].operations.operations[0] {
+        Operation::Assignment(assignment) => match &assignment.lhs {
+            rtast::Lvalue::RegBus(reg) => assert_eq!(reg.ident.node.0, "PC"),
+            other => panic!("unexpected lvalue: {:?}", other),
+        },
+        other => panic!("unexpected operation: {:?}", other),
+    }
+}
+
+#[test]
+fn hygienic_labels() {
+    let ast = rt_easy_parser::parse(
+        r#"
+        declare register A(7:0)
+        define WAIT { A <- A - 1; LOOP: if A <> 0 then goto LOOP fi; }
+        WAIT; WAIT; LOOP: goto LOOP;
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        labels(&ast),
+        vec![
+            None,
+            Some(Label("LOOP", Some(ExpansionId(0)))),
+            None,
+            Some(Label("LOOP", Some(ExpansionId(1)))),
+            Some(Label("LOOP", None)),
+        ]
+    );
+}
+
+#[test]
+fn expand_errors() {
+    const SOURCES: &[(&str, &str)] = &[
+        ("define M { nop; } define M { nop; } M;", "duplicate macro \"M\""),
+        ("M;", "no macro named \"M\" found"),
+        ("define M(A) { A <- 1; } M;", "macro \"M\" takes 1 argument(s) but 0 were supplied"),
+        ("define M { M; } M;", "macro \"M\" calls itself"),
+        (
+            "define M { L: nop; } X: M;",
+            "the first statement of macro \"M\" is already labeled, the call can not have a label",
+        ),
+    ];
+
+    for (source, message) in SOURCES {
+        let errors = rt_easy_parser::parse(source).unwrap_err();
+        let printed = rt_easy_parser::pretty_print_errors(&errors, source, None, false);
+        assert!(printed.ends_with(message), "{}", printed);
+    }
+}
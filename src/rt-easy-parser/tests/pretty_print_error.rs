@@ -24,10 +24,12 @@ fn pretty_print_error() {
            "if" ...
            "switch" ...
            "assert" ...
+           "define" ...
+           "=" ...
            <ID> ..."#;
 
     let error = util::parse_err(SOURCE);
-    let error_message = rt_easy_parser::pretty_print_error(&error, SOURCE, None, false);
+    let error_message = rt_easy_parser::pretty_print_errors(&error, SOURCE, None, false);
 
     assert_eq!(error_message, EXPECTED_ERROR);
 }
@@ -0,0 +1,99 @@
+mod util;
+
+fn error_lines(source: &str) -> Vec<usize> {
+    let errors = util::parse_err(source);
+    let printed = rt_easy_parser::pretty_print_errors(&errors, source, None, false);
+    printed
+        .lines()
+        .filter_map(|line| line.strip_prefix(" --> "))
+        .map(|location| location.split(':').next().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn multiple_statement_errors() {
+    const SOURCE: &str = "declare register A(7:0), B(7:0)
+A <- ;
+B <- A + 1;
+A <- B B;
+goto ;
+END:";
+
+    assert_eq!(error_lines(SOURCE), vec![2, 4, 5]);
+
+    let errors = util::parse_err(SOURCE);
+    let partial = errors.partial.expect("statement errors keep the partial ast");
+    assert_eq!(partial.declarations.len(), 1);
+    assert_eq!(partial.statements.len(), 1);
+    assert!(partial.trailing_label.is_some());
+}
+
+#[test]
+fn declaration_errors() {
+    const SOURCE: &str = "declare register A(7:
+declare register B(7:0)
+declare bus C(
+A <- B;
+B <- ;";
+
+    // Errors are located at the first unexpected token
+    assert_eq!(error_lines(SOURCE), vec![2, 4, 5]);
+
+    let errors = util::parse_err(SOURCE);
+    assert!(errors.partial.is_none());
+}
+
+#[test]
+fn macro_body_errors() {
+    const SOURCE: &str = "declare register A(7:0)
+define M {
+    A <- ;
+    A <- A + 1;
+    switch A { case 1: nop };
+    switch A { case : nop };
+}
+M;
+A <- A A;";
+
+    assert_eq!(error_lines(SOURCE), vec![3, 6, 9]);
+
+    let errors = util::parse_err(SOURCE);
+    let partial = errors.partial.unwrap();
+    assert_eq!(partial.statements.len(), 2);
+}
+
+#[test]
+fn expansion_errors() {
+    const SOURCE: &str = "define M(X) { X <- 1; }
+M;
+N;
+M(Y);";
+
+    assert_eq!(error_lines(SOURCE), vec![2, 3]);
+    assert_eq!(util::parse_err(SOURCE).partial.unwrap().statements.len(), 1);
+}
+
+#[test]
+fn unexpected_tokens() {
+    const SOURCE: &str = "declare register A(7:0)
+A <- 1;
+}
+A <- A + 1;
+) A <- ;
+END:";
+
+    assert_eq!(error_lines(SOURCE), vec![3, 5]);
+    assert_eq!(util::parse_err(SOURCE).partial.unwrap().statements.len(), 2);
+}
+
+#[test]
+fn definition_after_statement() {
+    const SOURCE: &str = "declare register A(7:0)
+A <- 1;
+declare register B(7:0)
+B <- A;";
+
+    assert_eq!(error_lines(SOURCE), vec![3]);
+    let partial = util::parse_err(SOURCE).partial.unwrap();
+    assert_eq!(partial.declarations.len(), 2);
+}
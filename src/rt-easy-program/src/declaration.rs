@@ -1,4 +1,5 @@
 use super::*;
+use rtcore::value::Value;
 
 #[derive(Debug)]
 pub enum Declaration {
@@ -6,11 +7,20 @@ pub enum Declaration {
     Bus(DeclareBus),
     Memory(DeclareMemory),
     RegisterArray(DeclareRegisterArray),
+    Constant(DeclareConstant),
 }
 
 #[derive(Debug)]
 pub struct DeclareRegister {
-    pub registers: Vec<Register>,
+    pub registers: Vec<DeclareRegisterItem>,
+}
+
+#[derive(Debug)]
+pub struct DeclareRegisterItem {
+    pub ident: Ident,
+    pub range: Option<BitRange>,
+    pub kind: RegisterKind,
+    pub init: Option<Value>,
 }
 
 #[derive(Debug)]
@@ -46,3 +56,15 @@ pub struct DeclareRegisterArrayItem {
     pub range: Option<BitRange>,
     pub len: usize,
 }
+
+/// Constants are folded into the expressions, their declarations only keep the values.
+#[derive(Debug)]
+pub struct DeclareConstant {
+    pub constants: Vec<Constant>,
+}
+
+#[derive(Debug)]
+pub struct Constant {
+    pub ident: Ident,
+    pub value: Value,
+}
@@ -0,0 +1,103 @@
+#[path = "../tests/util.rs"]
+mod util;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rt_easy_simulator::{fast::FastSimulator, Simulator};
+use rtcore::value::Value;
+use rtprogram::{Ident, Program};
+
+const MULT: &str = r#"
+    declare register A(15:0), FACTOR(15:0), RES(15:0)
+    declare bus INBUS(15:0)
+
+    BEGIN:
+        A <- INBUS, RES <- 0;
+        FACTOR <- INBUS;
+    LOOP:
+        if FACTOR <> 0 then
+            RES <- RES + A, FACTOR <- FACTOR - 1, goto LOOP
+        fi;
+"#;
+
+const MEMORY: &str = r#"
+    declare register AR(7:0), DR(15:0), SUM(15:0), COUNT(3:0)
+    declare memory MEM(AR,DR)
+
+    OUTER:
+        AR <- 0;
+    FILL:
+        DR <- AR + sxt COUNT, write MEM, AR <- AR + 1,
+        if AR <> 255 then goto FILL fi;
+    SUM_UP:
+        AR <- AR - 1, read MEM;
+        SUM <- SUM + DR, if AR <> 0 then goto SUM_UP fi;
+        COUNT <- COUNT + 1, if COUNT <> 15 then goto OUTER fi;
+"#;
+
+const REGISTER_ARRAY: &str = r#"
+    declare register IDX(4:0), TMP(31:0), ROUND(7:0)
+    declare register array ARR(31:0)[32]
+
+    INIT:
+        ARR[IDX] <- IDX.IDX.IDX, IDX <- IDX + 1, if IDX <> 31 then goto INIT fi;
+    SHUFFLE:
+        TMP <- (ARR[IDX] xor ARR[IDX - 1]) + TMP;
+        ARR[IDX - 1] <- TMP(30:0).TMP(31), IDX <- IDX - 1,
+        if IDX <> 0 then goto SHUFFLE fi;
+        ROUND <- ROUND + 1, if ROUND <> 100 then goto SHUFFLE fi;
+"#;
+
+fn run_simulator(program: Program, input: Option<(&str, &str)>) {
+    let mut simulator = Simulator::init(program);
+    if let Some((name, value)) = input {
+        let name = Ident(name.to_string());
+        simulator.write_bus(&name, Value::parse_dec(value).unwrap()).unwrap();
+        simulator.step(false).unwrap();
+        simulator.write_bus(&name, Value::parse_dec(value).unwrap()).unwrap();
+    }
+    while !simulator.is_finished() {
+        simulator.step(false).unwrap();
+    }
+}
+
+fn run_fast(program: &Program, input: Option<(&str, &str)>) {
+    let mut simulator = FastSimulator::init(program).unwrap();
+    if let Some((name, value)) = input {
+        let name = Ident(name.to_string());
+        simulator.write_bus(&name, Value::parse_dec(value).unwrap()).unwrap();
+        simulator.step();
+        simulator.write_bus(&name, Value::parse_dec(value).unwrap()).unwrap();
+    }
+    while simulator.step().is_some() {}
+}
+
+fn benchmark(c: &mut Criterion) {
+    let programs = [
+        ("mult", MULT, Some(("INBUS", "1000"))),
+        ("memory", MEMORY, None),
+        ("register_array", REGISTER_ARRAY, None),
+    ];
+
+    let mut group = c.benchmark_group("simulator");
+    group.sample_size(10);
+    for (name, source, input) in programs {
+        group.bench_with_input(BenchmarkId::new("simulator", name), &source, |b, source| {
+            b.iter_batched(
+                || util::compile(source),
+                |program| run_simulator(program, input),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("fast", name), &source, |b, source| {
+            b.iter_batched(
+                || util::compile(source),
+                |program| run_fast(&program, input),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,171 @@
+use rtcore::value::Value;
+use std::any::Any;
+use std::fmt;
+
+/// A memory-mapped device.
+///
+/// A device claims `size()` consecutive addresses of a declared memory (see
+/// [`Simulator::attach_device`](crate::Simulator::attach_device)). Reads and writes to these
+/// addresses are forwarded to the device instead of the memory data. All addresses passed to the
+/// device are offsets relative to the first claimed address.
+pub trait Device: fmt::Debug {
+    /// Number of consecutive addresses claimed by the device.
+    fn size(&self) -> usize;
+
+    /// Read the value at `offset`. The returned value is resized to `dr_size`.
+    fn read(&self, offset: usize, dr_size: usize) -> Value;
+
+    /// Write `value` to `offset`. Called when the write is clocked in.
+    fn write(&mut self, offset: usize, value: &Value);
+
+    /// Called on every clock edge.
+    fn clock(&mut self) {}
+
+    /// Called when the simulator is reset.
+    fn reset(&mut self) {}
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// A console/UART that collects all written bytes.
+///
+/// Claims a single address. Every write appends the lower 8 bits of the written value to the
+/// output. Reads always return zero.
+#[derive(Debug, Default)]
+pub struct Console {
+    output: Vec<u8>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn output_string(&self) -> String {
+        String::from_utf8_lossy(&self.output).into_owned()
+    }
+
+    pub fn clear(&mut self) {
+        self.output.clear();
+    }
+}
+
+impl Device for Console {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn read(&self, _offset: usize, dr_size: usize) -> Value {
+        Value::zero(dr_size)
+    }
+
+    fn write(&mut self, _offset: usize, value: &Value) {
+        self.output.push(value.clone().with_size(8).to_u64().unwrap() as u8);
+    }
+
+    fn reset(&mut self) {
+        self.output.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A timer that counts clock edges.
+///
+/// Claims a single address. Reads return the current tick count (truncated to the data register
+/// size), writes set the tick count.
+#[derive(Debug, Default)]
+pub struct TickTimer {
+    ticks: u64,
+}
+
+impl TickTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+}
+
+impl Device for TickTimer {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn read(&self, _offset: usize, dr_size: usize) -> Value {
+        Value::from_u64(self.ticks, dr_size)
+    }
+
+    fn write(&mut self, _offset: usize, value: &Value) {
+        // The lower 64 bits
+        self.ticks = value.clone().with_size(64).to_u64().unwrap();
+    }
+
+    fn clock(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+
+    fn reset(&mut self) {
+        self.ticks = 0;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A constant ROM region.
+///
+/// Claims one address per value. Writes are ignored.
+#[derive(Debug)]
+pub struct Rom {
+    data: Vec<Value>,
+}
+
+impl Rom {
+    pub fn new(data: Vec<Value>) -> Self {
+        Self { data }
+    }
+
+    pub fn data(&self) -> &[Value] {
+        &self.data
+    }
+}
+
+impl Device for Rom {
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn read(&self, offset: usize, dr_size: usize) -> Value {
+        self.data[offset].clone().with_size(dr_size)
+    }
+
+    fn write(&mut self, _offset: usize, _value: &Value) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
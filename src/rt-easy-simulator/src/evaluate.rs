@@ -49,6 +49,7 @@ impl Evaluate for BinaryTerm {
             BinaryOperator::Gt => Value::from(Bit::from(lhs > rhs)),
             BinaryOperator::Add => lhs + rhs,
             BinaryOperator::Sub => lhs - rhs,
+            BinaryOperator::Mul => lhs * rhs,
             BinaryOperator::And => lhs & rhs,
             BinaryOperator::Nand => !(lhs & rhs),
             BinaryOperator::Or => lhs | rhs,
@@ -0,0 +1,192 @@
+//! Tracking of uninitialized bits.
+//!
+//! Next to its value, every expression has a *known mask* of the same size. A bit in the mask is
+//! one if the corresponding bit of the value is initialized, and zero if it is unknown (`X`).
+
+use crate::{evaluate::Evaluate, state::State, Error};
+use rtcore::value::Value;
+use rtprogram::{
+    Atom, BinaryOperator, BinaryTerm, Bus, Concat, ConcatPartExpr, Expression, ExpressionKind,
+    Number, Register, RegisterArray, UnaryOperator, UnaryTerm,
+};
+use std::convert::Infallible;
+
+type Result<T = Value> = std::result::Result<T, Error>;
+
+pub trait EvaluateKnown {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result;
+}
+
+impl EvaluateKnown for Expression {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        match &self.kind {
+            ExpressionKind::Atom(atom) => atom.evaluate_known(state, ctx_size),
+            ExpressionKind::BinaryTerm(term) => term.evaluate_known(state, ctx_size),
+            ExpressionKind::UnaryTerm(term) => term.evaluate_known(state, ctx_size),
+        }
+    }
+}
+
+impl EvaluateKnown for Atom {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        match self {
+            Self::Concat(concat) => concat.evaluate_known(state, ctx_size),
+            Self::Register(reg) => reg.evaluate_known(state, ctx_size),
+            Self::Bus(bus) => bus.evaluate_known(state, ctx_size),
+            Self::RegisterArray(reg_array) => reg_array.evaluate_known(state, ctx_size),
+            Self::Number(number) => number.evaluate_known(state, ctx_size),
+        }
+    }
+}
+
+impl EvaluateKnown for BinaryTerm {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let ctx_size_inner = self.ctx_size.calc(ctx_size);
+        let lhs = self.lhs.evaluate_known(state, ctx_size_inner)?;
+        let rhs = self.rhs.evaluate_known(state, ctx_size_inner)?;
+
+        let known = match self.operator {
+            BinaryOperator::Eq
+            | BinaryOperator::Ne
+            | BinaryOperator::Le
+            | BinaryOperator::Lt
+            | BinaryOperator::Ge
+            | BinaryOperator::Gt => {
+                if is_known(&lhs) && is_known(&rhs) {
+                    Value::filled(1)
+                } else {
+                    Value::zero(1)
+                }
+            }
+            BinaryOperator::Add | BinaryOperator::Sub | BinaryOperator::Mul => {
+                known_carry(lhs & rhs)
+            }
+            BinaryOperator::And
+            | BinaryOperator::Nand
+            | BinaryOperator::Or
+            | BinaryOperator::Nor
+            | BinaryOperator::Xor => lhs & rhs,
+        };
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for UnaryTerm {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let ctx_size_inner = self.ctx_size.calc(ctx_size);
+        let mut rhs = self.expression.evaluate_known(state, ctx_size_inner)?;
+
+        let known = match self.operator {
+            UnaryOperator::Sign | UnaryOperator::Neg => known_carry(rhs),
+            UnaryOperator::Not => rhs,
+            UnaryOperator::Sxt => {
+                rhs.extend_sign(ctx_size);
+                rhs
+            }
+        };
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for Register {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let known = state.register(&self.ident)?.read_known(self.range)?;
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for Bus {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let known = state.bus(&self.ident)?.read_known(self.range)?;
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for RegisterArray {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let known = read_register_array_known(self, state)?;
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for Number {
+    fn evaluate_known(&self, _: &State, ctx_size: usize) -> Result {
+        Ok(Value::filled(self.value.size().max(ctx_size)))
+    }
+}
+
+impl EvaluateKnown for Concat<ConcatPartExpr> {
+    fn evaluate_known(&self, state: &State, ctx_size: usize) -> Result {
+        let knowns = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                ConcatPartExpr::Register(reg) => state.register(&reg.ident)?.read_known(reg.range),
+                ConcatPartExpr::Bus(bus) => state.bus(&bus.ident)?.read_known(bus.range),
+                ConcatPartExpr::RegisterArray(reg_array) => {
+                    read_register_array_known(reg_array, state)
+                }
+                ConcatPartExpr::Number(number) => Ok(Value::filled(number.value.size())),
+            })
+            .collect::<Result<Vec<Value>>>()?;
+
+        let known = Value::concat(knowns.iter().map(Value::as_slice));
+        Ok(extend_known(known, ctx_size))
+    }
+}
+
+impl EvaluateKnown for Infallible {
+    fn evaluate_known(&self, _: &State, _: usize) -> Result {
+        match *self {}
+    }
+}
+
+fn read_register_array_known(reg_array: &RegisterArray, state: &State) -> Result {
+    let state_reg_array = state.register_array(&reg_array.ident)?;
+
+    // An unknown index makes the whole value unknown
+    let idx_known = reg_array.index.evaluate_known(state, reg_array.index_ctx_size)?;
+    if !is_known(&idx_known) {
+        return Ok(Value::zero(state_reg_array.data_size()));
+    }
+
+    let idx = reg_array.index.evaluate(state, reg_array.index_ctx_size)?;
+    state_reg_array.read_known(idx)
+}
+
+/// Returns `true` if all bits of the mask are known.
+pub fn is_known(known: &Value) -> bool {
+    (!known).is_zero()
+}
+
+/// Extends the mask to `size`. The added bits are known, since values are zero extended.
+pub fn extend_known(known: Value, size: usize) -> Value {
+    if known.size() >= size {
+        return known;
+    }
+
+    let mut result = Value::filled(size);
+    result.slice_mut(..known.size()).write(&known);
+    result
+}
+
+/// Resizes the mask to exactly `size` bits, see [`extend_known`].
+pub fn fit_known(known: Value, size: usize) -> Value {
+    if known.size() > size {
+        known.with_size(size)
+    } else {
+        extend_known(known, size)
+    }
+}
+
+/// Known mask of an arithmetic result: An unknown bit taints all higher bits through the carry.
+fn known_carry(known: Value) -> Value {
+    let unknown = !known;
+    if unknown.is_zero() {
+        return Value::filled(unknown.size());
+    }
+
+    // Isolate the lowest unknown bit, all bits below are known
+    let lowest_unknown = &unknown & &(-&unknown);
+    lowest_unknown - Value::one(unknown.size())
+}
@@ -1,9 +1,14 @@
-use crate::{evaluate::Evaluate, state::State, Error};
-use rtcore::value::Value;
+use crate::{
+    evaluate::Evaluate,
+    evaluate_known::{fit_known, is_known, EvaluateKnown},
+    state::{State, WriteEvent},
+    Error, UninitializedKind,
+};
+use rtcore::{common::RegisterKind, value::Value};
 use rtprogram::{
     Assert, Assignment, ConcatPartLvalueClocked, ConcatPartLvalueUnclocked, Criterion,
-    EvalCriterion, EvalCriterionGroup, Goto, Label, Lvalue, Nop, Operation, OperationKind, Read,
-    Span, Write,
+    EvalCriterion, EvalCriterionGroup, Goto, Ident, Label, Lvalue, Nop, Operation, OperationKind,
+    Read, Register, RegisterArray, Span, Write,
 };
 
 type Result = std::result::Result<ExecuteResult, Error>;
@@ -40,6 +45,9 @@ impl Execute for Operation {
 impl Execute for EvalCriterion {
     fn execute(&self, state: &State) -> Result {
         let cond = self.condition.evaluate(state, 1)?;
+        if state.track_uninitialized() && !is_known(&self.condition.evaluate_known(state, 1)?) {
+            state.report_uninitialized(UninitializedKind::Condition);
+        }
 
         if cond == Value::one(1) {
             Ok(ExecuteResult::Criterion(Criterion::True(self.criterion_id), self.condition.span))
@@ -77,13 +85,21 @@ impl Execute for Goto {
 
 impl Execute for Write {
     fn execute(&self, state: &State) -> Result {
-        state.memory(&self.ident)?.write(state)?;
+        check_memory_address(&self.ident, state)?;
+        let memory = state.memory(&self.ident)?;
+        memory.write(state)?;
+        if state.observed() {
+            if let Some((address, value)) = memory.value_next() {
+                state.report_write(WriteEvent::Memory(self.ident.clone(), address, value));
+            }
+        }
         Ok(ExecuteResult::Void)
     }
 }
 
 impl Execute for Read {
     fn execute(&self, state: &State) -> Result {
+        check_memory_address(&self.ident, state)?;
         state.memory(&self.ident)?.read(state)?;
         Ok(ExecuteResult::Void)
     }
@@ -92,14 +108,41 @@ impl Execute for Read {
 impl Execute for Assignment {
     fn execute(&self, state: &State) -> Result {
         let value = self.rhs.evaluate(&state, self.size)?;
+        if state.track_uninitialized() {
+            let known = self.rhs.evaluate_known(state, self.size)?;
+            execute_known(self, state, fit_known(known, value.size()))?;
+        }
 
         match &self.lhs {
             Lvalue::Register(reg) => {
+                if state.observed() {
+                    state.report_write(WriteEvent::Register(
+                        reg.ident.clone(),
+                        reg.range,
+                        value.clone(),
+                    ));
+                }
                 state.register(&reg.ident)?.write(reg.range, value)?;
             }
-            Lvalue::Bus(bus) => state.bus(&bus.ident)?.write(bus.range, value)?,
+            Lvalue::Bus(bus) => {
+                if state.observed() {
+                    state.report_write(WriteEvent::Bus(
+                        bus.ident.clone(),
+                        bus.range,
+                        value.clone(),
+                    ));
+                }
+                state.bus(&bus.ident)?.write(bus.range, value)?
+            }
             Lvalue::RegisterArray(register_array) => {
                 let idx = register_array.index.evaluate(&state, register_array.index_ctx_size)?;
+                if state.observed() {
+                    state.report_write(WriteEvent::RegisterArray(
+                        register_array.ident.clone(),
+                        idx.clone(),
+                        value.clone(),
+                    ));
+                }
                 state.register_array(&register_array.ident)?.write(idx, value)?;
             }
             Lvalue::ConcatClocked(lhs) => {
@@ -110,13 +153,27 @@ impl Execute for Assignment {
                         ConcatPartLvalueClocked::RegisterArray(_, size) => *size,
                     };
 
-                    let value = value[start..start + size].to_owned();
+                    let value = value.slice(start..start + size).to_value();
                     match part {
                         ConcatPartLvalueClocked::Register(reg, _) => {
+                            if state.observed() {
+                                state.report_write(WriteEvent::Register(
+                                    reg.ident.clone(),
+                                    reg.range,
+                                    value.clone(),
+                                ));
+                            }
                             state.register(&reg.ident)?.write(reg.range, value)?;
                         }
                         ConcatPartLvalueClocked::RegisterArray(reg_array, _) => {
                             let idx = reg_array.index.evaluate(&state, reg_array.index_ctx_size)?;
+                            if state.observed() {
+                                state.report_write(WriteEvent::RegisterArray(
+                                    reg_array.ident.clone(),
+                                    idx.clone(),
+                                    value.clone(),
+                                ));
+                            }
                             state.register_array(&reg_array.ident)?.write(idx, value)?;
                         }
                     }
@@ -129,7 +186,14 @@ impl Execute for Assignment {
                 for part in lhs.parts.iter().rev() {
                     let ConcatPartLvalueUnclocked::Bus(bus, size) = part;
 
-                    let value = value[start..start + size].to_owned();
+                    let value = value.slice(start..start + size).to_value();
+                    if state.observed() {
+                        state.report_write(WriteEvent::Bus(
+                            bus.ident.clone(),
+                            bus.range,
+                            value.clone(),
+                        ));
+                    }
                     state.bus(&bus.ident)?.write(bus.range, value)?;
 
                     start += size;
@@ -144,6 +208,9 @@ impl Execute for Assignment {
 impl Execute for Assert {
     fn execute(&self, state: &State) -> Result {
         let cond = self.condition.evaluate(state, 1)?;
+        if state.track_uninitialized() && !is_known(&self.condition.evaluate_known(state, 1)?) {
+            state.report_uninitialized(UninitializedKind::Condition);
+        }
 
         if cond == Value::one(1) {
             Ok(ExecuteResult::Void)
@@ -152,3 +219,73 @@ impl Execute for Assert {
         }
     }
 }
+
+fn check_memory_address(ident: &Ident, state: &State) -> std::result::Result<(), Error> {
+    if state.track_uninitialized() {
+        let memory = state.memory(ident)?;
+        let ar_known = state.register(memory.address_register())?.read_known(None)?;
+        if !is_known(&ar_known) {
+            state.report_uninitialized(UninitializedKind::MemoryAddress(ident.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+fn execute_known(
+    assignment: &Assignment,
+    state: &State,
+    known: Value,
+) -> std::result::Result<(), Error> {
+    let write_register_known = |reg: &Register, known: Value| {
+        let reg_state = state.register(&reg.ident)?;
+        if reg_state.kind() == RegisterKind::Output && !is_known(&known) {
+            state.report_uninitialized(UninitializedKind::OutputRegister(reg.ident.clone()));
+        }
+        reg_state.write_known(reg.range, known)
+    };
+    let write_register_array_known = |reg_array: &RegisterArray, known: Value| {
+        let idx = reg_array.index.evaluate(state, reg_array.index_ctx_size)?;
+        let idx_known = reg_array.index.evaluate_known(state, reg_array.index_ctx_size)?;
+        let known = if is_known(&idx_known) { known } else { Value::zero(known.size()) };
+        state.register_array(&reg_array.ident)?.write_known(idx, known)
+    };
+
+    match &assignment.lhs {
+        Lvalue::Register(reg) => write_register_known(reg, known)?,
+        Lvalue::Bus(bus) => state.bus(&bus.ident)?.write_known(bus.range, known)?,
+        Lvalue::RegisterArray(reg_array) => write_register_array_known(reg_array, known)?,
+        Lvalue::ConcatClocked(lhs) => {
+            let mut start = 0;
+            for part in lhs.parts.iter().rev() {
+                let size = match part {
+                    ConcatPartLvalueClocked::Register(_, size) => *size,
+                    ConcatPartLvalueClocked::RegisterArray(_, size) => *size,
+                };
+
+                let known = known.slice(start..start + size).to_value();
+                match part {
+                    ConcatPartLvalueClocked::Register(reg, _) => write_register_known(reg, known)?,
+                    ConcatPartLvalueClocked::RegisterArray(reg_array, _) => {
+                        write_register_array_known(reg_array, known)?
+                    }
+                }
+
+                start += size;
+            }
+        }
+        Lvalue::ConcatUnclocked(lhs) => {
+            let mut start = 0;
+            for part in lhs.parts.iter().rev() {
+                let ConcatPartLvalueUnclocked::Bus(bus, size) = part;
+
+                let known = known.slice(start..start + size).to_value();
+                state.bus(&bus.ident)?.write_known(bus.range, known)?;
+
+                start += size;
+            }
+        }
+    }
+
+    Ok(())
+}
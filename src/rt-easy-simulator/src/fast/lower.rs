@@ -0,0 +1,792 @@
+//! Lowering of a [`Program`] into the compact form executed by the
+//! [`FastSimulator`](super::FastSimulator).
+//!
+//! All symbols are resolved to slot indices, goto labels to statement indices and criterion ids
+//! to dense indices. The size of every (sub)expression is known statically, so values of at most
+//! [`MAX_SIZE`] bits are packed into a single `u64`. Wider values fall back to [`Value`].
+
+use crate::{state::util::slice_idx, Error};
+use anyhow::anyhow;
+use rtcore::{
+    common::{BinaryOperator, BitRange, BusKind, UnaryOperator},
+    value::Value,
+};
+use rtprogram::{
+    Atom, BinaryTerm, Concat, ConcatPartExpr, ConcatPartLvalueClocked, ConcatPartLvalueUnclocked,
+    Criterion, CriterionId, Declaration, Expression, ExpressionKind, Ident, Label, Lvalue,
+    OperationKind, Program, Span, UnaryTerm,
+};
+use std::collections::HashMap;
+
+/// Maximum size (in bits) of values packed into a `u64` by the fast simulator.
+pub const MAX_SIZE: usize = 64;
+
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub struct Lowered {
+    pub symbols: HashMap<Ident, Symbol>,
+    pub registers: Vec<RegisterSlot>,
+    pub buses: Vec<BusSlot>,
+    pub register_arrays: Vec<RegisterArraySlot>,
+    pub memories: Vec<MemorySlot>,
+    pub statements: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Symbol {
+    Register(usize),
+    Bus(usize),
+    RegisterArray(usize),
+    Memory(usize),
+}
+
+/// Index into the packed or the wide values of a kind of slot.
+#[derive(Debug, Clone, Copy)]
+pub enum Storage {
+    Packed(usize),
+    Wide(usize),
+}
+
+impl Storage {
+    pub fn packed(self) -> usize {
+        match self {
+            Self::Packed(idx) => idx,
+            Self::Wide(_) => unreachable!("storage is not packed"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Storages {
+    packed: usize,
+    wide: usize,
+}
+
+impl Storages {
+    fn next(&mut self, is_wide: bool) -> Storage {
+        if is_wide {
+            self.wide += 1;
+            Storage::Wide(self.wide - 1)
+        } else {
+            self.packed += 1;
+            Storage::Packed(self.packed - 1)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RegisterSlot {
+    pub range: BitRange,
+    pub init: Value,
+    pub storage: Storage,
+}
+
+#[derive(Debug)]
+pub struct BusSlot {
+    pub range: BitRange,
+    pub kind: BusKind,
+    pub storage: Storage,
+}
+
+#[derive(Debug)]
+pub struct RegisterArraySlot {
+    pub len: usize,
+    pub data_size: usize,
+    pub storage: Storage,
+}
+
+/// Memories are wide if the address or the data register is wide.
+#[derive(Debug)]
+pub struct MemorySlot {
+    pub address_register: Storage,
+    pub data_register: Storage,
+    pub ar_size: usize,
+    pub dr_size: usize,
+    pub storage: Storage,
+}
+
+#[derive(Debug)]
+pub struct Statement {
+    pub steps: Vec<Step>,
+    /// Index of the first step after the pipe. Equal to `steps.len()` if there is no pipe.
+    pub split_at: usize,
+    pub criteria_count: usize,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Step {
+    /// Criterion index and expected value.
+    pub criteria: Vec<(usize, bool)>,
+    pub operation: Operation,
+}
+
+#[derive(Debug)]
+pub enum Operation {
+    EvalCriterion(usize, Node),
+    EvalCriterionGroup(Vec<(usize, Node)>),
+    Nop,
+    Goto(usize),
+    Write(usize),
+    Read(usize),
+    Assignment(Expr, Vec<Target>),
+    Assert(Node, Span),
+}
+
+/// Writes the bits `offset..offset + size` of the assigned value to the target.
+#[derive(Debug)]
+pub struct Target {
+    pub kind: TargetKind,
+    pub offset: u32,
+    pub size: usize,
+    pub mask: u64,
+}
+
+#[derive(Debug)]
+pub enum TargetKind {
+    Register { slot: usize, shift: u32, mask: u64 },
+    WideRegister { slot: usize, start: usize },
+    Bus { slot: usize, shift: u32, mask: u64 },
+    WideBus { slot: usize, start: usize },
+    RegisterArray { slot: usize, index: Node },
+    WideRegisterArray { slot: usize, index: Node },
+}
+
+/// An expression with at most [`MAX_SIZE`] bits is packed, a wider one is wide.
+#[derive(Debug)]
+pub enum Expr {
+    Packed(Node),
+    Wide(WideNode),
+}
+
+impl Expr {
+    fn into_packed(self) -> Node {
+        match self {
+            Self::Packed(node) => node,
+            Self::Wide(_) => unreachable!("expression is not packed"),
+        }
+    }
+
+    /// Converts into a wide node with at least `size` bits.
+    fn into_wide(self, size: usize) -> WideNode {
+        match self {
+            Self::Packed(node) => WideNode::Packed { node: Box::new(node), size },
+            Self::Wide(node) => node,
+        }
+    }
+}
+
+/// Evaluates to a `u64` with at most [`MAX_SIZE`] bits.
+#[derive(Debug)]
+pub enum Node {
+    Const(u64),
+    Register {
+        slot: usize,
+        shift: u32,
+        mask: u64,
+    },
+    /// Bits `start..start + size` of a wide register.
+    WideRegister {
+        slot: usize,
+        start: usize,
+        size: usize,
+    },
+    Bus {
+        slot: usize,
+        shift: u32,
+        mask: u64,
+    },
+    /// Bits `start..start + size` of a wide bus.
+    WideBus {
+        slot: usize,
+        start: usize,
+        size: usize,
+    },
+    RegisterArray {
+        slot: usize,
+        index: Box<Node>,
+    },
+    /// A wide register array index, `u64::MAX` (out of range) if it does not fit.
+    Index(Box<WideNode>),
+    /// Parts with their shift.
+    Concat(Vec<(Node, u32)>),
+    Binary {
+        operator: BinaryOperator,
+        lhs: Box<Node>,
+        rhs: Box<Node>,
+        mask: u64,
+    },
+    /// `mask` is the mask of the operand, `sign` its sign bit and `extend` the bits set by a sign
+    /// extension.
+    Unary {
+        operator: UnaryOperator,
+        expression: Box<Node>,
+        mask: u64,
+        sign: u64,
+        extend: u64,
+    },
+    /// Comparison of wide operands.
+    WideCompare {
+        operator: BinaryOperator,
+        lhs: Box<WideNode>,
+        rhs: Box<WideNode>,
+    },
+}
+
+/// Evaluates to a [`Value`].
+#[derive(Debug)]
+pub enum WideNode {
+    Const(Value),
+    Register {
+        slot: usize,
+        start: usize,
+        size: usize,
+    },
+    Bus {
+        slot: usize,
+        start: usize,
+        size: usize,
+    },
+    RegisterArray {
+        slot: usize,
+        index: Box<Node>,
+        size: usize,
+    },
+    /// Parts from most to least significant.
+    Concat(Vec<WideNode>),
+    /// Arithmetic and logic operators only, comparisons are [`Node::WideCompare`].
+    Binary {
+        operator: BinaryOperator,
+        lhs: Box<WideNode>,
+        rhs: Box<WideNode>,
+    },
+    /// `size` is the size of a sign extension.
+    Unary {
+        operator: UnaryOperator,
+        expression: Box<WideNode>,
+        size: usize,
+    },
+    /// A packed node zero extended to `size` bits.
+    Packed {
+        node: Box<Node>,
+        size: usize,
+    },
+    /// A wide node zero extended to `size` bits.
+    Extend {
+        node: Box<WideNode>,
+        size: usize,
+    },
+}
+
+pub fn mask(size: usize) -> u64 {
+    if size >= 64 {
+        u64::MAX
+    } else {
+        (1 << size) - 1
+    }
+}
+
+pub fn lower(program: &Program) -> Result<Lowered> {
+    let mut lowered = Lowered {
+        symbols: HashMap::new(),
+        registers: Vec::new(),
+        buses: Vec::new(),
+        register_arrays: Vec::new(),
+        memories: Vec::new(),
+        statements: Vec::new(),
+    };
+
+    // Declarations (memories need access to registers)
+    let mut register_storages = Storages::default();
+    let mut bus_storages = Storages::default();
+    let mut register_array_storages = Storages::default();
+    let mut memory_storages = Storages::default();
+    for declaration in program.declarations() {
+        match declaration {
+            Declaration::Register(declare_register) => {
+                for reg in &declare_register.registers {
+                    let range = reg.range.unwrap_or_default();
+                    lowered
+                        .symbols
+                        .insert(reg.ident.clone(), Symbol::Register(lowered.registers.len()));
+                    let init = match &reg.init {
+                        Some(init) => init.clone().with_size(range.size()),
+                        None => Value::zero(range.size()),
+                    };
+                    let storage = register_storages.next(range.size() > MAX_SIZE);
+                    lowered.registers.push(RegisterSlot { range, init, storage });
+                }
+            }
+            Declaration::Bus(declare_bus) => {
+                for bus in &declare_bus.buses {
+                    let range = bus.range.unwrap_or_default();
+                    lowered.symbols.insert(bus.ident.clone(), Symbol::Bus(lowered.buses.len()));
+                    let storage = bus_storages.next(range.size() > MAX_SIZE);
+                    lowered.buses.push(BusSlot { range, kind: bus.kind, storage });
+                }
+            }
+            Declaration::RegisterArray(declare_register_array) => {
+                for reg_array in &declare_register_array.register_arrays {
+                    let data_size = reg_array.range.unwrap_or_default().size();
+                    lowered.symbols.insert(
+                        reg_array.ident.clone(),
+                        Symbol::RegisterArray(lowered.register_arrays.len()),
+                    );
+                    let storage = register_array_storages.next(data_size > MAX_SIZE);
+                    lowered.register_arrays.push(RegisterArraySlot {
+                        len: reg_array.len,
+                        data_size,
+                        storage,
+                    });
+                }
+            }
+            Declaration::Memory(_) | Declaration::Constant(_) => (),
+        }
+    }
+    for declaration in program.declarations() {
+        if let Declaration::Memory(declare_memory) = declaration {
+            for mem in &declare_memory.memories {
+                let address_register =
+                    &lowered.registers[lowered.register(&mem.range.address_register)?];
+                let data_register =
+                    &lowered.registers[lowered.register(&mem.range.data_register)?];
+                let ar_size = address_register.range.size();
+                let dr_size = data_register.range.size();
+                let memory = MemorySlot {
+                    address_register: address_register.storage,
+                    data_register: data_register.storage,
+                    ar_size,
+                    dr_size,
+                    storage: memory_storages.next(ar_size.max(dr_size) > MAX_SIZE),
+                };
+                lowered.symbols.insert(mem.ident.clone(), Symbol::Memory(lowered.memories.len()));
+                lowered.memories.push(memory);
+            }
+        }
+    }
+
+    // Statements
+    let labels = program
+        .statements()
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, stmt)| Some((stmt.label.as_ref()?.node.clone(), idx)))
+        .collect::<HashMap<Label, usize>>();
+    for statement in program.statements() {
+        let mut criteria = Criteria::default();
+        let steps = statement
+            .steps
+            .node
+            .as_slice()
+            .iter()
+            .map(|step| {
+                Ok(Step {
+                    criteria: step
+                        .criteria
+                        .iter()
+                        .map(|criterion| match criterion {
+                            Criterion::True(id) => (criteria.idx(*id), true),
+                            Criterion::False(id) => (criteria.idx(*id), false),
+                        })
+                        .collect(),
+                    operation: lowered.operation(&step.operation, &labels, &mut criteria)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        lowered.statements.push(Statement {
+            steps,
+            split_at: statement.steps.node.split_at(),
+            criteria_count: criteria.ids.len(),
+            span: statement.steps.span,
+        });
+    }
+
+    Ok(lowered)
+}
+
+#[derive(Debug, Default)]
+struct Criteria {
+    ids: HashMap<CriterionId, usize>,
+}
+
+impl Criteria {
+    fn idx(&mut self, id: CriterionId) -> usize {
+        let len = self.ids.len();
+        *self.ids.entry(id).or_insert(len)
+    }
+}
+
+impl Lowered {
+    fn register(&self, ident: &Ident) -> Result<usize> {
+        match self.symbols.get(ident) {
+            Some(Symbol::Register(slot)) => Ok(*slot),
+            _ => Err(anyhow!("register `{}` not found", ident.0)),
+        }
+    }
+
+    fn bus(&self, ident: &Ident) -> Result<usize> {
+        match self.symbols.get(ident) {
+            Some(Symbol::Bus(slot)) => Ok(*slot),
+            _ => Err(anyhow!("bus `{}` not found", ident.0)),
+        }
+    }
+
+    fn register_array(&self, ident: &Ident) -> Result<usize> {
+        match self.symbols.get(ident) {
+            Some(Symbol::RegisterArray(slot)) => Ok(*slot),
+            _ => Err(anyhow!("register array `{}` not found", ident.0)),
+        }
+    }
+
+    fn memory(&self, ident: &Ident) -> Result<usize> {
+        match self.symbols.get(ident) {
+            Some(Symbol::Memory(slot)) => Ok(*slot),
+            _ => Err(anyhow!("memory `{}` not found", ident.0)),
+        }
+    }
+
+    fn operation(
+        &self,
+        operation: &rtprogram::Operation,
+        labels: &HashMap<Label, usize>,
+        criteria: &mut Criteria,
+    ) -> Result<Operation> {
+        let lowered = match &operation.kind {
+            OperationKind::EvalCriterion(eval_criterion) => Operation::EvalCriterion(
+                criteria.idx(eval_criterion.criterion_id),
+                self.condition(&eval_criterion.condition)?,
+            ),
+            OperationKind::EvalCriterionGroup(group) => Operation::EvalCriterionGroup(
+                group
+                    .0
+                    .iter()
+                    .map(|eval_criterion| {
+                        Ok((
+                            criteria.idx(eval_criterion.criterion_id),
+                            self.condition(&eval_criterion.condition)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            OperationKind::Nop(_) => Operation::Nop,
+            OperationKind::Goto(goto) => Operation::Goto(
+                *labels
+                    .get(&goto.label)
+                    .ok_or_else(|| anyhow!("failed to find goto label `{}`", goto.label.0))?,
+            ),
+            OperationKind::Write(write) => Operation::Write(self.memory(&write.ident)?),
+            OperationKind::Read(read) => Operation::Read(self.memory(&read.ident)?),
+            OperationKind::Assignment(assignment) => {
+                let (rhs, _) = self.expression(&assignment.rhs, assignment.size)?;
+                Operation::Assignment(rhs, self.lvalue(&assignment.lhs)?)
+            }
+            OperationKind::Assert(assert) => {
+                Operation::Assert(self.condition(&assert.condition)?, operation.span)
+            }
+        };
+
+        Ok(lowered)
+    }
+
+    fn lvalue(&self, lvalue: &Lvalue) -> Result<Vec<Target>> {
+        let targets = match lvalue {
+            Lvalue::Register(reg) => {
+                let (kind, size) = self.register_target(&reg.ident, reg.range)?;
+                vec![Target { kind, offset: 0, size, mask: mask(size) }]
+            }
+            Lvalue::Bus(bus) => {
+                let (kind, size) = self.bus_target(&bus.ident, bus.range)?;
+                vec![Target { kind, offset: 0, size, mask: mask(size) }]
+            }
+            Lvalue::RegisterArray(reg_array) => {
+                let (kind, size) = self.register_array_target(reg_array)?;
+                vec![Target { kind, offset: 0, size, mask: mask(size) }]
+            }
+            Lvalue::ConcatClocked(concat) => {
+                let mut offset = 0;
+                let mut targets = Vec::new();
+                for part in concat.parts.iter().rev() {
+                    let (kind, size) = match part {
+                        ConcatPartLvalueClocked::Register(reg, size) => {
+                            (self.register_target(&reg.ident, reg.range)?.0, *size)
+                        }
+                        ConcatPartLvalueClocked::RegisterArray(reg_array, size) => {
+                            (self.register_array_target(reg_array)?.0, *size)
+                        }
+                    };
+                    targets.push(Target { kind, offset, size, mask: mask(size) });
+                    offset += size as u32;
+                }
+                targets
+            }
+            Lvalue::ConcatUnclocked(concat) => {
+                let mut offset = 0;
+                let mut targets = Vec::new();
+                for part in concat.parts.iter().rev() {
+                    let ConcatPartLvalueUnclocked::Bus(bus, size) = part;
+                    let kind = self.bus_target(&bus.ident, bus.range)?.0;
+                    targets.push(Target { kind, offset, size: *size, mask: mask(*size) });
+                    offset += *size as u32;
+                }
+                targets
+            }
+        };
+
+        Ok(targets)
+    }
+
+    fn register_target(
+        &self,
+        ident: &Ident,
+        range: Option<BitRange>,
+    ) -> Result<(TargetKind, usize)> {
+        let register = &self.registers[self.register(ident)?];
+        let (shift, size) = field(register.range, range)?;
+        let kind = match register.storage {
+            Storage::Packed(slot) => TargetKind::Register { slot, shift, mask: mask(size) },
+            Storage::Wide(slot) => TargetKind::WideRegister { slot, start: shift as usize },
+        };
+        Ok((kind, size))
+    }
+
+    fn bus_target(&self, ident: &Ident, range: Option<BitRange>) -> Result<(TargetKind, usize)> {
+        let bus = &self.buses[self.bus(ident)?];
+        let (shift, size) = field(bus.range, range)?;
+        let kind = match bus.storage {
+            Storage::Packed(slot) => TargetKind::Bus { slot, shift, mask: mask(size) },
+            Storage::Wide(slot) => TargetKind::WideBus { slot, start: shift as usize },
+        };
+        Ok((kind, size))
+    }
+
+    fn register_array_target(
+        &self,
+        reg_array: &rtprogram::RegisterArray,
+    ) -> Result<(TargetKind, usize)> {
+        let register_array = &self.register_arrays[self.register_array(&reg_array.ident)?];
+        let index = self.index(reg_array)?;
+        let kind = match register_array.storage {
+            Storage::Packed(slot) => TargetKind::RegisterArray { slot, index },
+            Storage::Wide(slot) => TargetKind::WideRegisterArray { slot, index },
+        };
+        Ok((kind, register_array.data_size))
+    }
+
+    /// Lowers a condition, which is true if it evaluates to one.
+    fn condition(&self, expression: &Expression) -> Result<Node> {
+        let node = match self.expression(expression, 1)?.0 {
+            Expr::Packed(node) => node,
+            Expr::Wide(node) => Node::WideCompare {
+                operator: BinaryOperator::Eq,
+                lhs: Box::new(node),
+                rhs: Box::new(WideNode::Const(Value::one(1))),
+            },
+        };
+
+        Ok(node)
+    }
+
+    fn index(&self, reg_array: &rtprogram::RegisterArray) -> Result<Node> {
+        let node = match self.expression(&reg_array.index, reg_array.index_ctx_size)?.0 {
+            Expr::Packed(node) => node,
+            Expr::Wide(node) => Node::Index(Box::new(node)),
+        };
+
+        Ok(node)
+    }
+
+    /// Lowers the expression and returns it together with its size.
+    fn expression(&self, expression: &Expression, ctx_size: usize) -> Result<(Expr, usize)> {
+        let (expr, expr_size) = match &expression.kind {
+            ExpressionKind::Atom(atom) => self.atom(atom)?,
+            ExpressionKind::BinaryTerm(term) => self.binary_term(term, ctx_size)?,
+            ExpressionKind::UnaryTerm(term) => self.unary_term(term, ctx_size)?,
+        };
+
+        // Values are zero extended to the context size
+        let size = expr_size.max(ctx_size);
+        let expr = match expr {
+            Expr::Packed(node) if size > MAX_SIZE => {
+                Expr::Wide(WideNode::Packed { node: Box::new(node), size })
+            }
+            Expr::Wide(node) if size > expr_size => {
+                Expr::Wide(WideNode::Extend { node: Box::new(node), size })
+            }
+            expr => expr,
+        };
+
+        Ok((expr, size))
+    }
+
+    fn atom(&self, atom: &Atom) -> Result<(Expr, usize)> {
+        match atom {
+            Atom::Concat(concat) => self.concat(concat),
+            Atom::Register(reg) => self.register_node(&reg.ident, reg.range),
+            Atom::Bus(bus) => self.bus_node(&bus.ident, bus.range),
+            Atom::RegisterArray(reg_array) => self.register_array_node(reg_array),
+            Atom::Number(number) => Ok(number_node(&number.value)),
+        }
+    }
+
+    fn binary_term(&self, term: &BinaryTerm, ctx_size: usize) -> Result<(Expr, usize)> {
+        let ctx_size_inner = term.ctx_size.calc(ctx_size);
+        let (lhs, lhs_size) = self.expression(&term.lhs, ctx_size_inner)?;
+        let (rhs, rhs_size) = self.expression(&term.rhs, ctx_size_inner)?;
+        let operands_size = lhs_size.max(rhs_size);
+
+        let is_comparison = matches!(
+            term.operator,
+            BinaryOperator::Eq
+                | BinaryOperator::Ne
+                | BinaryOperator::Le
+                | BinaryOperator::Lt
+                | BinaryOperator::Ge
+                | BinaryOperator::Gt
+        );
+        let size = if is_comparison { 1 } else { operands_size };
+        let expr = if operands_size <= MAX_SIZE {
+            Expr::Packed(Node::Binary {
+                operator: term.operator,
+                lhs: Box::new(lhs.into_packed()),
+                rhs: Box::new(rhs.into_packed()),
+                mask: mask(operands_size),
+            })
+        } else if is_comparison {
+            Expr::Packed(Node::WideCompare {
+                operator: term.operator,
+                lhs: Box::new(lhs.into_wide(lhs_size)),
+                rhs: Box::new(rhs.into_wide(rhs_size)),
+            })
+        } else {
+            Expr::Wide(WideNode::Binary {
+                operator: term.operator,
+                lhs: Box::new(lhs.into_wide(lhs_size)),
+                rhs: Box::new(rhs.into_wide(rhs_size)),
+            })
+        };
+
+        Ok((expr, size))
+    }
+
+    fn unary_term(&self, term: &UnaryTerm, ctx_size: usize) -> Result<(Expr, usize)> {
+        let ctx_size_inner = term.ctx_size.calc(ctx_size);
+        let (expression, expression_size) = self.expression(&term.expression, ctx_size_inner)?;
+
+        let size = match term.operator {
+            UnaryOperator::Sxt => expression_size.max(ctx_size),
+            _ => expression_size,
+        };
+        let expr = if size <= MAX_SIZE {
+            Expr::Packed(Node::Unary {
+                operator: term.operator,
+                expression: Box::new(expression.into_packed()),
+                mask: mask(expression_size),
+                sign: 1 << (expression_size - 1),
+                extend: mask(ctx_size) & !mask(expression_size),
+            })
+        } else {
+            Expr::Wide(WideNode::Unary {
+                operator: term.operator,
+                expression: Box::new(expression.into_wide(expression_size)),
+                size,
+            })
+        };
+
+        Ok((expr, size))
+    }
+
+    fn register_node(&self, ident: &Ident, range: Option<BitRange>) -> Result<(Expr, usize)> {
+        let register = &self.registers[self.register(ident)?];
+        let (shift, size) = field(register.range, range)?;
+        let expr = match register.storage {
+            Storage::Packed(slot) => Expr::Packed(Node::Register { slot, shift, mask: mask(size) }),
+            Storage::Wide(slot) if size <= MAX_SIZE => {
+                Expr::Packed(Node::WideRegister { slot, start: shift as usize, size })
+            }
+            Storage::Wide(slot) => {
+                Expr::Wide(WideNode::Register { slot, start: shift as usize, size })
+            }
+        };
+
+        Ok((expr, size))
+    }
+
+    fn bus_node(&self, ident: &Ident, range: Option<BitRange>) -> Result<(Expr, usize)> {
+        let bus = &self.buses[self.bus(ident)?];
+        let (shift, size) = field(bus.range, range)?;
+        let expr = match bus.storage {
+            Storage::Packed(slot) => Expr::Packed(Node::Bus { slot, shift, mask: mask(size) }),
+            Storage::Wide(slot) if size <= MAX_SIZE => {
+                Expr::Packed(Node::WideBus { slot, start: shift as usize, size })
+            }
+            Storage::Wide(slot) => Expr::Wide(WideNode::Bus { slot, start: shift as usize, size }),
+        };
+
+        Ok((expr, size))
+    }
+
+    fn register_array_node(&self, reg_array: &rtprogram::RegisterArray) -> Result<(Expr, usize)> {
+        let register_array = &self.register_arrays[self.register_array(&reg_array.ident)?];
+        let index = Box::new(self.index(reg_array)?);
+        let size = register_array.data_size;
+        let expr = match register_array.storage {
+            Storage::Packed(slot) => Expr::Packed(Node::RegisterArray { slot, index }),
+            Storage::Wide(slot) => Expr::Wide(WideNode::RegisterArray { slot, index, size }),
+        };
+
+        Ok((expr, size))
+    }
+
+    fn concat(&self, concat: &Concat<ConcatPartExpr>) -> Result<(Expr, usize)> {
+        let parts = concat
+            .parts
+            .iter()
+            .map(|part| match part {
+                ConcatPartExpr::Register(reg) => self.register_node(&reg.ident, reg.range),
+                ConcatPartExpr::Bus(bus) => self.bus_node(&bus.ident, bus.range),
+                ConcatPartExpr::RegisterArray(reg_array) => self.register_array_node(reg_array),
+                ConcatPartExpr::Number(number) => Ok(number_node(&number.value)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let size = parts.iter().map(|(_, part_size)| part_size).sum::<usize>();
+
+        let expr = if size <= MAX_SIZE {
+            let mut shift = 0;
+            let mut packed = Vec::new();
+            for (part, part_size) in parts.into_iter().rev() {
+                packed.push((part.into_packed(), shift));
+                shift += part_size as u32;
+            }
+            Expr::Packed(Node::Concat(packed))
+        } else {
+            Expr::Wide(WideNode::Concat(
+                parts.into_iter().map(|(part, part_size)| part.into_wide(part_size)).collect(),
+            ))
+        };
+
+        Ok((expr, size))
+    }
+}
+
+fn number_node(value: &Value) -> (Expr, usize) {
+    let size = value.size();
+    let expr = match value.to_u64() {
+        Some(value) if size <= MAX_SIZE => Expr::Packed(Node::Const(value)),
+        _ => Expr::Wide(WideNode::Const(value.clone())),
+    };
+
+    (expr, size)
+}
+
+/// Returns shift and size of the accessed bits.
+fn field(declared: BitRange, range: Option<BitRange>) -> Result<(u32, usize)> {
+    match range {
+        Some(range) => {
+            let slice_idx = slice_idx(declared, range)?;
+            Ok((slice_idx.start as u32, slice_idx.len()))
+        }
+        None => Ok((0, declared.size())),
+    }
+}
@@ -0,0 +1,341 @@
+//! A fast execution mode for long-running programs.
+//!
+//! The [`FastSimulator`] lowers a [`Program`] into a compact indexed form: All symbols are resolved
+//! to slots, all values are packed into machine words and the steps of every statement are stored
+//! in precomputed tables. It behaves exactly like the [`Simulator`](crate::Simulator), but only
+//! supports whole statement steps. Micro steps, breakpoints, devices, observers and tracking of
+//! uninitialized bits are not supported.
+//!
+//! Values wider than [`MAX_SIZE`] bits do not fit into a machine word. They are stored and
+//! evaluated as [`Value`], which is slower but behaves the same.
+
+mod lower;
+mod state;
+
+pub use self::lower::MAX_SIZE;
+
+use self::{
+    lower::{Expr, Lowered, Operation, Storage, Symbol},
+    state::FastState,
+};
+use crate::Error;
+use anyhow::anyhow;
+use rtcore::{
+    common::BusKind,
+    value::{SignedValue, Value},
+};
+use rtprogram::{Ident, Program, Span};
+
+pub struct FastSimulator {
+    cycle_count: usize,
+    state: FastState,
+    buses_persist: Vec<bool>,
+
+    program: Lowered,
+    /// Index of the next statement, `None` if terminated.
+    statement_idx: Option<usize>,
+    criteria: Vec<bool>,
+}
+
+impl FastSimulator {
+    /// Lowers the program.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a symbol or goto label of the program can not be resolved.
+    pub fn init(program: &Program) -> Result<Self, Error> {
+        let program = lower::lower(program)?;
+        Ok(Self {
+            cycle_count: 0,
+            state: FastState::init(&program),
+            buses_persist: vec![false; program.buses.len()],
+
+            program,
+            statement_idx: Some(0),
+            criteria: Vec::new(),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.cycle_count = 0;
+        self.state = FastState::init(&self.program);
+        self.buses_persist = vec![false; self.program.buses.len()];
+
+        self.statement_idx = Some(0);
+    }
+
+    pub fn cycle_count(&self) -> usize {
+        self.cycle_count
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.statement_idx.is_none()
+    }
+
+    /// Executes the next statement. Returns `None` if the simulator is finished.
+    pub fn step(&mut self) -> Option<FastStepResult> {
+        let statement_idx = self.statement_idx?;
+
+        // Clear intern buses
+        for (slot, bus) in self.program.buses.iter().enumerate() {
+            if bus.kind == BusKind::Intern && !self.buses_persist[slot] {
+                self.state.clear_bus(bus.storage);
+            }
+        }
+        self.buses_persist.iter_mut().for_each(|persist| *persist = false);
+
+        let statement = match self.program.statements.get(statement_idx) {
+            Some(statement) => statement,
+            None => {
+                self.statement_idx = None;
+                return None;
+            }
+        };
+
+        // Execute steps
+        self.criteria.clear();
+        self.criteria.resize(statement.criteria_count, false);
+        let mut goto = None;
+        for (step_idx, step) in statement.steps.iter().enumerate() {
+            if step_idx == statement.split_at && step_idx != 0 {
+                self.state.clock();
+            }
+
+            if !step.criteria.iter().all(|(idx, expected)| self.criteria[*idx] == *expected) {
+                continue;
+            }
+
+            let state = &mut self.state;
+            match &step.operation {
+                Operation::EvalCriterion(idx, condition) => {
+                    if state.eval(condition) == 1 {
+                        self.criteria[*idx] = true;
+                    }
+                }
+                Operation::EvalCriterionGroup(group) => {
+                    if let Some((idx, _)) =
+                        group.iter().find(|(_, condition)| state.eval(condition) == 1)
+                    {
+                        self.criteria[*idx] = true;
+                    }
+                }
+                Operation::Nop => (),
+                Operation::Goto(target) => goto = Some(*target),
+                Operation::Write(slot) => state.write_memory(&self.program.memories[*slot]),
+                Operation::Read(slot) => state.read_memory(&self.program.memories[*slot]),
+                Operation::Assignment(Expr::Packed(rhs), targets) => {
+                    let value = state.eval(rhs);
+                    state.assign(targets, value);
+                }
+                Operation::Assignment(Expr::Wide(rhs), targets) => {
+                    let value = state.eval_wide(rhs);
+                    state.assign_wide(targets, &value);
+                }
+                Operation::Assert(condition, span) => {
+                    if state.eval(condition) != 1 {
+                        self.statement_idx = None;
+                        return Some(FastStepResult {
+                            statement: statement_idx,
+                            span: *span,
+                            kind: FastStepResultKind::AssertError,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Clock and advance
+        self.state.clock();
+        self.statement_idx = Some(goto.unwrap_or(statement_idx + 1));
+        self.cycle_count += 1;
+
+        Some(FastStepResult {
+            statement: statement_idx,
+            span: statement.span,
+            kind: FastStepResultKind::StatementEnd,
+        })
+    }
+
+    // ------------------------------------------------------------
+    // Registers
+    // ------------------------------------------------------------
+
+    pub fn register_value(&self, name: &Ident) -> Result<Value, Error> {
+        let register = &self.program.registers[self.register(name)?];
+        Ok(self.state.register(register.storage, register.range.size()))
+    }
+    pub fn write_register(
+        &mut self,
+        name: &Ident,
+        value: impl Into<SignedValue>,
+    ) -> Result<(), Error> {
+        let register = &self.program.registers[self.register(name)?];
+        let value = into_value(value.into(), register.range.size())?;
+        match register.storage {
+            // Fits, because the register is packed
+            Storage::Packed(slot) => self.state.registers[slot] = value.to_u64().unwrap(),
+            Storage::Wide(slot) => self.state.wide_registers[slot] = value,
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------
+    // Buses
+    // ------------------------------------------------------------
+
+    pub fn bus_value(&self, name: &Ident) -> Result<Value, Error> {
+        let bus = &self.program.buses[self.bus(name)?];
+        let value = match bus.storage {
+            Storage::Packed(slot) => Value::from_u64(self.state.buses[slot], bus.range.size()),
+            Storage::Wide(slot) => self.state.wide_buses[slot].clone(),
+        };
+        Ok(value)
+    }
+    pub fn write_bus(&mut self, name: &Ident, value: impl Into<SignedValue>) -> Result<(), Error> {
+        let slot = self.bus(name)?;
+        let bus = &self.program.buses[slot];
+        let value = into_value(value.into(), bus.range.size())?;
+        match bus.storage {
+            // Fits, because the bus is packed
+            Storage::Packed(slot) => self.state.buses[slot] = value.to_u64().unwrap(),
+            Storage::Wide(slot) => self.state.wide_buses[slot] = value,
+        }
+
+        // Persist bus value if between statements
+        if self.statement_idx.is_some() {
+            self.buses_persist[slot] = true;
+        }
+
+        Ok(())
+    }
+
+    // ------------------------------------------------------------
+    // Register arrays
+    // ------------------------------------------------------------
+
+    pub fn register_array_value(&self, name: &Ident, idx: usize) -> Result<Value, Error> {
+        let reg_array = &self.program.register_arrays[self.register_array(name)?];
+        let value = match reg_array.storage {
+            Storage::Packed(slot) => self.state.register_arrays[slot]
+                .get(idx)
+                .map(|value| Value::from_u64(*value, reg_array.data_size)),
+            Storage::Wide(slot) => self.state.wide_register_arrays[slot].get(idx).cloned(),
+        };
+        value.ok_or_else(|| anyhow!("index out of range"))
+    }
+    pub fn write_register_array(
+        &mut self,
+        name: &Ident,
+        idx: usize,
+        value: impl Into<SignedValue>,
+    ) -> Result<(), Error> {
+        let reg_array = &self.program.register_arrays[self.register_array(name)?];
+        let value = into_value(value.into(), reg_array.data_size)?;
+        match reg_array.storage {
+            Storage::Packed(slot) => {
+                let target = self.state.register_arrays[slot]
+                    .get_mut(idx)
+                    .ok_or_else(|| anyhow!("index out of range"))?;
+                // Fits, because the register array is packed
+                *target = value.to_u64().unwrap();
+            }
+            Storage::Wide(slot) => {
+                let target = self.state.wide_register_arrays[slot]
+                    .get_mut(idx)
+                    .ok_or_else(|| anyhow!("index out of range"))?;
+                *target = value;
+            }
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------
+    // Memories
+    // ------------------------------------------------------------
+
+    pub fn memory_value(&self, name: &Ident, addr: &Value) -> Result<Value, Error> {
+        let memory = &self.program.memories[self.memory(name)?];
+        if addr.size() > memory.ar_size {
+            return Err(anyhow!("address too big"));
+        }
+        let value = match memory.storage {
+            Storage::Packed(slot) => {
+                // Fits, because the address register is packed
+                let value = self.state.memories[slot].get(addr.to_u64().unwrap());
+                Value::from_u64(value, memory.dr_size)
+            }
+            Storage::Wide(slot) => self.state.wide_memories[slot]
+                .get(addr)
+                .cloned()
+                .unwrap_or_else(|| Value::zero(memory.dr_size)),
+        };
+        Ok(value)
+    }
+    pub fn write_memory(
+        &mut self,
+        name: &Ident,
+        addr: Value,
+        value: impl Into<SignedValue>,
+    ) -> Result<(), Error> {
+        let memory = &self.program.memories[self.memory(name)?];
+        if addr.size() > memory.ar_size {
+            return Err(anyhow!("address too big"));
+        }
+        let value = into_value(value.into(), memory.dr_size)?;
+        match memory.storage {
+            Storage::Packed(slot) => {
+                // Fits, because the address and data register are packed
+                self.state.memories[slot].set(addr.to_u64().unwrap(), value.to_u64().unwrap());
+            }
+            Storage::Wide(slot) => {
+                self.state.wide_memories[slot].insert(addr, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn register(&self, name: &Ident) -> Result<usize, Error> {
+        match self.program.symbols.get(name) {
+            Some(Symbol::Register(slot)) => Ok(*slot),
+            _ => Err(anyhow!("register `{}` not found", name.0)),
+        }
+    }
+
+    fn bus(&self, name: &Ident) -> Result<usize, Error> {
+        match self.program.symbols.get(name) {
+            Some(Symbol::Bus(slot)) => Ok(*slot),
+            _ => Err(anyhow!("bus `{}` not found", name.0)),
+        }
+    }
+
+    fn register_array(&self, name: &Ident) -> Result<usize, Error> {
+        match self.program.symbols.get(name) {
+            Some(Symbol::RegisterArray(slot)) => Ok(*slot),
+            _ => Err(anyhow!("register array `{}` not found", name.0)),
+        }
+    }
+
+    fn memory(&self, name: &Ident) -> Result<usize, Error> {
+        match self.program.symbols.get(name) {
+            Some(Symbol::Memory(slot)) => Ok(*slot),
+            _ => Err(anyhow!("memory `{}` not found", name.0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastStepResult {
+    pub statement: usize,
+    pub span: Span,
+    pub kind: FastStepResultKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastStepResultKind {
+    StatementEnd,
+    AssertError,
+}
+
+fn into_value(value: SignedValue, size: usize) -> Result<Value, Error> {
+    value.into_twos_complement(size).map_err(|_| anyhow!("value too big"))
+}
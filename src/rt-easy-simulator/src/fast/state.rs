@@ -0,0 +1,460 @@
+use super::lower::{mask, Lowered, MemorySlot, Node, Storage, Target, TargetKind, WideNode};
+use rtcore::{
+    common::{BinaryOperator, UnaryOperator},
+    value::Value,
+};
+use std::collections::HashMap;
+
+/// Memories with an address register of at most this size are stored densely.
+const DENSE_MEMORY_MAX_AR_SIZE: usize = 16;
+
+#[derive(Debug)]
+pub struct FastState {
+    pub registers: Vec<u64>,
+    registers_next: Vec<u64>,
+    registers_pending: Vec<bool>,
+    registers_dirty: Vec<usize>,
+    pub wide_registers: Vec<Value>,
+    wide_registers_next: Vec<Option<Value>>,
+    pub buses: Vec<u64>,
+    pub wide_buses: Vec<Value>,
+    pub register_arrays: Vec<Vec<u64>>,
+    register_arrays_next: Vec<Option<(usize, u64)>>,
+    pub wide_register_arrays: Vec<Vec<Value>>,
+    wide_register_arrays_next: Vec<Option<(usize, Value)>>,
+    pub memories: Vec<MemoryData>,
+    memories_next: Vec<Option<(u64, u64)>>,
+    pub wide_memories: Vec<HashMap<Value, Value>>,
+    wide_memories_next: Vec<Option<(Value, Value)>>,
+}
+
+impl FastState {
+    pub fn init(program: &Lowered) -> Self {
+        let mut state = Self {
+            registers: Vec::new(),
+            registers_next: Vec::new(),
+            registers_pending: Vec::new(),
+            registers_dirty: Vec::new(),
+            wide_registers: Vec::new(),
+            wide_registers_next: Vec::new(),
+            buses: Vec::new(),
+            wide_buses: Vec::new(),
+            register_arrays: Vec::new(),
+            register_arrays_next: Vec::new(),
+            wide_register_arrays: Vec::new(),
+            wide_register_arrays_next: Vec::new(),
+            memories: Vec::new(),
+            memories_next: Vec::new(),
+            wide_memories: Vec::new(),
+            wide_memories_next: Vec::new(),
+        };
+
+        // Slots are numbered in declaration order
+        for register in &program.registers {
+            match register.storage {
+                Storage::Packed(_) => {
+                    // Fits, because the register is packed
+                    state.registers.push(register.init.to_u64().unwrap());
+                    state.registers_next.push(0);
+                    state.registers_pending.push(false);
+                }
+                Storage::Wide(_) => {
+                    state.wide_registers.push(register.init.clone());
+                    state.wide_registers_next.push(None);
+                }
+            }
+        }
+        for bus in &program.buses {
+            match bus.storage {
+                Storage::Packed(_) => state.buses.push(0),
+                Storage::Wide(_) => state.wide_buses.push(Value::zero(bus.range.size())),
+            }
+        }
+        for reg_array in &program.register_arrays {
+            match reg_array.storage {
+                Storage::Packed(_) => {
+                    state.register_arrays.push(vec![0; reg_array.len]);
+                    state.register_arrays_next.push(None);
+                }
+                Storage::Wide(_) => {
+                    state
+                        .wide_register_arrays
+                        .push(vec![Value::zero(reg_array.data_size); reg_array.len]);
+                    state.wide_register_arrays_next.push(None);
+                }
+            }
+        }
+        for memory in &program.memories {
+            match memory.storage {
+                Storage::Packed(_) => {
+                    state.memories.push(if memory.ar_size <= DENSE_MEMORY_MAX_AR_SIZE {
+                        MemoryData::Dense(vec![0; 1 << memory.ar_size])
+                    } else {
+                        MemoryData::Sparse(HashMap::new())
+                    });
+                    state.memories_next.push(None);
+                }
+                Storage::Wide(_) => {
+                    state.wide_memories.push(HashMap::new());
+                    state.wide_memories_next.push(None);
+                }
+            }
+        }
+
+        state
+    }
+
+    pub fn eval(&self, node: &Node) -> u64 {
+        match node {
+            Node::Const(value) => *value,
+            Node::Register { slot, shift, mask } => (self.registers[*slot] >> shift) & mask,
+            Node::WideRegister { slot, start, size } => {
+                packed_field(&self.wide_registers[*slot], *start, *size)
+            }
+            Node::Bus { slot, shift, mask } => (self.buses[*slot] >> shift) & mask,
+            Node::WideBus { slot, start, size } => {
+                packed_field(&self.wide_buses[*slot], *start, *size)
+            }
+            Node::RegisterArray { slot, index } => {
+                let index = self.eval(index) as usize;
+                self.register_arrays[*slot].get(index).copied().unwrap_or(0)
+            }
+            Node::Index(index) => self.eval_wide(index).to_u64().unwrap_or(u64::MAX),
+            Node::Concat(parts) => {
+                parts.iter().fold(0, |value, (part, shift)| value | (self.eval(part) << shift))
+            }
+            Node::Binary { operator, lhs, rhs, mask } => {
+                let lhs = self.eval(lhs);
+                let rhs = self.eval(rhs);
+                match operator {
+                    BinaryOperator::Eq => (lhs == rhs) as u64,
+                    BinaryOperator::Ne => (lhs != rhs) as u64,
+                    BinaryOperator::Le => (lhs <= rhs) as u64,
+                    BinaryOperator::Lt => (lhs < rhs) as u64,
+                    BinaryOperator::Ge => (lhs >= rhs) as u64,
+                    BinaryOperator::Gt => (lhs > rhs) as u64,
+                    BinaryOperator::Add => lhs.wrapping_add(rhs) & mask,
+                    BinaryOperator::Sub => lhs.wrapping_sub(rhs) & mask,
+                    BinaryOperator::Mul => lhs.wrapping_mul(rhs) & mask,
+                    BinaryOperator::And => lhs & rhs,
+                    BinaryOperator::Nand => !(lhs & rhs) & mask,
+                    BinaryOperator::Or => lhs | rhs,
+                    BinaryOperator::Nor => !(lhs | rhs) & mask,
+                    BinaryOperator::Xor => lhs ^ rhs,
+                }
+            }
+            Node::Unary { operator, expression, mask, sign, extend } => {
+                let value = self.eval(expression);
+                match operator {
+                    UnaryOperator::Sign | UnaryOperator::Neg => value.wrapping_neg() & mask,
+                    UnaryOperator::Not => !value & mask,
+                    UnaryOperator::Sxt => {
+                        if value & sign != 0 {
+                            value | extend
+                        } else {
+                            value
+                        }
+                    }
+                }
+            }
+            Node::WideCompare { operator, lhs, rhs } => {
+                let lhs = self.eval_wide(lhs);
+                let rhs = self.eval_wide(rhs);
+                match operator {
+                    BinaryOperator::Eq => (lhs == rhs) as u64,
+                    BinaryOperator::Ne => (lhs != rhs) as u64,
+                    BinaryOperator::Le => (lhs <= rhs) as u64,
+                    BinaryOperator::Lt => (lhs < rhs) as u64,
+                    BinaryOperator::Ge => (lhs >= rhs) as u64,
+                    BinaryOperator::Gt => (lhs > rhs) as u64,
+                    _ => unreachable!("not a comparison"),
+                }
+            }
+        }
+    }
+
+    pub fn eval_wide(&self, node: &WideNode) -> Value {
+        match node {
+            WideNode::Const(value) => value.clone(),
+            WideNode::Register { slot, start, size } => {
+                self.wide_registers[*slot].slice(*start..*start + *size).to_value()
+            }
+            WideNode::Bus { slot, start, size } => {
+                self.wide_buses[*slot].slice(*start..*start + *size).to_value()
+            }
+            WideNode::RegisterArray { slot, index, size } => {
+                let index = self.eval(index) as usize;
+                self.wide_register_arrays[*slot]
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| Value::zero(*size))
+            }
+            WideNode::Concat(parts) => {
+                let values = parts.iter().map(|part| self.eval_wide(part)).collect::<Vec<_>>();
+                Value::concat(values.iter().map(Value::as_slice))
+            }
+            WideNode::Binary { operator, lhs, rhs } => {
+                let lhs = self.eval_wide(lhs);
+                let rhs = self.eval_wide(rhs);
+                match operator {
+                    BinaryOperator::Add => lhs + rhs,
+                    BinaryOperator::Sub => lhs - rhs,
+                    BinaryOperator::Mul => lhs * rhs,
+                    BinaryOperator::And => lhs & rhs,
+                    BinaryOperator::Nand => !(lhs & rhs),
+                    BinaryOperator::Or => lhs | rhs,
+                    BinaryOperator::Nor => !(lhs | rhs),
+                    BinaryOperator::Xor => lhs ^ rhs,
+                    _ => unreachable!("comparisons are packed"),
+                }
+            }
+            WideNode::Unary { operator, expression, size } => {
+                let mut value = self.eval_wide(expression);
+                match operator {
+                    UnaryOperator::Sign | UnaryOperator::Neg => -value,
+                    UnaryOperator::Not => !value,
+                    UnaryOperator::Sxt => {
+                        value.extend_sign(*size);
+                        value
+                    }
+                }
+            }
+            WideNode::Packed { node, size } => Value::from_u64(self.eval(node), *size),
+            WideNode::Extend { node, size } => {
+                let mut value = self.eval_wide(node);
+                value.extend_zero(*size);
+                value
+            }
+        }
+    }
+
+    pub fn register(&self, storage: Storage, size: usize) -> Value {
+        match storage {
+            Storage::Packed(slot) => Value::from_u64(self.registers[slot], size),
+            Storage::Wide(slot) => self.wide_registers[slot].clone(),
+        }
+    }
+
+    /// Schedules a write of `value` to the bits `shift..` of the register.
+    pub fn write_register(&mut self, slot: usize, shift: u32, mask: u64, value: u64) {
+        if !self.registers_pending[slot] {
+            self.registers_pending[slot] = true;
+            self.registers_next[slot] = self.registers[slot];
+            self.registers_dirty.push(slot);
+        }
+
+        let next = &mut self.registers_next[slot];
+        *next = (*next & !(mask << shift)) | ((value & mask) << shift);
+    }
+
+    /// Schedules a write of `value` to the bits `start..` of the wide register.
+    pub fn write_wide_register(&mut self, slot: usize, start: usize, value: &Value) {
+        let current = &self.wide_registers[slot];
+        let next = self.wide_registers_next[slot].get_or_insert_with(|| current.clone());
+        next.slice_mut(start..start + value.size()).write(value);
+    }
+
+    pub fn write_bus(&mut self, slot: usize, shift: u32, mask: u64, value: u64) {
+        let bus = &mut self.buses[slot];
+        *bus = (*bus & !(mask << shift)) | ((value & mask) << shift);
+    }
+
+    pub fn write_wide_bus(&mut self, slot: usize, start: usize, value: &Value) {
+        self.wide_buses[slot].slice_mut(start..start + value.size()).write(value);
+    }
+
+    pub fn clear_bus(&mut self, storage: Storage) {
+        match storage {
+            Storage::Packed(slot) => self.buses[slot] = 0,
+            Storage::Wide(slot) => {
+                let bus = &mut self.wide_buses[slot];
+                *bus = Value::zero(bus.size());
+            }
+        }
+    }
+
+    pub fn write_register_array(&mut self, slot: usize, index: usize, value: u64) {
+        self.register_arrays_next[slot] = Some((index, value));
+    }
+
+    pub fn write_wide_register_array(&mut self, slot: usize, index: usize, value: Value) {
+        self.wide_register_arrays_next[slot] = Some((index, value));
+    }
+
+    /// Writes the bits `offset..offset + size` of `value` to the targets.
+    pub fn assign(&mut self, targets: &[Target], value: u64) {
+        for target in targets {
+            self.write_target(target, Bits::Packed((value >> target.offset) & target.mask));
+        }
+    }
+
+    /// Writes the bits `offset..offset + size` of the wide `value` to the targets.
+    pub fn assign_wide(&mut self, targets: &[Target], value: &Value) {
+        for target in targets {
+            let start = target.offset as usize;
+            self.write_target(
+                target,
+                Bits::Wide(value.slice(start..start + target.size).to_value()),
+            );
+        }
+    }
+
+    fn write_target(&mut self, target: &Target, bits: Bits) {
+        match &target.kind {
+            TargetKind::Register { slot, shift, mask } => {
+                self.write_register(*slot, *shift, *mask, bits.packed())
+            }
+            TargetKind::WideRegister { slot, start } => {
+                self.write_wide_register(*slot, *start, &bits.wide(target.size))
+            }
+            TargetKind::Bus { slot, shift, mask } => {
+                self.write_bus(*slot, *shift, *mask, bits.packed())
+            }
+            TargetKind::WideBus { slot, start } => {
+                self.write_wide_bus(*slot, *start, &bits.wide(target.size))
+            }
+            TargetKind::RegisterArray { slot, index } => {
+                let index = self.eval(index) as usize;
+                self.write_register_array(*slot, index, bits.packed());
+            }
+            TargetKind::WideRegisterArray { slot, index } => {
+                let index = self.eval(index) as usize;
+                self.write_wide_register_array(*slot, index, bits.wide(target.size));
+            }
+        }
+    }
+
+    /// Schedules a write of the data register to the memory at the address register.
+    pub fn write_memory(&mut self, memory: &MemorySlot) {
+        match memory.storage {
+            Storage::Packed(slot) => {
+                let address = self.registers[memory.address_register.packed()];
+                let value = self.registers[memory.data_register.packed()];
+                self.memories_next[slot] = Some((address, value));
+            }
+            Storage::Wide(slot) => {
+                let address = self.register(memory.address_register, memory.ar_size);
+                let value = self.register(memory.data_register, memory.dr_size);
+                self.wide_memories_next[slot] = Some((address, value));
+            }
+        }
+    }
+
+    /// Schedules a write of the memory at the address register to the data register.
+    pub fn read_memory(&mut self, memory: &MemorySlot) {
+        match memory.storage {
+            Storage::Packed(slot) => {
+                let address = self.registers[memory.address_register.packed()];
+                let value = self.memories[slot].get(address);
+                self.write_register(memory.data_register.packed(), 0, mask(memory.dr_size), value);
+            }
+            Storage::Wide(slot) => {
+                let address = self.register(memory.address_register, memory.ar_size);
+                let value = self.wide_memories[slot]
+                    .get(&address)
+                    .cloned()
+                    .unwrap_or_else(|| Value::zero(memory.dr_size));
+                match memory.data_register {
+                    Storage::Packed(dr) => {
+                        // Fits, because the data register is packed
+                        self.write_register(dr, 0, u64::MAX, value.to_u64().unwrap())
+                    }
+                    Storage::Wide(dr) => self.write_wide_register(dr, 0, &value),
+                }
+            }
+        }
+    }
+
+    pub fn clock(&mut self) {
+        for slot in self.registers_dirty.drain(..) {
+            self.registers[slot] = self.registers_next[slot];
+            self.registers_pending[slot] = false;
+        }
+        for (slot, next) in self.wide_registers_next.iter_mut().enumerate() {
+            if let Some(value) = next.take() {
+                self.wide_registers[slot] = value;
+            }
+        }
+        for (slot, next) in self.register_arrays_next.iter_mut().enumerate() {
+            if let Some((index, value)) = next.take() {
+                if let Some(target) = self.register_arrays[slot].get_mut(index) {
+                    *target = value;
+                }
+            }
+        }
+        for (slot, next) in self.wide_register_arrays_next.iter_mut().enumerate() {
+            if let Some((index, value)) = next.take() {
+                if let Some(target) = self.wide_register_arrays[slot].get_mut(index) {
+                    *target = value;
+                }
+            }
+        }
+        for (slot, next) in self.memories_next.iter_mut().enumerate() {
+            if let Some((address, value)) = next.take() {
+                self.memories[slot].set(address, value);
+            }
+        }
+        for (slot, next) in self.wide_memories_next.iter_mut().enumerate() {
+            if let Some((address, value)) = next.take() {
+                self.wide_memories[slot].insert(address, value);
+            }
+        }
+    }
+}
+
+/// Bits written to a target.
+enum Bits {
+    Packed(u64),
+    Wide(Value),
+}
+
+impl Bits {
+    fn packed(self) -> u64 {
+        match self {
+            Self::Packed(value) => value,
+            // Fits, because packed targets have at most `MAX_SIZE` bits
+            Self::Wide(value) => value.to_u64().unwrap(),
+        }
+    }
+
+    fn wide(self, size: usize) -> Value {
+        match self {
+            Self::Packed(value) => Value::from_u64(value, size),
+            Self::Wide(value) => value,
+        }
+    }
+}
+
+/// Returns the bits `start..start + size` of a wide value with at most `MAX_SIZE` bits.
+fn packed_field(value: &Value, start: usize, size: usize) -> u64 {
+    // Fits, because the field has at most `MAX_SIZE` bits
+    value.slice(start..start + size).to_value().to_u64().unwrap()
+}
+
+#[derive(Debug)]
+pub enum MemoryData {
+    Dense(Vec<u64>),
+    Sparse(HashMap<u64, u64>),
+}
+
+impl MemoryData {
+    pub fn get(&self, address: u64) -> u64 {
+        match self {
+            Self::Dense(data) => data.get(address as usize).copied().unwrap_or(0),
+            Self::Sparse(data) => data.get(&address).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn set(&mut self, address: u64, value: u64) {
+        match self {
+            Self::Dense(data) => {
+                if let Some(target) = data.get_mut(address as usize) {
+                    *target = value;
+                }
+            }
+            Self::Sparse(data) => {
+                data.insert(address, value);
+            }
+        }
+    }
+}
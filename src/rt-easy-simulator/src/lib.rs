@@ -3,12 +3,19 @@
 mod changed;
 mod error;
 mod evaluate;
+mod evaluate_known;
 mod execute;
 mod simulator;
 mod state;
+mod uninitialized;
+
+pub mod device;
+pub mod fast;
+pub mod observer;
 
 pub use self::{
     changed::Changed,
     error::{Error, Result},
     simulator::{Simulator, StepResult, StepResultKind},
+    uninitialized::{UninitializedDiagnostic, UninitializedKind},
 };
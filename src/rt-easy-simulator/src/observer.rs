@@ -0,0 +1,90 @@
+use crate::Changed;
+use rtcore::{common::BitRange, value::Value};
+use rtprogram::{Ident, Label, Span, Step};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Receives events while the simulator is running.
+///
+/// Register observers with [`Simulator::add_observer`](crate::Simulator::add_observer). All
+/// methods have empty default implementations, so an observer only implements the events it is
+/// interested in. To access the observer while it is registered, register an
+/// `Rc<RefCell<impl Observer>>` and keep a clone of the `Rc`.
+#[allow(unused_variables)]
+pub trait Observer {
+    /// A micro step has been executed.
+    fn micro_step(&mut self, statement: usize, step: &Step, span: Span) {}
+
+    /// A condition has been evaluated.
+    fn condition(&mut self, statement: usize, result: bool, span: Span) {}
+
+    /// A value has been written to a bus.
+    fn bus_write(&mut self, bus: &Ident, range: Option<BitRange>, value: &Value) {}
+
+    /// A register write has been scheduled. It takes effect on the next clock edge.
+    fn register_write(&mut self, register: &Ident, range: Option<BitRange>, value: &Value) {}
+
+    /// A register array write has been scheduled. It takes effect on the next clock edge.
+    fn register_array_write(&mut self, register_array: &Ident, index: usize, value: &Value) {}
+
+    /// A memory write has been scheduled. It takes effect on the next clock edge.
+    fn memory_write(&mut self, memory: &Ident, address: &Value, value: &Value) {}
+
+    /// A clock edge (at a pipe or at the end of a statement).
+    fn clock(&mut self, changed: &Changed) {}
+
+    /// A goto has been taken at the end of `statement`.
+    fn goto(&mut self, statement: usize, label: &Label, target: usize) {}
+
+    /// The simulator stopped at a breakpoint.
+    fn breakpoint(&mut self, statement: usize) {}
+
+    /// An assert failed.
+    fn assert_error(&mut self, statement: usize, span: Span) {}
+}
+
+impl<T: Observer + ?Sized> Observer for Rc<RefCell<T>> {
+    fn micro_step(&mut self, statement: usize, step: &Step, span: Span) {
+        self.borrow_mut().micro_step(statement, step, span)
+    }
+
+    fn condition(&mut self, statement: usize, result: bool, span: Span) {
+        self.borrow_mut().condition(statement, result, span)
+    }
+
+    fn bus_write(&mut self, bus: &Ident, range: Option<BitRange>, value: &Value) {
+        self.borrow_mut().bus_write(bus, range, value)
+    }
+
+    fn register_write(&mut self, register: &Ident, range: Option<BitRange>, value: &Value) {
+        self.borrow_mut().register_write(register, range, value)
+    }
+
+    fn register_array_write(&mut self, register_array: &Ident, index: usize, value: &Value) {
+        self.borrow_mut().register_array_write(register_array, index, value)
+    }
+
+    fn memory_write(&mut self, memory: &Ident, address: &Value, value: &Value) {
+        self.borrow_mut().memory_write(memory, address, value)
+    }
+
+    fn clock(&mut self, changed: &Changed) {
+        self.borrow_mut().clock(changed)
+    }
+
+    fn goto(&mut self, statement: usize, label: &Label, target: usize) {
+        self.borrow_mut().goto(statement, label, target)
+    }
+
+    fn breakpoint(&mut self, statement: usize) {
+        self.borrow_mut().breakpoint(statement)
+    }
+
+    fn assert_error(&mut self, statement: usize, span: Span) {
+        self.borrow_mut().assert_error(statement, span)
+    }
+}
+
+/// Identifies a registered observer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(pub(crate) usize);
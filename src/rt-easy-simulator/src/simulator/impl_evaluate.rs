@@ -0,0 +1,138 @@
+use super::Simulator;
+use crate::{evaluate::Evaluate, Error};
+use anyhow::anyhow;
+use rtast as ast;
+use rtcore::{
+    common::{Number, NumberKind, Span, Spanned},
+    value::Value,
+};
+use rtprogram::Declaration;
+
+impl Simulator {
+    /// Evaluates an RT expression (e.g. `A + B(3:0)` or `ARR[2]`) against the current state.
+    ///
+    /// The expression is checked against the declarations of the program. The result has the
+    /// size of the expression.
+    pub fn evaluate(&self, expr: &str) -> Result<Value, Error> {
+        let expression = match parser::parse_expression(expr) {
+            Ok(expression) => expression,
+            Err(e) => return Err(anyhow!(parser::pretty_print_error(&e, expr, None, false))),
+        };
+
+        let (expression, size) = match compiler::compile_expression(&self.ast(), expression) {
+            Ok(res) => res,
+            Err(e) => return Err(anyhow!(e.pretty_print(expr, None, false))),
+        };
+        let expression = compiler_backend_simulator::generate_expression(expression);
+
+        expression.evaluate(&self.state, size)
+    }
+
+    /// Checks a parsed RT expression against the declarations of the program and returns its
+    /// size. The errors have the spans of the expression.
+    pub fn check_expression(
+        &self,
+        expression: ast::Expression<'_>,
+    ) -> Result<usize, compiler::Error> {
+        let (_, size) = compiler::compile_expression(&self.ast(), expression)?;
+        Ok(size)
+    }
+
+    /// The declarations of the program, without statements.
+    fn ast(&self) -> ast::Ast<'_> {
+        ast::Ast {
+            includes: Vec::new(),
+            declarations: map_declarations(self.program.declarations()),
+            statements: Vec::new(),
+            trailing_label: None,
+            expansions: Vec::new(),
+        }
+    }
+}
+
+fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
+    declarations
+        .iter()
+        .map(|declaration| match declaration {
+            Declaration::Register(declare) => ast::Declaration::Register(ast::DeclareRegister {
+                registers: declare
+                    .registers
+                    .iter()
+                    .map(|register| ast::DeclareRegisterItem {
+                        ident: spanned_dummy(ast::Ident(&register.ident.0)),
+                        range: register.range.map(|range| spanned_dummy(range.into())),
+                        init: None,
+                        span: Span::dummy(),
+                    })
+                    .collect(),
+                kind: declare.registers[0].kind,
+                span: Span::dummy(),
+            }),
+            Declaration::Bus(declare) => ast::Declaration::Bus(ast::DeclareBus {
+                buses: declare
+                    .buses
+                    .iter()
+                    .map(|bus| ast::RegBus {
+                        ident: spanned_dummy(ast::Ident(&bus.ident.0)),
+                        range: bus.range.map(|range| spanned_dummy(range.into())),
+                        span: Span::dummy(),
+                    })
+                    .collect(),
+                kind: declare.buses[0].kind,
+                span: Span::dummy(),
+            }),
+            Declaration::Memory(declare) => ast::Declaration::Memory(ast::DeclareMemory {
+                memories: declare
+                    .memories
+                    .iter()
+                    .map(|memory| ast::Memory {
+                        ident: spanned_dummy(ast::Ident(&memory.ident.0)),
+                        range: ast::MemoryRange {
+                            address_register: spanned_dummy(ast::Ident(
+                                &memory.range.address_register.0,
+                            )),
+                            data_register: spanned_dummy(ast::Ident(&memory.range.data_register.0)),
+                            span: Span::dummy(),
+                        },
+                        span: Span::dummy(),
+                    })
+                    .collect(),
+                span: Span::dummy(),
+            }),
+            Declaration::RegisterArray(declare) => {
+                ast::Declaration::RegisterArray(ast::DeclareRegisterArray {
+                    register_arrays: declare
+                        .register_arrays
+                        .iter()
+                        .map(|reg_array| ast::DeclareRegisterArrayItem {
+                            ident: spanned_dummy(ast::Ident(&reg_array.ident.0)),
+                            range: reg_array.range.map(|range| spanned_dummy(range.into())),
+                            len: spanned_dummy(reg_array.len.into()),
+                            span: Span::dummy(),
+                        })
+                        .collect(),
+                    span: Span::dummy(),
+                })
+            }
+            Declaration::Constant(declare) => ast::Declaration::Constant(ast::DeclareConstant {
+                constants: declare
+                    .constants
+                    .iter()
+                    .map(|constant| ast::DeclareConstantItem {
+                        ident: spanned_dummy(ast::Ident(&constant.ident.0)),
+                        value: ast::Expression::Atom(ast::Atom::Number(spanned_dummy(Number {
+                            value: constant.value.clone(),
+                            kind: NumberKind::Decimal,
+                        }))),
+                        span: Span::dummy(),
+                    })
+                    .collect(),
+                span: Span::dummy(),
+            }),
+        })
+        .collect()
+}
+
+fn spanned_dummy<T>(node: T) -> Spanned<T> {
+    Spanned { node, span: Span::dummy() }
+}
@@ -1,5 +1,6 @@
 use super::Simulator;
-use crate::Error;
+use crate::{device::Device, Error};
+use memory_file::{Format, MemoryDiff, MemoryFile, Options};
 use rtcore::{
     common::{BusKind, RegisterKind},
     value::{SignedValue, Value},
@@ -28,6 +29,7 @@ impl Simulator {
         let register = self.state.register_mut(name)?;
         let value = into_twos_complement(value.into(), register.range().size())?;
         register.write(None, value)?;
+        register.write_known(None, Value::filled(register.range().size()))?;
         register.clock();
 
         Ok(())
@@ -47,6 +49,7 @@ impl Simulator {
         let bus = self.state.bus_mut(name)?;
         let value = into_twos_complement(value.into(), bus.range().size())?;
         bus.write(None, value)?;
+        bus.write_known(None, Value::filled(bus.range().size()))?;
 
         // Persist bus value if between statements
         if self.cursor.is_at_statement_start() {
@@ -87,7 +90,8 @@ impl Simulator {
         let idx = Value::parse_bin(&format!("{:b}", idx)).unwrap();
         let value = into_twos_complement(value.into(), reg_array.data_size())?;
 
-        reg_array.write(idx, value)?;
+        reg_array.write(idx.clone(), value)?;
+        reg_array.write_known(idx, Value::filled(reg_array.data_size()))?;
         reg_array.clock();
 
         Ok(())
@@ -133,6 +137,14 @@ impl Simulator {
         memory.write_at(addr, value)?;
         Ok(())
     }
+    /// Snapshot of the content of the memory `name`.
+    pub fn memory_file(&self, name: &Ident) -> Result<MemoryFile, Error> {
+        Ok(self.state.memory(name)?.memory_file())
+    }
+    /// Changes of the memory `name` compared to `reference`, e.g. a snapshot taken before a run.
+    pub fn diff_memory(&self, name: &Ident, reference: &MemoryFile) -> Result<MemoryDiff, Error> {
+        Ok(reference.diff(&self.state.memory(name)?.memory_file()))
+    }
     pub fn save_memory<W>(&self, name: &Ident, writer: W) -> Result<(), Error>
     where
         W: std::io::Write,
@@ -145,6 +157,63 @@ impl Simulator {
     {
         self.state.memory_mut(name)?.load_from_save(reader)
     }
+    pub fn save_memory_as<W>(
+        &self,
+        name: &Ident,
+        format: Format,
+        options: &Options,
+        writer: W,
+    ) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        self.state.memory(name)?.save_as(format, options, writer)
+    }
+    pub fn load_memory_from_save_as<R>(
+        &mut self,
+        name: &Ident,
+        format: Format,
+        options: &Options,
+        reader: R,
+    ) -> Result<(), Error>
+    where
+        R: std::io::Read,
+    {
+        self.state.memory_mut(name)?.load_from_save_as(format, options, reader)
+    }
+
+    // ------------------------------------------------------------
+    // Devices
+    // ------------------------------------------------------------
+
+    /// Attaches a memory-mapped device to the memory `name`. The device claims the addresses
+    /// `start..start + device.size()`.
+    pub fn attach_device(
+        &mut self,
+        name: &Ident,
+        start: Value,
+        device: impl Device + 'static,
+    ) -> Result<(), Error> {
+        self.state.memory_mut(name)?.attach_device(start, Box::new(device))
+    }
+    pub fn device<T: Device + 'static>(&self, name: &Ident, start: &Value) -> Result<&T, Error> {
+        self.state
+            .memory(name)?
+            .device(start)
+            .and_then(|device| device.as_any().downcast_ref())
+            .ok_or_else(|| anyhow::anyhow!("no such device at address {}", start.as_hex()))
+    }
+    pub fn device_mut<T: Device + 'static>(
+        &mut self,
+        name: &Ident,
+        start: &Value,
+    ) -> Result<&mut T, Error> {
+        self.state
+            .memory_mut(name)?
+            .device_mut(start)
+            .and_then(|device| device.as_any_mut().downcast_mut())
+            .ok_or_else(|| anyhow::anyhow!("no such device at address {}", start.as_hex()))
+    }
 }
 
 fn into_twos_complement(value: SignedValue, size: usize) -> anyhow::Result<Value> {
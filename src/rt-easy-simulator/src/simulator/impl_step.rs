@@ -1,11 +1,12 @@
 use super::{Changed, Simulator, StepResult, StepResultKind};
 use crate::{
     execute::{Execute, ExecuteResult},
-    state::State,
-    Error,
+    observer::{Observer, ObserverId},
+    state::{State, WriteEvent},
+    Error, UninitializedDiagnostic, UninitializedKind,
 };
 use anyhow::anyhow;
-use rtprogram::{Criterion, CriterionId, Label, Step};
+use rtprogram::{Criterion, CriterionId, Label, OperationKind, Span, Step};
 use std::{collections::HashSet, mem};
 
 impl Simulator {
@@ -84,6 +85,7 @@ impl Simulator {
                 && self.breakpoints.contains(&cursor.statement_idx)
             {
                 cursor.triggered_breakpoint = true;
+                notify(&mut self.observers, |o| o.breakpoint(cursor.statement_idx));
                 break (Ok(Some(StepResult {
                     statement: cursor.statement_idx,
                     span: statement.steps.span,
@@ -100,7 +102,17 @@ impl Simulator {
                         })?;
 
                     // Execute step
-                    let step_result = exec_step(cursor, &self.state, cursor.statement_idx, step)?;
+                    let step_result = exec_step(
+                        cursor,
+                        &self.state,
+                        cursor.statement_idx,
+                        statement.steps.span,
+                        step,
+                        &mut self.uninitialized,
+                    )?;
+                    if let Some(step_result) = &step_result {
+                        notify_step(&mut self.observers, &self.state, step, step_result);
+                    }
 
                     // Advance cursor
                     if step_idx == statement.steps.node.as_slice().len() - 1 {
@@ -119,6 +131,7 @@ impl Simulator {
                 StepIdx::Pipe => {
                     // Clock
                     let changed = self.state.clock();
+                    notify(&mut self.observers, |o| o.clock(&changed));
 
                     // Step result
                     let step_result = StepResult {
@@ -140,6 +153,7 @@ impl Simulator {
                 StepIdx::Semicolon => {
                     // Clock
                     let changed = self.state.clock();
+                    notify(&mut self.observers, |o| o.clock(&changed));
 
                     // Step result
                     let step_result = StepResult {
@@ -163,6 +177,11 @@ impl Simulator {
                             ))?,
                         None => cursor.statement_idx + 1,
                     };
+                    if let Some(goto_label) = &cursor.goto {
+                        notify(&mut self.observers, |o| {
+                            o.goto(cursor.statement_idx, goto_label, next_statement_idx)
+                        });
+                    }
                     self.cursor = Cursor::new(next_statement_idx);
 
                     // Finish cycle
@@ -187,6 +206,7 @@ impl Cursor {
             statement_idx,
             step_idx: StepIdx::Step(0),
             criteria_set: HashSet::new(),
+            criteria_uninitialized: HashSet::new(),
             goto: None,
             triggered_breakpoint: false,
         })
@@ -196,6 +216,13 @@ impl Cursor {
         matches!(self, Cursor::Live(..))
     }
 
+    pub fn statement_idx(&self) -> Option<usize> {
+        match self {
+            Cursor::Live(cursor) => Some(cursor.statement_idx),
+            Cursor::Terminated => None,
+        }
+    }
+
     pub fn is_at_statement_start(&self) -> bool {
         matches!(self, Cursor::Live(CursorLive { step_idx: StepIdx::Step(0), .. }))
     }
@@ -206,6 +233,7 @@ pub struct CursorLive {
     statement_idx: usize,
     step_idx: StepIdx,
     criteria_set: HashSet<CriterionId>,
+    criteria_uninitialized: HashSet<CriterionId>,
     goto: Option<Label>,
     triggered_breakpoint: bool,
 }
@@ -217,6 +245,54 @@ enum StepIdx {
     Semicolon,
 }
 
+fn notify(observers: &mut [(ObserverId, Box<dyn Observer>)], mut f: impl FnMut(&mut dyn Observer)) {
+    for (_, observer) in observers {
+        f(&mut **observer);
+    }
+}
+
+fn notify_step(
+    observers: &mut [(ObserverId, Box<dyn Observer>)],
+    state: &State,
+    step: &Step,
+    step_result: &StepResult,
+) {
+    if observers.is_empty() {
+        return;
+    }
+
+    let statement = step_result.statement;
+    notify(observers, |o| o.micro_step(statement, step, step_result.span));
+
+    for write in state.take_writes() {
+        match &write {
+            WriteEvent::Register(ident, range, value) => {
+                notify(observers, |o| o.register_write(ident, *range, value))
+            }
+            WriteEvent::Bus(ident, range, value) => {
+                notify(observers, |o| o.bus_write(ident, *range, value))
+            }
+            WriteEvent::RegisterArray(ident, index, value) => {
+                let index = usize::from_str_radix(&index.as_bin(false), 2).unwrap();
+                notify(observers, |o| o.register_array_write(ident, index, value))
+            }
+            WriteEvent::Memory(ident, address, value) => {
+                notify(observers, |o| o.memory_write(ident, address, value))
+            }
+        }
+    }
+
+    match step_result.kind {
+        StepResultKind::Condition { result, span } => {
+            notify(observers, |o| o.condition(statement, result, span))
+        }
+        StepResultKind::AssertError => {
+            notify(observers, |o| o.assert_error(statement, step_result.span))
+        }
+        _ => (),
+    }
+}
+
 fn criteria_match(criteria: &[Criterion], criteria_set: &HashSet<CriterionId>) -> bool {
     criteria.iter().all(|criterion| match criterion {
         Criterion::True(id) => criteria_set.contains(id),
@@ -228,10 +304,47 @@ fn exec_step(
     cursor: &mut CursorLive,
     state: &State,
     statement_idx: usize,
+    statement_span: Span,
     step: &Step,
+    uninitialized: &mut Vec<UninitializedDiagnostic>,
 ) -> Result<Option<StepResult>, Error> {
     if criteria_match(&step.criteria, &cursor.criteria_set) {
-        let kind = match step.operation.execute(state)? {
+        let execute_result = step.operation.execute(state)?;
+
+        // Collect uninitialized diagnostics
+        if state.track_uninitialized() {
+            let mut kinds = state.take_uninitialized();
+            if kinds.contains(&UninitializedKind::Condition) {
+                match &step.operation.kind {
+                    OperationKind::EvalCriterion(eval_criterion) => {
+                        cursor.criteria_uninitialized.insert(eval_criterion.criterion_id);
+                    }
+                    OperationKind::EvalCriterionGroup(group) => {
+                        cursor
+                            .criteria_uninitialized
+                            .extend(group.0.iter().map(|e| e.criterion_id));
+                    }
+                    _ => (),
+                }
+            }
+            if matches!(execute_result, ExecuteResult::Goto(_))
+                && step.criteria.iter().any(|criterion| match criterion {
+                    Criterion::True(id) | Criterion::False(id) => {
+                        cursor.criteria_uninitialized.contains(id)
+                    }
+                })
+            {
+                kinds.push(UninitializedKind::Goto);
+            }
+            uninitialized.extend(kinds.into_iter().map(|kind| UninitializedDiagnostic {
+                kind,
+                statement: statement_idx,
+                span: statement_span,
+                operation_span: step.span(),
+            }));
+        }
+
+        let kind = match execute_result {
             ExecuteResult::Void => StepResultKind::Void,
             ExecuteResult::Criterion(Criterion::True(id), cond_span) => {
                 cursor.criteria_set.insert(id);
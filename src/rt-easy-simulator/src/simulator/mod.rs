@@ -1,8 +1,14 @@
+#[cfg(feature = "evaluate")]
+mod impl_evaluate;
 mod impl_state_view;
 mod impl_step;
 
 use self::impl_step::Cursor;
-use crate::{state::State, Changed};
+use crate::{
+    observer::{Observer, ObserverId},
+    state::State,
+    Changed, UninitializedDiagnostic,
+};
 use rtprogram::{Ident, Label, Program, Signals, Span};
 use std::collections::{BTreeSet, HashSet};
 
@@ -15,6 +21,12 @@ pub struct Simulator {
     cursor: Cursor,
 
     breakpoints: BTreeSet<usize>,
+
+    track_uninitialized: bool,
+    uninitialized: Vec<UninitializedDiagnostic>,
+
+    observers: Vec<(ObserverId, Box<dyn Observer>)>,
+    next_observer_id: usize,
 }
 
 impl Simulator {
@@ -28,12 +40,23 @@ impl Simulator {
             cursor: Cursor::new(0),
 
             breakpoints: BTreeSet::new(),
+
+            track_uninitialized: false,
+            uninitialized: Vec::new(),
+
+            observers: Vec::new(),
+            next_observer_id: 0,
         }
     }
 
     pub fn reset(&mut self, reset_breakpoints: bool) {
         self.cycle_count = 0;
+        let devices = self.state.take_devices();
         self.state = State::init(&self.program);
+        self.state.restore_devices(devices);
+        self.state.set_track_uninitialized(self.track_uninitialized);
+        self.state.set_observed(!self.observers.is_empty());
+        self.uninitialized = Vec::new();
         self.buses_persist = HashSet::new();
 
         self.cursor = Cursor::new(0);
@@ -50,6 +73,51 @@ impl Simulator {
         !self.cursor.is_live()
     }
 
+    /// Index of the statement the simulator is at, `None` at the end of the program.
+    pub fn statement(&self) -> Option<usize> {
+        self.cursor.statement_idx().filter(|idx| *idx < self.program.statements().len())
+    }
+
+    /// Enables tracking of uninitialized bits.
+    ///
+    /// Registers, register arrays, memories and input buses start uninitialized. If tracking is
+    /// enabled, the simulator reports a diagnostic whenever an uninitialized value affects a
+    /// condition, a goto, a memory address or an output register (see
+    /// [`Simulator::uninitialized_diagnostics`]). Values written while tracking was disabled are
+    /// considered uninitialized, so tracking should be enabled before the first step.
+    pub fn set_track_uninitialized(&mut self, track_uninitialized: bool) {
+        self.track_uninitialized = track_uninitialized;
+        self.state.set_track_uninitialized(track_uninitialized);
+    }
+
+    pub fn track_uninitialized(&self) -> bool {
+        self.track_uninitialized
+    }
+
+    pub fn uninitialized_diagnostics(&self) -> &[UninitializedDiagnostic] {
+        &self.uninitialized
+    }
+
+    /// Registers an observer. Observers are kept across resets.
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) -> ObserverId {
+        let id = ObserverId(self.next_observer_id);
+        self.next_observer_id += 1;
+
+        self.observers.push((id, Box::new(observer)));
+        self.state.set_observed(true);
+
+        id
+    }
+
+    /// Removes an observer. Returns `false` if the observer does not exist.
+    pub fn remove_observer(&mut self, id: ObserverId) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        self.state.set_observed(!self.observers.is_empty());
+
+        self.observers.len() != len
+    }
+
     pub fn program(&self) -> &Program {
         &self.program
     }
@@ -10,13 +10,19 @@ use std::cell::RefCell;
 pub struct BusState {
     range: BitRange,
     value: RefCell<Value>,
+    known: RefCell<Value>,
     kind: BusKind,
 }
 
 impl BusState {
     pub fn init(range: Option<BitRange>, kind: BusKind) -> Self {
         let range = range.unwrap_or_default();
-        Self { range, value: RefCell::new(Value::zero(range.size())), kind }
+        Self {
+            range,
+            value: RefCell::new(Value::zero(range.size())),
+            known: RefCell::new(Value::zero(range.size())),
+            kind,
+        }
     }
 
     pub fn read(&self, idx: Option<BitRange>) -> Result<Value, Error> {
@@ -26,7 +32,7 @@ impl BusState {
         };
 
         let slice_idx = slice_idx(self.range, idx)?;
-        Ok(self.value.borrow()[slice_idx].to_owned())
+        Ok(self.value.borrow().slice(slice_idx).to_value())
     }
 
     pub fn write(&self, idx: Option<BitRange>, value: Value) -> Result<(), Error> {
@@ -41,7 +47,33 @@ impl BusState {
         };
 
         let slice_idx = slice_idx(self.range, idx)?;
-        target[slice_idx].write(&value);
+        target.slice_mut(slice_idx).write(&value);
+        Ok(())
+    }
+
+    pub fn read_known(&self, idx: Option<BitRange>) -> Result<Value, Error> {
+        let idx = match idx {
+            Some(idx) => idx,
+            None => return Ok(self.known.borrow().clone()),
+        };
+
+        let slice_idx = slice_idx(self.range, idx)?;
+        Ok(self.known.borrow().slice(slice_idx).to_value())
+    }
+
+    pub fn write_known(&self, idx: Option<BitRange>, known: Value) -> Result<(), Error> {
+        let mut target = self.known.borrow_mut();
+
+        let idx = match idx {
+            Some(idx) => idx,
+            None => {
+                target.write(&known);
+                return Ok(());
+            }
+        };
+
+        let slice_idx = slice_idx(self.range, idx)?;
+        target.slice_mut(slice_idx).write(&known);
         Ok(())
     }
 
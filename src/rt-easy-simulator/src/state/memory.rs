@@ -1,9 +1,13 @@
 use super::State;
-use crate::Error;
+use crate::{
+    device::Device,
+    evaluate_known::{fit_known, is_known},
+    Error,
+};
 use anyhow::anyhow;
-use memory_file::MemoryFile;
+use memory_file::{Format, MemoryFile, Options};
 use rtcore::value::Value;
-use rtprogram::MemoryRange;
+use rtprogram::{Ident, MemoryRange};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io;
@@ -15,14 +19,131 @@ const MEMORY_PAGE_SIZE: usize = 2usize.pow(MEMORY_PAGE_SIZE_EXP as u32);
 pub struct MemoryState {
     data: HashMap<Value, Value>,
     data_next: RefCell<Option<(Value, Value)>>,
+    known: HashMap<Value, Value>,
+    known_next: RefCell<Option<(Value, Value)>>,
     range: MemoryRange,
     ar_size: usize,
     dr_size: usize,
+    devices: Vec<AttachedDevice>,
+}
+
+#[derive(Debug)]
+pub struct AttachedDevice {
+    start: usize,
+    device: Box<dyn Device>,
 }
 
 impl MemoryState {
     pub fn init(range: MemoryRange, ar_size: usize, dr_size: usize) -> Self {
-        Self { data: HashMap::new(), data_next: RefCell::new(None), range, ar_size, dr_size }
+        Self {
+            data: HashMap::new(),
+            data_next: RefCell::new(None),
+            known: HashMap::new(),
+            known_next: RefCell::new(None),
+            range,
+            ar_size,
+            dr_size,
+            devices: Vec::new(),
+        }
+    }
+
+    pub fn attach_device(&mut self, start: Value, device: Box<dyn Device>) -> Result<(), Error> {
+        // Check range
+        if start.size() > self.ar_size {
+            return Err(anyhow!("address too big"));
+        }
+        let start = value_to_usize(&start).ok_or_else(|| anyhow!("address too big"))?;
+        let end = start.checked_add(device.size()).ok_or_else(|| anyhow!("device too big"))?;
+        if device.size() == 0 {
+            return Err(anyhow!("device must claim at least one address"));
+        }
+        if self.ar_size < usize::BITS as usize && end > 1 << self.ar_size {
+            return Err(anyhow!("device does not fit into memory"));
+        }
+
+        // Check overlap
+        for attached in &self.devices {
+            if start < attached.start + attached.device.size() && attached.start < end {
+                return Err(anyhow!(
+                    "device overlaps with device at address {}",
+                    value_from_usize(attached.start, self.ar_size).as_hex()
+                ));
+            }
+        }
+
+        self.devices.push(AttachedDevice { start, device });
+
+        Ok(())
+    }
+
+    pub fn device(&self, start: &Value) -> Option<&dyn Device> {
+        let start = value_to_usize(start)?;
+        self.devices.iter().find(|d| d.start == start).map(|d| &*d.device)
+    }
+
+    pub fn device_mut(&mut self, start: &Value) -> Option<&mut (dyn Device + 'static)> {
+        let start = value_to_usize(start)?;
+        match self.devices.iter_mut().find(|d| d.start == start) {
+            Some(d) => Some(&mut *d.device),
+            None => None,
+        }
+    }
+
+    pub fn take_devices(&mut self) -> Vec<AttachedDevice> {
+        std::mem::take(&mut self.devices)
+    }
+
+    /// Restores devices taken from a previous state and resets them.
+    pub fn restore_devices(&mut self, mut devices: Vec<AttachedDevice>) {
+        for attached in &mut devices {
+            attached.device.reset();
+        }
+        self.devices = devices;
+    }
+
+    fn device_at(&self, addr: &Value) -> Option<(&dyn Device, usize)> {
+        let addr = value_to_usize(addr)?;
+        self.devices.iter().find_map(|d| {
+            if addr >= d.start && addr - d.start < d.device.size() {
+                Some((&*d.device, addr - d.start))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn device_at_mut(&mut self, addr: &Value) -> Option<(&mut (dyn Device + 'static), usize)> {
+        let addr = value_to_usize(addr)?;
+        self.devices.iter_mut().find_map(|d| {
+            if addr >= d.start && addr - d.start < d.device.size() {
+                Some((&mut *d.device, addr - d.start))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn get(&self, addr: &Value) -> Value {
+        match self.device_at(addr) {
+            Some((device, offset)) => device.read(offset, self.dr_size).with_size(self.dr_size),
+            None => self.data.get(addr).cloned().unwrap_or_else(|| Value::zero(self.dr_size)),
+        }
+    }
+
+    fn get_known(&self, addr: &Value) -> Value {
+        match self.device_at(addr) {
+            Some(_) => Value::filled(self.dr_size),
+            None => self.known.get(addr).cloned().unwrap_or_else(|| Value::zero(self.dr_size)),
+        }
+    }
+
+    fn set(&mut self, addr: Value, value: Value) {
+        match self.device_at_mut(&addr) {
+            Some((device, offset)) => device.write(offset, &value),
+            None => {
+                self.data.insert(addr, value);
+            }
+        }
     }
 
     pub fn value_next(&self) -> Option<(Value, Value)> {
@@ -35,11 +156,22 @@ impl MemoryState {
         debug_assert_eq!(ar_value.size(), self.ar_size);
 
         // Read from memory
-        let value = self.data.get(&ar_value).cloned().unwrap_or_else(|| Value::zero(self.dr_size));
+        let value = self.get(&ar_value);
 
         // Write into data_register
         state.register(&self.range.data_register)?.write(None, value)?;
 
+        // Track uninitialized bits
+        if state.track_uninitialized() {
+            let ar_known = state.register(&self.range.address_register)?.read_known(None)?;
+            let known = if is_known(&ar_known) {
+                self.get_known(&ar_value)
+            } else {
+                Value::zero(self.dr_size)
+            };
+            state.register(&self.range.data_register)?.write_known(None, known)?;
+        }
+
         Ok(())
     }
 
@@ -52,6 +184,13 @@ impl MemoryState {
         let dr_value = state.register(&self.range.data_register)?.read(None)?;
         debug_assert_eq!(dr_value.size(), self.dr_size);
 
+        // Track uninitialized bits (a write to an unknown address is reported by the caller)
+        if state.track_uninitialized() {
+            let dr_known = state.register(&self.range.data_register)?.read_known(None)?;
+            *self.known_next.borrow_mut() =
+                Some((ar_value.clone(), fit_known(dr_known, self.dr_size)));
+        }
+
         // Write to memory
         *self.data_next.borrow_mut() = Some((ar_value, dr_value));
 
@@ -59,13 +198,23 @@ impl MemoryState {
     }
 
     pub fn clock(&mut self) -> Option<Value> {
-        match self.data_next.get_mut().take() {
+        if let Some((ar_value, known)) = self.known_next.get_mut().take() {
+            self.known.insert(ar_value, known);
+        }
+
+        let changed = match self.data_next.get_mut().take() {
             Some((ar_value, dr_value)) => {
-                self.data.insert(ar_value.clone(), dr_value);
+                self.set(ar_value.clone(), dr_value);
                 Some(ar_value)
             }
             None => None,
+        };
+
+        for attached in &mut self.devices {
+            attached.device.clock();
         }
+
+        changed
     }
 
     pub fn write_at(&mut self, addr: Value, value: Value) -> Result<(), Error> {
@@ -85,7 +234,8 @@ impl MemoryState {
         }
 
         // Insert data
-        self.data.insert(addr, value);
+        self.known.insert(addr.clone(), Value::filled(self.dr_size));
+        self.set(addr, value);
 
         Ok(())
     }
@@ -146,7 +296,7 @@ impl MemoryState {
         let mut result = Vec::new();
         for _ in 0..MEMORY_PAGE_SIZE {
             let addr_next = &addr + Value::one(1);
-            let value = self.data.get(&addr).cloned().unwrap_or_else(|| Value::zero(self.dr_size));
+            let value = self.get(&addr);
             result.push((addr, value));
             addr = addr_next;
 
@@ -158,51 +308,102 @@ impl MemoryState {
         result
     }
 
-    pub fn save<W>(&self, mut writer: W) -> Result<(), Error>
+    /// Snapshot of the memory content.
+    pub fn memory_file(&self) -> MemoryFile {
+        // Addresses and values always have the sizes of the memory
+        MemoryFile::new(self.ar_size, self.dr_size, self.data.clone()).unwrap()
+    }
+
+    pub fn save<W>(&self, writer: W) -> Result<(), Error>
     where
         W: io::Write,
     {
-        let mem = MemoryFile::new(self.ar_size, self.dr_size, self.data.clone())
-            .map_err(|()| anyhow!("invalid memory"))?;
-        writer
-            .write_all(mem.to_string().as_bytes())
-            .map_err(|e| anyhow!("failed to save memory: {}", e))
+        self.save_as(Format::RtEasy, &Default::default(), writer)
     }
 
-    pub fn load_from_save<R>(&mut self, mut reader: R) -> Result<(), Error>
+    pub fn save_as<W>(&self, format: Format, options: &Options, mut writer: W) -> Result<(), Error>
+    where
+        W: io::Write,
+    {
+        let bytes = self
+            .memory_file()
+            .export(format, options)
+            .map_err(|e| anyhow!("memory can not be saved as {}: {}", format, e))?;
+        writer.write_all(&bytes).map_err(|e| anyhow!("failed to save memory: {}", e))
+    }
+
+    pub fn load_from_save<R>(&mut self, reader: R) -> Result<(), Error>
+    where
+        R: io::Read,
+    {
+        self.load_from_save_as(Format::RtEasy, &Default::default(), reader)
+    }
+
+    pub fn load_from_save_as<R>(
+        &mut self,
+        format: Format,
+        options: &Options,
+        mut reader: R,
+    ) -> Result<(), Error>
     where
         R: io::Read,
     {
         // Read
-        let mut source = String::new();
-        reader.read_to_string(&mut source).map_err(|_| anyhow!("failed to read memory file"))?;
+        let mut source = Vec::new();
+        reader.read_to_end(&mut source).map_err(|_| anyhow!("failed to read memory file"))?;
 
         // Parse file
-        let mem = match MemoryFile::parse(&source) {
-            Ok(mem) => mem,
-            Err(()) => {
-                // Try to parse as deprecated
-                parse_deprecated(&source).map_err(|()| anyhow!("invalid memory file"))?
+        let mem = match format {
+            Format::RtEasy => {
+                let source =
+                    String::from_utf8(source).map_err(|_| anyhow!("failed to read memory file"))?;
+                let mem = match MemoryFile::parse(&source) {
+                    Ok(mem) => mem,
+                    Err(e) => {
+                        // Try to parse as deprecated
+                        parse_deprecated(&source)
+                            .map_err(|()| Error::new(e).context("invalid memory file"))?
+                    }
+                };
+
+                // Check
+                if mem.ar_size() != self.ar_size || mem.dr_size() != self.dr_size {
+                    return Err(anyhow!("invalid memory size"));
+                }
+
+                mem
             }
+            _ => MemoryFile::import(format, &source, self.ar_size, self.dr_size, options)
+                .map_err(|e| Error::new(e).context(format!("invalid {} memory file", format)))?,
         };
 
-        // Check
-        if mem.ar_size() != self.ar_size || mem.dr_size() != self.dr_size {
-            return Err(anyhow!("invalid memory size"));
-        }
-
         // Load data
         self.data = mem.into_data();
+        self.known =
+            self.data.keys().map(|addr| (addr.clone(), Value::filled(self.dr_size))).collect();
         *self.data_next.get_mut() = None;
+        *self.known_next.get_mut() = None;
 
         Ok(())
     }
 
+    pub fn address_register(&self) -> &Ident {
+        &self.range.address_register
+    }
+
     pub fn dr_size(&self) -> usize {
         self.dr_size
     }
 }
 
+fn value_to_usize(value: &Value) -> Option<usize> {
+    usize::from_str_radix(&value.as_bin(false), 2).ok()
+}
+
+fn value_from_usize(value: usize, size: usize) -> Value {
+    Value::parse_bin(&format!("{:b}", value)).unwrap().with_size(size)
+}
+
 // impl fmt::Display for MemoryState {
 //     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 //         let mut addresses = self.data.keys().collect::<Vec<_>>();
@@ -2,18 +2,22 @@ mod bus;
 mod memory;
 mod register;
 mod register_array;
-mod util;
+pub mod util;
 
 use self::{
-    bus::BusState, memory::MemoryState, register::RegisterState, register_array::RegisterArrayState,
+    bus::BusState,
+    memory::{AttachedDevice, MemoryState},
+    register::RegisterState,
+    register_array::RegisterArrayState,
 };
-use crate::{Changed, Result};
+use crate::{Changed, Result, UninitializedKind};
 use anyhow::anyhow;
 use rtcore::{
-    common::{BusKind, RegisterKind},
+    common::{BitRange, BusKind, RegisterKind},
     value::Value,
 };
 use rtprogram::{Declaration, Ident, Program};
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
@@ -22,6 +26,21 @@ pub struct State {
     buses: HashMap<Ident, BusState>,
     register_arrays: HashMap<Ident, RegisterArrayState>,
     memories: HashMap<Ident, MemoryState>,
+
+    track_uninitialized: bool,
+    uninitialized: RefCell<Vec<UninitializedKind>>,
+
+    observed: bool,
+    writes: RefCell<Vec<WriteEvent>>,
+}
+
+/// A write scheduled by an operation, collected for observers.
+#[derive(Debug)]
+pub enum WriteEvent {
+    Register(Ident, Option<BitRange>, Value),
+    Bus(Ident, Option<BitRange>, Value),
+    RegisterArray(Ident, Value, Value),
+    Memory(Ident, Value, Value),
 }
 
 impl State {
@@ -36,8 +55,10 @@ impl State {
             match declaration {
                 Declaration::Register(declare_register) => {
                     for reg in &declare_register.registers {
-                        registers
-                            .insert(reg.ident.clone(), RegisterState::init(reg.range, reg.kind));
+                        registers.insert(
+                            reg.ident.clone(),
+                            RegisterState::init(reg.range, reg.kind, reg.init.as_ref()),
+                        );
                     }
                 }
                 Declaration::Bus(declare_bus) => {
@@ -54,7 +75,7 @@ impl State {
                         );
                     }
                 }
-                Declaration::Memory(_) => (),
+                Declaration::Memory(_) | Declaration::Constant(_) => (),
             }
         }
 
@@ -73,7 +94,17 @@ impl State {
             }
         }
 
-        Self { registers, buses, memories, register_arrays }
+        Self {
+            registers,
+            buses,
+            memories,
+            register_arrays,
+            track_uninitialized: false,
+            uninitialized: RefCell::new(Vec::new()),
+
+            observed: false,
+            writes: RefCell::new(Vec::new()),
+        }
     }
 
     pub fn clock(&mut self) -> Changed {
@@ -101,14 +132,57 @@ impl State {
         changed
     }
 
+    /// Takes all attached devices (e.g. to keep them across a reset).
+    pub fn take_devices(&mut self) -> HashMap<Ident, Vec<AttachedDevice>> {
+        self.memories.iter_mut().map(|(name, state)| (name.clone(), state.take_devices())).collect()
+    }
+
+    /// Restores devices taken by [`State::take_devices`] and resets them.
+    pub fn restore_devices(&mut self, devices: HashMap<Ident, Vec<AttachedDevice>>) {
+        for (name, devices) in devices {
+            if let Some(state) = self.memories.get_mut(&name) {
+                state.restore_devices(devices);
+            }
+        }
+    }
+
     pub fn clear_intern_buses(&self, buses_persist: &HashSet<Ident>) {
         for (ident, bus) in &self.buses {
             if !buses_persist.contains(ident) && bus.kind() == BusKind::Intern {
                 bus.write(None, Value::zero(bus.range().size())).unwrap();
+                if self.track_uninitialized {
+                    bus.write_known(None, Value::filled(bus.range().size())).unwrap();
+                }
             }
         }
     }
 
+    pub fn track_uninitialized(&self) -> bool {
+        self.track_uninitialized
+    }
+    pub fn set_track_uninitialized(&mut self, track_uninitialized: bool) {
+        self.track_uninitialized = track_uninitialized;
+    }
+    pub fn report_uninitialized(&self, kind: UninitializedKind) {
+        self.uninitialized.borrow_mut().push(kind);
+    }
+    pub fn take_uninitialized(&self) -> Vec<UninitializedKind> {
+        std::mem::take(&mut *self.uninitialized.borrow_mut())
+    }
+
+    pub fn observed(&self) -> bool {
+        self.observed
+    }
+    pub fn set_observed(&mut self, observed: bool) {
+        self.observed = observed;
+    }
+    pub fn report_write(&self, event: WriteEvent) {
+        self.writes.borrow_mut().push(event);
+    }
+    pub fn take_writes(&self) -> Vec<WriteEvent> {
+        std::mem::take(&mut *self.writes.borrow_mut())
+    }
+
     pub fn register_names(&self, kind: RegisterKind) -> impl Iterator<Item = &Ident> {
         self.registers
             .iter()
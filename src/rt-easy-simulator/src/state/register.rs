@@ -11,13 +11,28 @@ pub struct RegisterState {
     range: BitRange,
     value: Value,
     value_next: RefCell<Option<Value>>,
+    known: Value,
+    known_next: RefCell<Option<Value>>,
     kind: RegisterKind,
 }
 
 impl RegisterState {
-    pub fn init(range: Option<BitRange>, kind: RegisterKind) -> Self {
+    pub fn init(range: Option<BitRange>, kind: RegisterKind, init: Option<&Value>) -> Self {
         let range = range.unwrap_or_default();
-        Self { range, value: Value::zero(range.size()), value_next: RefCell::new(None), kind }
+        let (value, known) = match init {
+            // A register with an initial value is fully initialized
+            Some(init) => (init.clone().with_size(range.size()), Value::filled(range.size())),
+            None => (Value::zero(range.size()), Value::zero(range.size())),
+        };
+
+        Self {
+            range,
+            value,
+            value_next: RefCell::new(None),
+            known,
+            known_next: RefCell::new(None),
+            kind,
+        }
     }
 
     pub fn value_next(&self) -> Option<Value> {
@@ -31,7 +46,7 @@ impl RegisterState {
         };
 
         let slice_idx = slice_idx(self.range, idx)?;
-        Ok(self.value[slice_idx].to_owned())
+        Ok(self.value.slice(slice_idx).to_value())
     }
 
     pub fn write(&self, idx: Option<BitRange>, value: Value) -> Result<(), Error> {
@@ -47,11 +62,42 @@ impl RegisterState {
         };
 
         let slice_idx = slice_idx(self.range, idx)?;
-        target[slice_idx].write(&value);
+        target.slice_mut(slice_idx).write(&value);
+        Ok(())
+    }
+
+    pub fn read_known(&self, idx: Option<BitRange>) -> Result<Value, Error> {
+        let idx = match idx {
+            Some(idx) => idx,
+            None => return Ok(self.known.clone()),
+        };
+
+        let slice_idx = slice_idx(self.range, idx)?;
+        Ok(self.known.slice(slice_idx).to_value())
+    }
+
+    pub fn write_known(&self, idx: Option<BitRange>, known: Value) -> Result<(), Error> {
+        let mut target = self.known_next.borrow_mut();
+        let target = target.get_or_insert_with(|| self.known.clone());
+
+        let idx = match idx {
+            Some(idx) => idx,
+            None => {
+                target.write(&known);
+                return Ok(());
+            }
+        };
+
+        let slice_idx = slice_idx(self.range, idx)?;
+        target.slice_mut(slice_idx).write(&known);
         Ok(())
     }
 
     pub fn clock(&mut self) -> bool {
+        if let Some(known_next) = self.known_next.get_mut().take() {
+            self.known = known_next;
+        }
+
         match self.value_next.get_mut().take() {
             Some(value_next) => {
                 self.value = value_next;
@@ -10,13 +10,22 @@ const REGISTER_ARRAY_PAGE_SIZE: usize = 32;
 pub struct RegisterArrayState {
     data: HashMap<Value, Value>,
     data_next: RefCell<Option<(Value, Value)>>,
+    known: HashMap<Value, Value>,
+    known_next: RefCell<Option<(Value, Value)>>,
     len: usize,
     data_size: usize,
 }
 
 impl RegisterArrayState {
     pub fn init(len: usize, data_size: usize) -> Self {
-        Self { data: HashMap::new(), data_next: RefCell::new(None), len, data_size }
+        Self {
+            data: HashMap::new(),
+            data_next: RefCell::new(None),
+            known: HashMap::new(),
+            known_next: RefCell::new(None),
+            len,
+            data_size,
+        }
     }
 
     pub fn value_next(&self) -> Option<(usize, Value)> {
@@ -47,7 +56,31 @@ impl RegisterArrayState {
         Ok(())
     }
 
+    pub fn read_known(&self, idx: Value) -> Result<Value, Error> {
+        // Check idx
+        if idx.size() > self.index_size() {
+            return Err(anyhow!("index too big"));
+        }
+
+        let known = self.known.get(&idx).cloned().unwrap_or_else(|| Value::zero(self.data_size));
+        Ok(known)
+    }
+
+    pub fn write_known(&self, idx: Value, known: Value) -> Result<(), Error> {
+        // Check idx
+        if idx.size() > self.index_size() {
+            return Err(anyhow!("index too big"));
+        }
+
+        *self.known_next.borrow_mut() = Some((idx, known.with_size(self.data_size)));
+        Ok(())
+    }
+
     pub fn clock(&mut self) -> Option<Value> {
+        if let Some((idx, known)) = self.known_next.get_mut().take() {
+            self.known.insert(idx, known);
+        }
+
         match self.data_next.get_mut().take() {
             Some((idx, value)) => {
                 self.data.insert(idx.clone(), value);
@@ -0,0 +1,44 @@
+use rtprogram::{Ident, Span};
+use std::fmt;
+
+/// Reported when an uninitialized value affects the control flow or the outputs of a program.
+///
+/// Only reported if uninitialized tracking is enabled, see
+/// [`Simulator::set_track_uninitialized`](crate::Simulator::set_track_uninitialized).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UninitializedDiagnostic {
+    pub kind: UninitializedKind,
+    /// Index of the statement
+    pub statement: usize,
+    /// Span of the statement
+    pub span: Span,
+    /// Span of the operation
+    pub operation_span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UninitializedKind {
+    /// A condition (`if`, `switch` or `assert`) depends on an uninitialized value
+    Condition,
+    /// A goto depends on a condition with an uninitialized value
+    Goto,
+    /// A memory is accessed with an uninitialized address
+    MemoryAddress(Ident),
+    /// An uninitialized value is written to an output register
+    OutputRegister(Ident),
+}
+
+impl fmt::Display for UninitializedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Condition => write!(f, "condition depends on an uninitialized value"),
+            Self::Goto => write!(f, "goto depends on an uninitialized value"),
+            Self::MemoryAddress(memory) => {
+                write!(f, "memory `{}` is accessed with an uninitialized address", memory.0)
+            }
+            Self::OutputRegister(register) => {
+                write!(f, "uninitialized value is written to output `{}`", register.0)
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+mod util;
+
+use rt_easy_simulator::Simulator;
+use rtcore::value::Value;
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+declare constant WIDTH = 8, MSB = WIDTH - 1, INIT = 0x0F, LEN = 4
+declare register A(MSB:0), B(MSB:0)
+declare register array ARR(MSB:0)[LEN]
+
+A <- INIT + WIDTH, B <- MSB;
+ARR[3] <- A - INIT;
+B <- ARR[3];
+"#;
+
+#[test]
+fn constant() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let ident = |name: &str| Ident(name.to_string());
+    let value = |simulator: &Simulator, name: &str| simulator.register_value(&ident(name)).unwrap();
+
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "A"), Value::parse_dec("23").unwrap().with_size(8));
+    assert_eq!(value(&simulator, "B"), Value::parse_dec("7").unwrap().with_size(8));
+
+    simulator.step(false).unwrap();
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "B"), Value::parse_dec("8").unwrap().with_size(8));
+}
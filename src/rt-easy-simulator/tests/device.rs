@@ -0,0 +1,78 @@
+mod util;
+
+use rt_easy_simulator::{
+    device::{Console, Rom, TickTimer},
+    Simulator,
+};
+use rtcore::value::Value;
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+    declare register AR(7:0), DR(7:0), X(7:0), T(7:0)
+    declare memory MEM(AR,DR)
+
+    AR <- 0xF0, DR <- 72;
+    write MEM;
+    DR <- 105;
+    write MEM;
+    AR <- 0x10;
+    read MEM;
+    X <- DR, DR <- 0xFF;
+    write MEM;
+    read MEM;
+    AR <- 0xF1;
+    read MEM;
+    T <- DR;
+"#;
+
+fn mem() -> Ident {
+    Ident("MEM".to_string())
+}
+
+fn hex(s: &str) -> Value {
+    Value::parse_hex(s).unwrap()
+}
+
+fn setup() -> Simulator {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    simulator.attach_device(&mem(), hex("F0"), Console::new()).unwrap();
+    simulator.attach_device(&mem(), hex("F1"), TickTimer::new()).unwrap();
+    simulator.attach_device(&mem(), hex("10"), Rom::new(vec![hex("2A"), hex("2B")])).unwrap();
+    simulator
+}
+
+#[test]
+fn devices() {
+    let mut simulator = setup();
+    while !simulator.is_finished() {
+        simulator.step(false).unwrap();
+    }
+
+    // Console
+    let console = simulator.device::<Console>(&mem(), &hex("F0")).unwrap();
+    assert_eq!(console.output_string(), "Hi");
+
+    // ROM (write is ignored)
+    assert_eq!(simulator.register_value(&Ident("X".to_string())).unwrap(), hex("2A"));
+    let page = simulator.memory_page(&mem(), Value::parse_dec("1").unwrap()).unwrap();
+    assert_eq!(page[0x10], (hex("10"), hex("2A")));
+
+    // Timer (one clock edge per statement, read in the 11th statement)
+    assert_eq!(simulator.register_value(&Ident("T".to_string())).unwrap(), hex("A"));
+    assert_eq!(simulator.device::<TickTimer>(&mem(), &hex("F1")).unwrap().ticks(), 12);
+
+    // Reset keeps devices, but resets them
+    simulator.reset(false);
+    let console = simulator.device::<Console>(&mem(), &hex("F0")).unwrap();
+    assert_eq!(console.output_string(), "");
+    assert!(simulator.device::<TickTimer>(&mem(), &hex("F0")).is_err());
+}
+
+#[test]
+fn overlap() {
+    let mut simulator = setup();
+    assert!(simulator.attach_device(&mem(), hex("11"), Console::new()).is_err());
+    assert!(simulator.attach_device(&mem(), hex("12"), Console::new()).is_ok());
+    assert!(simulator.attach_device(&mem(), hex("FF"), Rom::new(vec![hex("0"); 2])).is_err());
+    assert!(simulator.attach_device(&mem(), hex("100"), Console::new()).is_err());
+}
@@ -0,0 +1,85 @@
+mod util;
+
+use rt_easy_simulator::Simulator;
+use rtcore::value::Value;
+
+const SOURCE: &str = r#"
+    declare register A(7:0), B(3:0), AR(1:0), DR(7:0)
+    declare bus C(7:0)
+    declare register array ARR(7:0)[4]
+    declare memory MEM(AR, DR)
+
+    A <- 0xF3, B <- 5, ARR[2] <- 42, C <- 7;
+    ARR[1] <- A(3:0), DR <- C;
+"#;
+
+fn dec(s: &str) -> Value {
+    Value::parse_dec(s).unwrap()
+}
+
+#[test]
+fn evaluate() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    simulator.step(false).unwrap();
+
+    assert_eq!(simulator.evaluate("A").unwrap(), dec("243"));
+    assert_eq!(simulator.evaluate("A(3:0)").unwrap(), dec("3"));
+    assert_eq!(simulator.evaluate("A(7:4) + B").unwrap(), dec("4")); // Overflow
+    assert_eq!(simulator.evaluate("ARR[2]").unwrap(), dec("42"));
+    assert_eq!(simulator.evaluate("ARR[B(1:0) - 3] = 42").unwrap(), dec("1"));
+    assert_eq!(simulator.evaluate("B.A(0)").unwrap(), dec("11"));
+    assert_eq!(simulator.evaluate("C").unwrap(), dec("7"));
+
+    // Size of the expression
+    assert_eq!(simulator.evaluate("B + 1").unwrap().size(), 4);
+    assert_eq!(simulator.evaluate("A = 243").unwrap().size(), 1);
+
+    // Reads the current state
+    simulator.step(false).unwrap();
+    assert_eq!(simulator.evaluate("ARR[1]").unwrap(), dec("3"));
+    assert_eq!(simulator.evaluate("DR").unwrap(), dec("0"));
+}
+
+#[test]
+fn evaluate_constant() {
+    const SOURCE: &str = r#"
+        declare constant WIDTH = 8, MSB = WIDTH - 1, MASK = 0x0F
+        declare register A(MSB:0)
+
+        A <- 0xAB;
+    "#;
+
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    simulator.step(false).unwrap();
+
+    assert_eq!(simulator.evaluate("WIDTH").unwrap(), dec("8"));
+    assert_eq!(simulator.evaluate("A(MSB:4) + MSB").unwrap(), dec("1")); // Overflow
+    assert_eq!(simulator.evaluate("A and MASK").unwrap(), dec("11"));
+}
+
+#[test]
+fn evaluate_err() {
+    let simulator = Simulator::init(util::compile(SOURCE));
+
+    assert!(simulator.evaluate("A +").is_err());
+    assert!(simulator.evaluate("X").is_err());
+    assert!(simulator.evaluate("A(8:0)").is_err());
+    assert!(simulator.evaluate("MEM").is_err());
+}
+
+#[test]
+fn check_expression() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+
+    let check = |expr: &str| simulator.check_expression(parser::parse_expression(expr).unwrap());
+    assert_eq!(check("A(7:4) + B").unwrap(), 4);
+    assert_eq!(check("ARR[2] = 42").unwrap(), 1);
+    assert!(check("X").is_err());
+    assert!(check("A(8:0)").is_err());
+
+    assert_eq!(simulator.statement(), Some(0));
+    simulator.step(false).unwrap();
+    assert_eq!(simulator.statement(), Some(1));
+    simulator.step(false).unwrap();
+    assert_eq!(simulator.statement(), None);
+}
@@ -0,0 +1,412 @@
+mod util;
+
+use rt_easy_simulator::{
+    fast::{FastSimulator, FastStepResultKind},
+    Simulator, StepResultKind,
+};
+use rtcore::{
+    common::{BusKind, RegisterKind},
+    value::{SignedValue, Value},
+};
+use rtprogram::Ident;
+
+/// Runs `source` in both simulators and compares the whole state after every statement. Before
+/// every statement, all `inputs` are written.
+fn assert_equivalent(source: &str, inputs: &[(&str, &str)]) {
+    let mut simulator = Simulator::init(util::compile(source));
+    let mut fast = FastSimulator::init(&util::compile(source)).unwrap();
+
+    for _ in 0..10_000 {
+        for (name, value) in inputs {
+            let name = Ident(name.to_string());
+            let value = SignedValue::parse_dec(value).unwrap();
+            simulator.write_bus(&name, value.clone()).unwrap();
+            fast.write_bus(&name, value).unwrap();
+        }
+
+        let result = simulator.step(false).unwrap();
+        let fast_result = fast.step();
+        match (&result, &fast_result) {
+            (Some(result), Some(fast_result)) => {
+                assert_eq!(result.statement, fast_result.statement);
+                match result.kind {
+                    StepResultKind::StatementEnd(_) => {
+                        assert_eq!(fast_result.kind, FastStepResultKind::StatementEnd)
+                    }
+                    StepResultKind::AssertError => {
+                        assert_eq!(fast_result.kind, FastStepResultKind::AssertError);
+                        assert_eq!(result.span, fast_result.span);
+                    }
+                    _ => panic!("unexpected step result {:?}", result),
+                }
+            }
+            (None, None) => (),
+            _ => panic!("step results differ: {:?} vs {:?}", result, fast_result),
+        }
+
+        assert_state_eq(&simulator, &fast);
+        assert_eq!(simulator.cycle_count(), fast.cycle_count());
+        assert_eq!(simulator.is_finished(), fast.is_finished());
+
+        if simulator.is_finished() {
+            return;
+        }
+    }
+
+    panic!("program did not terminate");
+}
+
+fn assert_state_eq(simulator: &Simulator, fast: &FastSimulator) {
+    for kind in [RegisterKind::Intern, RegisterKind::Output] {
+        for name in simulator.registers(kind) {
+            assert_eq!(
+                simulator.register_value(name).unwrap(),
+                fast.register_value(name).unwrap(),
+                "register {}",
+                name.0
+            );
+        }
+    }
+    for kind in [BusKind::Intern, BusKind::Input] {
+        for name in simulator.buses(kind) {
+            assert_eq!(
+                simulator.bus_value(name).unwrap(),
+                fast.bus_value(name).unwrap(),
+                "bus {}",
+                name.0
+            );
+        }
+    }
+    for name in simulator.register_arrays() {
+        for page_nr in 1..=simulator.register_array_page_count(name).unwrap() {
+            for (idx, value) in simulator.register_array_page(name, page_nr).unwrap() {
+                assert_eq!(
+                    value,
+                    fast.register_array_value(name, idx).unwrap(),
+                    "register array {}[{}]",
+                    name.0,
+                    idx
+                );
+            }
+        }
+    }
+    for name in simulator.memories() {
+        let mut page_nr = Some(Value::parse_dec("1").unwrap());
+        while let Some(nr) = page_nr {
+            for (address, value) in simulator.memory_page(name, nr.clone()).unwrap() {
+                assert_eq!(
+                    value,
+                    fast.memory_value(name, &address).unwrap(),
+                    "memory {}[{}]",
+                    name.0,
+                    address.as_dec()
+                );
+            }
+            page_nr = simulator.memory_page_next(name, nr).unwrap();
+        }
+    }
+}
+
+#[test]
+fn mult() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), FACTOR(7:0), RES(7:0)
+        declare bus INBUS(7:0), OUTBUS(7:0)
+
+        BEGIN:
+            A <- INBUS, RES <- 0;
+            FACTOR <- INBUS;
+        LOOP:
+            if FACTOR <> 0 then
+                RES <- RES + A, FACTOR <- FACTOR - 1, goto LOOP
+            else
+                OUTBUS <- RES
+            fi;
+    "#;
+
+    assert_equivalent(SOURCE, &[("INBUS", "13")]);
+}
+
+#[test]
+fn mul() {
+    const SOURCE: &str = r#"
+        declare input IN(7:0)
+        declare output OUT(7:0)
+        declare register A(7:0), Q(7:0), M(7:0), COUNT(2:0)
+
+        INIT:
+            A <- 0, COUNT <- 0,
+            M <- IN;
+            Q <- IN;
+
+        ADD:
+            if Q(0) = 1 then
+                A(7:0) <- A(6:0) + M(6:0)
+            else
+                A(7:0) <- A(6:0) + 0
+            fi;
+
+        RSHIFT_AND_TEST:
+            A(7) <- 0, A(6:0).Q <- A.Q(7:1),
+            if COUNT <> 6 then
+                COUNT <- COUNT + 1, goto ADD
+            fi;
+
+        SIGN:
+            A(7) <- M(7) xor Q(0), A(6:0).Q <- A.Q(7:1);
+        OUTPUT:
+            OUT <- Q;
+            OUT <- A;
+    "#;
+
+    assert_equivalent(SOURCE, &[("IN", "-7")]);
+}
+
+#[test]
+fn switch_case() {
+    const SOURCE: &str = r#"
+        declare register X(7:0)
+
+        SW:
+        switch X {
+            case 1 = 0:
+                X(0) <- 1, goto SW
+            case 1 <> 0:
+                X(1) <- 1, goto SW
+            case (1 < 0) + (1 <= 3) + (2 > 0) + ("1" >= 0x1):
+                X(2) <- 1, goto SW
+            case 8 - 1:
+                X(3) <- 1, goto SW
+            case "11"."11":
+                X(4) <- 1, goto SW
+            case (((1 and 1) or 0b11) xor 0b11100):
+                X(5) <- 1, goto SW
+            default:
+                X(7:6) <- "11"
+        };
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn operators() {
+    const SOURCE: &str = r#"
+        declare register A(3:0), B(3:0), C(7:0), R(0:7), W(63:0)
+        declare bus X(7:0), Y(7:0)
+
+        A <- 0b1001, B <- 0b0101;
+        C <- sxt A.A(3).A(3).A(1);
+        A <- 8, B <- -1;
+        C <- A + sxt B;
+        A <- 0b1111;
+        C <- sxt A, R(2:5) <- A;
+        C <- not A, R(0) <- 1;
+        C <- not not A - 0b11111, W <- -1;
+        A(0).A(3:2).A(1) <- 0b1110, W <- W + 2;
+        A(3:0) <- sxt 0b01, C <- 0b1110 nor 0b0101;
+        A <- sxt 0b111 + 0b1, C <- 0b1110 nand R;
+        X.Y(3:0) <- 0xABC, C <- R(1:6);
+        C <- X - Y, W(63:32) <- X.Y.X.Y;
+        C <- -C + (C <= A) + (C > A), W(31:0) <- sxt C;
+        C <- A * B, W(31:0) <- sxt C * sxt X.Y + W(15:0) * 3;
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn buses() {
+    const SOURCE: &str = r#"
+        declare register X(7:0)
+        declare bus B(7:0), C(7:0)
+        declare input IN(7:0)
+
+        X <- (1 > 0) + (X = X);
+        X(3:0) <- (not 0) xor "1010";
+        X <- sxt B(0), B <- 1;
+        X <- B + C, B <- IN, C <- B;
+        X <- IN;
+    "#;
+
+    assert_equivalent(SOURCE, &[("IN", "99")]);
+}
+
+#[test]
+fn pipe() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), B(7:0)
+
+        A <- 1, B <- 2 | if A = 1 then goto ADD fi;
+        A <- 99;
+        ADD:
+            A <- A + B | if A = 5 then goto END else goto ADD fi;
+        END:
+            B <- A;
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn register_array() {
+    const SOURCE: &str = r#"
+        declare register A(7:0), IDX(5:0)
+        declare register array ARR(7:0)[64]
+
+        ARR[0] <- 12 + ARR[1];
+
+        IDX <- 1;
+        ARR[IDX] <- ARR[IDX - 1] + 3;
+
+        ARR["111111"].A(7) <- 0b101;
+
+        LOOP:
+            ARR[IDX] <- IDX + ARR[IDX - 1], IDX <- IDX + 1,
+            if IDX <> 40 then goto LOOP fi;
+        A <- ARR[39] + ARR[63];
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn memory() {
+    const SOURCE: &str = r#"
+        declare register AR(5:0), DR(7:0), SUM(7:0)
+        declare memory MEM(AR,DR)
+
+        AR <- 0, DR <- 42;
+        write MEM;
+        AR <- 2, DR <- 7;
+        write MEM;
+        AR <- 0, DR <- 21;
+        write MEM;
+
+        FILL:
+            DR <- AR.AR(1:0), write MEM, AR <- AR + 1,
+            if AR <> 63 then goto FILL fi;
+        SUM_UP:
+            AR <- AR - 1, read MEM;
+            SUM <- SUM + DR, if AR <> 0 then goto SUM_UP fi;
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn assert() {
+    const SOURCE: &str = r#"
+        declare register X(7:0)
+        declare bus B(7:0), C(7:0)
+
+        X <- 12,
+        assert 1,
+        assert X < 42,
+        assert B > 17,
+        B <- 18,
+        assert C = "11111111",
+        C <- -1,
+        if 1 then assert X = X fi;
+
+        if X > 2 then assert 2 = 5 fi;
+        X <- 0;
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn state_access() {
+    const SOURCE: &str = r#"
+        declare register AR(3:0), DR(7:0), X(7:0)
+        declare register array ARR(7:0)[4]
+        declare memory MEM(AR,DR)
+
+        read MEM, X <- ARR[1];
+    "#;
+
+    let mut fast = FastSimulator::init(&util::compile(SOURCE)).unwrap();
+    let ident = |name: &str| Ident(name.to_string());
+    let dec = |value: &str| Value::parse_dec(value).unwrap();
+
+    fast.write_register(&ident("AR"), dec("3")).unwrap();
+    fast.write_memory(&ident("MEM"), dec("3"), SignedValue::parse_dec("-2").unwrap()).unwrap();
+    fast.write_register_array(&ident("ARR"), 1, dec("9")).unwrap();
+    assert!(fast.write_register(&ident("X"), dec("256")).is_err());
+    assert!(fast.register_value(&ident("MEM")).is_err());
+    assert!(fast.register_array_value(&ident("ARR"), 4).is_err());
+
+    fast.step();
+    assert_eq!(fast.register_value(&ident("DR")).unwrap(), dec("254"));
+    assert_eq!(fast.register_value(&ident("X")).unwrap(), dec("9"));
+    assert_eq!(fast.cycle_count(), 1);
+
+    fast.reset();
+    assert_eq!(fast.register_value(&ident("DR")).unwrap(), dec("0"));
+    assert_eq!(fast.memory_value(&ident("MEM"), &dec("3")).unwrap(), dec("0"));
+    assert_eq!(fast.cycle_count(), 0);
+}
+
+#[test]
+fn wide() {
+    const SOURCE: &str = r#"
+        declare register A(31:0), B(31:0), C(7:0), W(99:0), V(127:0), AR(1:0), IDX(1:0)
+        declare bus X(79:0), Y(7:0)
+        declare register array ARR(71:0)[4]
+        declare memory MEM(AR, W)
+
+        C <- A.B.C = 0, W <- -1, X <- 0x10000000000000001;
+        V <- W + 1, W(7:0) <- 3, A <- X(71:40);
+        V <- V(63:0) * W(63:0) - sxt C, C <- W(99:92) + V(127:120), Y <- X(7:0), write MEM;
+        W <- not W, ARR[IDX] <- V(71:0) xor X(71:0), IDX <- IDX + 1, AR <- 1;
+        V(95:32) <- V(127:64) + W(63:0), ARR[IDX].B <- sxt W nor 3, read MEM;
+        if V > W.A then C <- 1 else C <- 2 fi, if X = 0 then B <- 7 fi;
+        V <- W(55:0).ARR[1], AR <- W(1:0), if W(5:4) <> 0 then goto END fi;
+        V <- 0;
+        END:
+            assert V = W(55:0).ARR[1], X(79:73) <- 0b1010101, B <- X(79:48);
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
+
+#[test]
+fn wide_state_access() {
+    const SOURCE: &str = r#"
+        declare register AR(3:0), DR(99:0), X(99:0)
+        declare register array ARR(99:0)[4]
+        declare memory MEM(AR,DR)
+
+        read MEM, X <- ARR[1] + 1;
+    "#;
+
+    let mut fast = FastSimulator::init(&util::compile(SOURCE)).unwrap();
+    let ident = |name: &str| Ident(name.to_string());
+    let hex = |value: &str| Value::parse_hex(value).unwrap();
+
+    fast.write_register(&ident("AR"), hex("3")).unwrap();
+    fast.write_memory(&ident("MEM"), hex("3"), SignedValue::parse_dec("-2").unwrap()).unwrap();
+    fast.write_register_array(&ident("ARR"), 1, hex("ffffffffffffffffff")).unwrap();
+    assert!(fast.write_register(&ident("X"), hex("10000000000000000000000000")).is_err());
+
+    fast.step();
+    assert_eq!(fast.register_value(&ident("DR")).unwrap(), hex("ffffffffffffffffffffffffe"));
+    assert_eq!(fast.register_value(&ident("X")).unwrap(), hex("1000000000000000000"));
+    assert_eq!(fast.register_value(&ident("X")).unwrap().size(), 100);
+    assert_eq!(fast.memory_value(&ident("MEM"), &hex("2")).unwrap(), Value::zero(100));
+}
+
+#[test]
+fn register_init() {
+    const SOURCE: &str = r#"
+        declare register PC(7:0) = 0xF0, COUNT(3:0) = 3
+        declare output OUT(7:0) = 1
+
+        LOOP:
+            PC <- PC + 1, OUT <- OUT + PC, COUNT <- COUNT - 1;
+            if COUNT <> 0 then goto LOOP fi;
+    "#;
+
+    assert_equivalent(SOURCE, &[]);
+}
@@ -0,0 +1,29 @@
+mod util;
+
+use rt_easy_simulator::Simulator;
+use rtcore::value::Value;
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+declare register A(7:0), B(7:0), COUNT(7:0)
+define COUNT_DOWN(R) { COUNT <- COUNT + 1; LOOP: if R <> 0 then R <- R - 1, goto LOOP fi; }
+
+A <- 3, B <- 2;
+COUNT_DOWN(A);
+COUNT_DOWN(B);
+"#;
+
+#[test]
+fn macros() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    while !simulator.is_finished() {
+        simulator.step(false).unwrap();
+    }
+
+    let value = |name: &str| simulator.register_value(&Ident(name.to_string())).unwrap();
+    assert_eq!(value("A"), Value::zero(8));
+    assert_eq!(value("B"), Value::zero(8));
+    assert_eq!(value("COUNT"), Value::parse_dec("2").unwrap().with_size(8));
+    // 1 + (1 + 4) + (1 + 3)
+    assert_eq!(simulator.cycle_count(), 10);
+}
@@ -1,5 +1,6 @@
 mod util;
 
+use memory_file::{Endianness, Format, MemoryFile, Options, ParseError, ParseErrorKind};
 use rt_easy_simulator::Simulator;
 use rtcore::value::Value;
 use rtprogram::Ident;
@@ -82,3 +83,84 @@ fn memory() {
         simulator.memory_page(&Ident("MEM".to_string()), Value::parse_dec("1").unwrap()).unwrap();
     assert_eq!(page[0], (Value::parse_dec("0").unwrap(), Value::parse_dec("42").unwrap()));
 }
+
+#[test]
+fn memory_formats() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    for _ in 0..4 {
+        simulator.step(false).unwrap();
+    }
+
+    // Save as Intel HEX
+    let mut save = Vec::new();
+    simulator
+        .save_memory_as(&Ident("MEM".to_string()), Format::IntelHex, &Default::default(), &mut save)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(save.clone()).unwrap(),
+        ":010000002AD5\n:0100020007F6\n:00000001FF\n"
+    );
+
+    // Load from Intel HEX
+    for _ in 0..2 {
+        simulator.step(false).unwrap();
+    }
+    simulator
+        .load_memory_from_save_as(
+            &Ident("MEM".to_string()),
+            Format::IntelHex,
+            &Default::default(),
+            &save[..],
+        )
+        .unwrap();
+    let page =
+        simulator.memory_page(&Ident("MEM".to_string()), Value::parse_dec("1").unwrap()).unwrap();
+    assert_eq!(page[0], (Value::parse_dec("0").unwrap(), Value::parse_dec("42").unwrap()));
+    assert_eq!(page[2], (Value::parse_dec("2").unwrap(), Value::parse_dec("7").unwrap()));
+
+    // Load from raw binary with two bytes per word
+    let options = Options { endianness: Endianness::Big, word_bytes: Some(2) };
+    simulator
+        .load_memory_from_save_as(
+            &Ident("MEM".to_string()),
+            Format::Binary,
+            &options,
+            &[0x00, 0x01, 0x00, 0xFF][..],
+        )
+        .unwrap();
+    let page =
+        simulator.memory_page(&Ident("MEM".to_string()), Value::parse_dec("1").unwrap()).unwrap();
+    assert_eq!(page[0], (Value::parse_dec("0").unwrap(), Value::parse_dec("1").unwrap()));
+    assert_eq!(page[1], (Value::parse_dec("1").unwrap(), Value::parse_dec("255").unwrap()));
+
+    // Data does not fit
+    let err = simulator
+        .load_memory_from_save_as(
+            &Ident("MEM".to_string()),
+            Format::ReadMemH,
+            &Default::default(),
+            &b"1FF"[..],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid readmemh memory file");
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(err.kind, ParseErrorKind::ValueTooWide { dr_size: 8 });
+}
+
+#[test]
+fn memory_diff() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let name = Ident("MEM".to_string());
+
+    // Changes of the run
+    let snapshot = simulator.memory_file(&name).unwrap();
+    for _ in 0..6 {
+        simulator.step(false).unwrap();
+    }
+    let diff = simulator.diff_memory(&name, &snapshot).unwrap();
+    assert_eq!(diff.to_string(), "00: unset -> 15\n02: unset -> 07\n");
+
+    // Compare with a reference
+    let reference = MemoryFile::parse("H 6 8\n15 0 7").unwrap();
+    assert!(simulator.diff_memory(&name, &reference).unwrap().is_empty());
+}
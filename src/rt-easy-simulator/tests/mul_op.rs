@@ -0,0 +1,40 @@
+mod util;
+
+use rt_easy_simulator::Simulator;
+use rtcore::value::{SignedValue, Value};
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+declare register A(7:0), B(7:0), X(15:0)
+
+X <- A * B;
+X <- sxt A * sxt B;
+X <- sxt A * B;
+X <- 1 + A * 2 * 2 - 1;
+"#;
+
+#[test]
+fn mul_op() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let ident = |name: &str| Ident(name.to_string());
+    let value = |simulator: &Simulator, name: &str| simulator.register_value(&ident(name)).unwrap();
+
+    simulator.write_register(&ident("A"), SignedValue::parse_dec("-3").unwrap()).unwrap();
+    simulator.write_register(&ident("B"), Value::parse_dec("5").unwrap()).unwrap();
+
+    // Unsigned, result is as wide as both operands together
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "X"), Value::parse_dec("1265").unwrap());
+
+    // Signed
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "X"), Value::parse_dec("65521").unwrap());
+
+    // Mixed, only the lhs is signed
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "X"), Value::parse_dec("65521").unwrap());
+
+    // Precedence
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "X"), Value::parse_dec("1012").unwrap());
+}
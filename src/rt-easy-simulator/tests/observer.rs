@@ -0,0 +1,112 @@
+mod util;
+
+use rt_easy_simulator::{observer::Observer, Changed, Simulator};
+use rtcore::{common::BitRange, value::Value};
+use rtprogram::{Ident, Label, Span, Step};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const SOURCE: &str = r#"
+    declare register A(3:0), AR(1:0), DR(3:0)
+    declare bus B(3:0)
+    declare register array ARR(3:0)[2]
+    declare memory MEM(AR, DR)
+
+START:
+    B <- 3, A <- B, ARR[0] <- B;
+    if A = 3 then DR <- 1, write MEM, goto NEXT else goto START fi;
+NEXT:
+    assert A = 0;
+"#;
+
+#[derive(Debug, Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+impl Observer for Recorder {
+    fn micro_step(&mut self, statement: usize, _step: &Step, _span: Span) {
+        self.events.push(format!("micro_step {}", statement));
+    }
+    fn condition(&mut self, statement: usize, result: bool, _span: Span) {
+        self.events.push(format!("condition {} {}", statement, result));
+    }
+    fn bus_write(&mut self, bus: &Ident, _range: Option<BitRange>, value: &Value) {
+        self.events.push(format!("bus {} {}", bus.0, value.as_dec()));
+    }
+    fn register_write(&mut self, register: &Ident, _range: Option<BitRange>, value: &Value) {
+        self.events.push(format!("register {} {}", register.0, value.as_dec()));
+    }
+    fn register_array_write(&mut self, register_array: &Ident, index: usize, value: &Value) {
+        self.events.push(format!(
+            "register_array {}[{}] {}",
+            register_array.0,
+            index,
+            value.as_dec()
+        ));
+    }
+    fn memory_write(&mut self, memory: &Ident, address: &Value, value: &Value) {
+        self.events.push(format!("memory {}[{}] {}", memory.0, address.as_dec(), value.as_dec()));
+    }
+    fn clock(&mut self, changed: &Changed) {
+        self.events.push(format!("clock {}", changed.registers.len()));
+    }
+    fn goto(&mut self, statement: usize, label: &Label, target: usize) {
+        self.events.push(format!("goto {} {} {}", statement, label.0, target));
+    }
+    fn breakpoint(&mut self, statement: usize) {
+        self.events.push(format!("breakpoint {}", statement));
+    }
+    fn assert_error(&mut self, statement: usize, _span: Span) {
+        self.events.push(format!("assert_error {}", statement));
+    }
+}
+
+#[test]
+fn observer() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    simulator.add_observer(recorder.clone());
+    simulator.add_breakpoint(2);
+
+    while !simulator.is_finished() {
+        simulator.step(true).unwrap();
+    }
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            "micro_step 0",
+            "bus B 3",
+            "micro_step 0",
+            "register A 3",
+            "micro_step 0",
+            "register_array ARR[0] 3",
+            "clock 1",
+            "micro_step 1",
+            "condition 1 true",
+            "micro_step 1",
+            "register DR 1",
+            "micro_step 1",
+            "memory MEM[0] 0",
+            "micro_step 1",
+            "clock 1",
+            "goto 1 NEXT 2",
+            "breakpoint 2",
+            "micro_step 2",
+            "assert_error 2",
+        ]
+    );
+}
+
+#[test]
+fn remove_observer() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    let id = simulator.add_observer(recorder.clone());
+    assert!(simulator.remove_observer(id));
+    assert!(!simulator.remove_observer(id));
+
+    simulator.step(false).unwrap();
+    assert!(recorder.borrow().events.is_empty());
+}
@@ -0,0 +1,34 @@
+mod util;
+
+use rt_easy_simulator::Simulator;
+use rtcore::value::Value;
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+declare constant START = 0x10
+declare register PC(7:0) = START, ACC(3:0) = 0b1010, X(3:0)
+
+PC <- PC + 1, ACC <- 0, X <- 1;
+"#;
+
+#[test]
+fn register_init() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    let ident = |name: &str| Ident(name.to_string());
+    let value = |simulator: &Simulator, name: &str| simulator.register_value(&ident(name)).unwrap();
+
+    // Init
+    assert_eq!(value(&simulator, "PC"), Value::parse_hex("10").unwrap());
+    assert_eq!(value(&simulator, "ACC"), Value::parse_bin("1010").unwrap());
+    assert_eq!(value(&simulator, "X"), Value::zero(4));
+
+    simulator.step(false).unwrap();
+    assert_eq!(value(&simulator, "PC"), Value::parse_hex("11").unwrap());
+    assert_eq!(value(&simulator, "ACC"), Value::zero(4));
+
+    // Reset
+    simulator.reset(false);
+    assert_eq!(value(&simulator, "PC"), Value::parse_hex("10").unwrap());
+    assert_eq!(value(&simulator, "ACC"), Value::parse_bin("1010").unwrap());
+    assert_eq!(value(&simulator, "X"), Value::zero(4));
+}
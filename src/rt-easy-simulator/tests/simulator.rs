@@ -54,7 +54,7 @@ fn simulator_cycle_count() {
 fn simulator_statement_span() {
     let simulator = Simulator::init(util::compile(SOURCE));
 
-    assert_eq!(simulator.statement_span(0), Some(Span { start: 42, end: 56 }));
-    assert_eq!(simulator.statement_span(1), Some(Span { start: 62, end: 83 }));
+    assert_eq!(simulator.statement_span(0), Some(Span::from(42..56)));
+    assert_eq!(simulator.statement_span(1), Some(Span::from(62..83)));
     assert_eq!(simulator.statement_span(3), None);
 }
@@ -0,0 +1,71 @@
+mod util;
+
+use rt_easy_simulator::{Simulator, UninitializedKind};
+use rtcore::value::Value;
+use rtprogram::Ident;
+
+const SOURCE: &str = r#"
+    declare register A(7:0), B(7:0), AR(3:0), DR(7:0)
+    declare output OUT(7:0)
+    declare memory MEM(AR, DR)
+
+    A <- 1;
+    if B = 1 then A <- 2 else goto NEXT fi;
+NEXT:
+    read MEM;
+    OUT <- A + DR;
+    OUT <- A(3:0) + B(7:4), AR <- A(3:0);
+    read MEM;
+    OUT <- DR(0), DR <- 0;
+    write MEM;
+    read MEM;
+    OUT <- DR;
+"#;
+
+fn run(simulator: &mut Simulator) {
+    while !simulator.is_finished() {
+        simulator.step(false).unwrap();
+    }
+}
+
+#[test]
+fn uninitialized() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    simulator.set_track_uninitialized(true);
+    run(&mut simulator);
+
+    let kinds = simulator
+        .uninitialized_diagnostics()
+        .iter()
+        .map(|d| (d.statement, d.kind.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            (1, UninitializedKind::Condition),
+            (1, UninitializedKind::Goto),
+            (2, UninitializedKind::MemoryAddress(Ident("MEM".to_string()))),
+            (3, UninitializedKind::OutputRegister(Ident("OUT".to_string()))),
+            (4, UninitializedKind::OutputRegister(Ident("OUT".to_string()))),
+            (6, UninitializedKind::OutputRegister(Ident("OUT".to_string()))),
+        ]
+    );
+
+    // Spans point into the source
+    let diagnostic = &simulator.uninitialized_diagnostics()[0];
+    assert_eq!(&SOURCE[diagnostic.operation_span.range()], "B = 1");
+
+    // Reset clears the diagnostics, but keeps tracking enabled
+    simulator.reset(false);
+    assert!(simulator.uninitialized_diagnostics().is_empty());
+    simulator.write_register(&Ident("B".to_string()), Value::parse_dec("1").unwrap()).unwrap();
+    run(&mut simulator);
+    assert_eq!(simulator.uninitialized_diagnostics().len(), 3);
+}
+
+#[test]
+fn disabled() {
+    let mut simulator = Simulator::init(util::compile(SOURCE));
+    run(&mut simulator);
+    assert!(simulator.uninitialized_diagnostics().is_empty());
+}
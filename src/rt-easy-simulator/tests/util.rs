@@ -3,7 +3,7 @@ use rtprogram::Program;
 pub fn compile(source: &str) -> Program {
     let ast = match parser::parse(source) {
         Ok(ast) => ast,
-        Err(e) => panic!("{}", parser::pretty_print_error(&e, source, None, false)),
+        Err(e) => panic!("{}", parser::pretty_print_errors(&e, source, None, false)),
     };
 
     let backend = compiler_backend_simulator::BackendSimulator;
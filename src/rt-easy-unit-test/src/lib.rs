@@ -1,8 +1,12 @@
 #![deny(rust_2018_idioms)]
 
+mod report;
 mod run;
+mod table;
 
 pub mod parser;
 pub mod unit_test;
 
-pub use self::run::run;
+pub use self::report::{Report, TestError, TestResult};
+pub use self::run::{run, Options};
+pub use self::table::TableError;
@@ -6,9 +6,19 @@ pub enum Token {
     OperationAssignment,
     #[regex(r"assert[^\r\n]*")]
     OperationAssert,
+    #[regex(r"expect[^\r\n]*")]
+    OperationExpect,
+    #[regex(r"for[ \t][A-Z0-9_, \t]*with")]
+    TableColumns,
 
+    #[regex("(%|0[bB])[01]+")]
+    LiteralNumberBin,
+    #[regex(r"(\$|0[xX])[0-9a-fA-F]+")]
+    LiteralNumberHex,
     #[regex("[0-9]+")]
     LiteralNumberDec,
+    #[regex(r#""[^"\r\n]*""#)]
+    LiteralString,
 
     #[token("step")]
     KeywordStep,
@@ -24,6 +34,19 @@ pub enum Token {
     KeywordRemove,
     #[token("breakpoint")]
     KeywordBreakpoint,
+    #[token("test")]
+    KeywordTest,
+    #[token("setup")]
+    KeywordSetup,
+
+    #[token("{")]
+    BraceOpen,
+    #[token("}")]
+    BraceClose,
+    #[token(",")]
+    Comma,
+    #[token("-")]
+    Minus,
 
     #[regex("[a-zA-Z_][a-zA-Z0-9_]*", is_valid_ident)]
     Identifier,
@@ -43,8 +43,13 @@ pub fn pretty_print_error(
             match token {
                 OperationAssignment => "<ASSIGNMENT>",
                 OperationAssert => "<ASSERT>",
+                OperationExpect => "<EXPECT>",
+                TableColumns => "<FOR_WITH>",
 
+                LiteralNumberBin => "<NUM_BIN>",
+                LiteralNumberHex => "<NUM_HEX>",
                 LiteralNumberDec => "<NUM_DEC>",
+                LiteralString => "<STRING>",
 
                 KeywordStep => "\"step\"",
                 KeywordMicroStep => "\"microStep\"",
@@ -53,6 +58,13 @@ pub fn pretty_print_error(
                 KeywordSet => "\"set\"",
                 KeywordRemove => "\"remove\"",
                 KeywordBreakpoint => "\"breakpoint\"",
+                KeywordTest => "\"test\"",
+                KeywordSetup => "\"setup\"",
+
+                BraceOpen => "\"{\"",
+                BraceClose => "\"}\"",
+                Comma => "\",\"",
+                Minus => "\"-\"",
 
                 Identifier => "<ID>",
 
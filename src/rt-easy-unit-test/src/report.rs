@@ -0,0 +1,107 @@
+use ansi_term::Colour::{Green, Red};
+use rtcore::common::Span;
+use std::fmt::Write;
+
+#[derive(Debug)]
+pub struct Report {
+    pub tests: Vec<TestResult>,
+}
+
+#[derive(Debug)]
+pub struct TestResult {
+    /// `None` for the operations of a file without `test` blocks.
+    pub name: Option<String>,
+    /// The columns and values of the row for tests with a table.
+    pub parameters: Vec<(String, String)>,
+    pub error: Option<TestError>,
+}
+
+/// The first failed operation of a test, later operations of the test are not run.
+#[derive(Debug)]
+pub struct TestError {
+    pub message: String,
+    /// Span in the unit test file.
+    pub span: Span,
+    /// E.g. the values of a failed comparison and the state of the simulator.
+    pub notes: Vec<String>,
+}
+
+impl Report {
+    pub fn is_success(&self) -> bool {
+        self.tests.iter().all(|test| test.error.is_none())
+    }
+
+    pub fn passed(&self) -> usize {
+        self.tests.iter().filter(|test| test.error.is_none()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.tests.len() - self.passed()
+    }
+
+    /// One line per test, followed by the errors of the failed tests and a summary.
+    pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        let paint = |failed: bool, text: &str| match (ansi_colors, failed) {
+            (false, _) => text.to_string(),
+            (true, false) => Green.paint(text).to_string(),
+            (true, true) => Red.paint(text).to_string(),
+        };
+
+        let mut s = String::new();
+        for test in &self.tests {
+            let result = match test.error {
+                Some(_) => paint(true, "FAILED"),
+                None => paint(false, "ok"),
+            };
+            writeln!(s, "test {} ... {}", test.display_name(), result).unwrap();
+        }
+
+        for test in &self.tests {
+            if let Some(error) = &test.error {
+                writeln!(s, "\n---- {} ----", test.display_name()).unwrap();
+                writeln!(s, "{}", error.pretty_print(source, file_name, ansi_colors)).unwrap();
+            }
+        }
+
+        let result = match self.is_success() {
+            true => paint(false, "ok"),
+            false => paint(true, "FAILED"),
+        };
+        write!(s, "\ntest result: {}. {} passed; {} failed", result, self.passed(), self.failed())
+            .unwrap();
+
+        s
+    }
+}
+
+impl TestResult {
+    fn display_name(&self) -> String {
+        let name = self.name.as_deref().unwrap_or("<unnamed>");
+        if self.parameters.is_empty() {
+            return name.to_string();
+        }
+
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect::<Vec<_>>();
+        format!("{} [{}]", name, parameters.join(", "))
+    }
+}
+
+impl TestError {
+    pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        let mut error = pretty_error::Error::new(&self.message)
+            .with_source(source, pretty_error::Span::Range(self.span.range()))
+            .with_ansi_colors(ansi_colors);
+        if let Some(file_name) = file_name {
+            error = error.with_file_name(file_name);
+        }
+        for note in &self.notes {
+            error = error.with_note(note);
+        }
+
+        error.to_string()
+    }
+}
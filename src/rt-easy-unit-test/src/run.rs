@@ -1,70 +1,192 @@
+use crate::report::{Report, TestError, TestResult};
 use crate::unit_test::{
-    Assert, Assignment, MicroStep, OperationKind, RemoveBreakpoint, Reset, Run, SetBreakpoint,
-    Step, UnitTest,
+    Assert, AssertCycles, AssertMemory, Assignment, Comparison, Expect, MicroStep, Operation,
+    OperationKind, RemoveBreakpoint, Reset, Run, SetBreakpoint, Step, Table, TableRows, UnitTest,
 };
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{anyhow, Context};
+use memory_file::MemoryFile;
 use rtast as ast;
-use rtcore::common::{BusKind, RegisterKind, Span, Spanned};
-use rtprogram::{Declaration, Label as ProgramLabel, Program};
+use rtcore::common::{BinaryOperator, BusKind, Number, NumberKind, RegisterKind, Span, Spanned};
+use rtcore::value::Value;
+use rtprogram::{Declaration, Ident, Label as ProgramLabel, Program};
 use simulator::{Simulator, StepResult, StepResultKind};
+use std::borrow::Cow;
+use std::ops::Range;
 
-// TODO: Better errrors (custom_error+pretty_print instead of anyhow)
+type Result<T> = std::result::Result<T, OperationError>;
 
-pub fn run(program: Program, unit_test: UnitTest) -> Result<()> {
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Fails a test if an assert of the program fails without `expect assert_error` after the
+    /// operation.
+    pub fail_on_assert_error: bool,
+}
+
+pub fn run(program: Program, unit_test: UnitTest, options: &Options) -> Report {
     let mut simulator = Simulator::init(program);
+    let setup = unit_test.setup.map(|setup| setup.operations).unwrap_or_default();
 
-    for operation in unit_test.operations {
-        match operation.kind {
-            OperationKind::Step(Step { amount }) => {
-                for _ in 0..amount.unwrap_or(1) {
-                    simulator.step(false).context("Step failed")?;
+    let mut tests = Vec::new();
+    for test in unit_test.tests {
+        let mut run_test = |parameters: Vec<(String, String)>| {
+            let error = run_test(&mut simulator, &setup, &test.operations, &parameters, options);
+            tests.push(TestResult { name: test.name.clone(), parameters, error });
+        };
+
+        match test.table {
+            None => run_test(Vec::new()),
+            Some(Table { columns, rows: TableRows::Inline(rows), .. }) => {
+                for row in rows {
+                    run_test(columns.iter().cloned().zip(row.values).collect());
                 }
             }
-            OperationKind::MicroStep(MicroStep { amount }) => {
-                for _ in 0..amount.unwrap_or(1) {
-                    simulator.micro_step(false).context("Micro Step failed")?;
-                }
+            Some(Table { rows: TableRows::File { path, span }, .. }) => {
+                let message = format!("the table {} is not loaded", path);
+                let error = TestError { message, span, notes: Vec::new() };
+                tests.push(TestResult {
+                    name: test.name,
+                    parameters: Vec::new(),
+                    error: Some(error),
+                });
             }
-            OperationKind::Run(Run) => {
-                while !simulator.is_finished() {
-                    let step_result = simulator.step(true).context("Run failed")?;
-                    if matches!(
-                        step_result,
-                        Some(StepResult { kind: StepResultKind::Breakpoint, .. })
-                    ) {
-                        break;
-                    }
+        }
+    }
+
+    Report { tests }
+}
+
+/// Runs the operations from the initial state until the first error.
+fn run_test(
+    simulator: &mut Simulator,
+    setup: &[Operation],
+    operations: &[Operation],
+    parameters: &[(String, String)],
+    options: &Options,
+) -> Option<TestError> {
+    simulator.reset(true);
+
+    let mut assert_error = None;
+    let mut operations = setup.iter().chain(operations).peekable();
+    while let Some(operation) = operations.next() {
+        let mut result = exec_operation(simulator, operation, parameters, &mut assert_error);
+
+        // A failed assert of the program can only be expected by the next operation
+        let is_expected = matches!(
+            operations.peek(),
+            Some(Operation { kind: OperationKind::Expect(Expect::AssertError), .. })
+        );
+        if let (Ok(()), false) = (&result, is_expected) {
+            match assert_error.take() {
+                Some(step_result) if options.fail_on_assert_error => {
+                    result = Err(OperationError::program_assert(simulator, &step_result));
                 }
+                _ => (),
             }
-            OperationKind::Reset(Reset) => simulator.reset(true),
-            OperationKind::SetBreakpoint(SetBreakpoint { label }) => {
-                let label = ProgramLabel(label.0);
-                simulator.add_breakpoint_at_label(&label);
-            }
-            OperationKind::RemoveBreakpoint(RemoveBreakpoint { label }) => {
-                let label = ProgramLabel(label.0);
-                simulator.remove_breakpoint_at_label(&label);
+        }
+
+        if let Err(error) = result {
+            return Some(error.into_test_error(operation.span, simulator));
+        }
+    }
+
+    None
+}
+
+fn exec_operation(
+    simulator: &mut Simulator,
+    operation: &Operation,
+    parameters: &[(String, String)],
+    assert_error: &mut Option<StepResult>,
+) -> Result<()> {
+    // Spans in substituted text do not match the unit test
+    let mut substituted = false;
+    match exec_operation_(simulator, operation, parameters, assert_error, &mut substituted) {
+        Err(error) if substituted => Err(OperationError { span: None, ..error }),
+        result => result,
+    }
+}
+
+fn exec_operation_<'a>(
+    simulator: &mut Simulator,
+    operation: &'a Operation,
+    parameters: &[(String, String)],
+    assert_error: &mut Option<StepResult>,
+    substituted: &mut bool,
+) -> Result<()> {
+    let mut substitute = |text: &'a str| -> Cow<'a, str> {
+        let text = substitute(text, parameters);
+        *substituted |= matches!(text, Cow::Owned(_));
+        text
+    };
+
+    match &operation.kind {
+        OperationKind::Step(Step { amount }) => {
+            for _ in 0..amount.unwrap_or(1) {
+                let step_result = simulator.step(false).context("Step failed")?;
+                record_assert_error(step_result, assert_error);
             }
-            OperationKind::Assignment(Assignment { assignment }) => {
-                let assignment = match parser::parse_assignment(&assignment) {
-                    Ok(assignment) => assignment,
-                    Err(_e) => bail!("Failed to parse assignment"), // TODO: better error
-                };
-                exec_assignment(&mut simulator, assignment)?;
+        }
+        OperationKind::MicroStep(MicroStep { amount }) => {
+            for _ in 0..amount.unwrap_or(1) {
+                let step_result = simulator.micro_step(false).context("Micro Step failed")?;
+                record_assert_error(step_result, assert_error);
             }
-            OperationKind::Assert(Assert { assert }) => {
-                let assert = match parser::parse_assert(&assert) {
-                    Ok(assert) => assert,
-                    Err(_e) => bail!("Failed to parse assert"), // TODO: better error
-                };
-                exec_assert(&mut simulator, assert)?;
+        }
+        OperationKind::Run(Run) => {
+            while !simulator.is_finished() {
+                let step_result = simulator.step(true).context("Run failed")?;
+                if matches!(step_result, Some(StepResult { kind: StepResultKind::Breakpoint, .. }))
+                {
+                    break;
+                }
+                record_assert_error(step_result, assert_error);
             }
         }
+        OperationKind::Reset(Reset) => simulator.reset(true),
+        OperationKind::SetBreakpoint(SetBreakpoint { label }) => {
+            let label = ProgramLabel(label.0.clone());
+            simulator.add_breakpoint_at_label(&label);
+        }
+        OperationKind::RemoveBreakpoint(RemoveBreakpoint { label }) => {
+            let label = ProgramLabel(label.0.clone());
+            simulator.remove_breakpoint_at_label(&label);
+        }
+        OperationKind::Assignment(Assignment { assignment }) => {
+            let assignment = substitute(assignment);
+            let assignment =
+                parser::parse_assignment(&assignment).map_err(OperationError::parse)?;
+            exec_assignment(simulator, assignment)?;
+        }
+        OperationKind::Assert(Assert { assert }) => exec_assert(simulator, &substitute(assert))?,
+        OperationKind::AssertMemory(AssertMemory { memory, content }) => {
+            exec_assert_memory(simulator, &substitute(memory), &substitute(content))?;
+        }
+        OperationKind::AssertCycles(assert) => exec_assert_cycles(simulator, assert)?,
+        OperationKind::Expect(expect) => exec_expect(simulator, expect, assert_error)?,
     }
 
     Ok(())
 }
 
+/// Remembers a failed assert of the program for `expect assert_error`.
+fn record_assert_error(step_result: Option<StepResult>, assert_error: &mut Option<StepResult>) {
+    if let Some(step_result @ StepResult { kind: StepResultKind::AssertError, .. }) = step_result {
+        *assert_error = Some(step_result);
+    }
+}
+
+/// Replaces `{NAME}` with the value of the parameter `NAME`.
+fn substitute<'a>(text: &'a str, parameters: &[(String, String)]) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(text);
+    for (name, value) in parameters {
+        let placeholder = format!("{{{}}}", name);
+        if text.contains(&placeholder) {
+            text = Cow::Owned(text.replace(&placeholder, value));
+        }
+    }
+    text
+}
+
 fn exec_assignment(simulator: &mut Simulator, assignment: ast::Assignment<'_>) -> Result<()> {
     // Setup
     let test_program = build_test_program(
@@ -74,7 +196,7 @@ fn exec_assignment(simulator: &mut Simulator, assignment: ast::Assignment<'_>) -
     let mut test_simulator = setup_test_simulator(&*simulator, test_program)?;
 
     // Run assignment
-    test_simulator.step(false)?;
+    test_simulator.step(false).context("Assignment failed")?;
 
     // Copy inputs back to simulator
     for bus in test_simulator.buses(BusKind::Intern) {
@@ -85,22 +207,129 @@ fn exec_assignment(simulator: &mut Simulator, assignment: ast::Assignment<'_>) -
     Ok(())
 }
 
-fn exec_assert(simulator: &mut Simulator, assert: ast::Assert<'_>) -> Result<()> {
-    // Setup
-    let test_program =
-        build_test_program(simulator.program().declarations(), ast::Operation::Assert(assert))?;
-    let mut test_simulator = setup_test_simulator(&*simulator, test_program)?;
+fn exec_assert(simulator: &Simulator, assert: &str) -> Result<()> {
+    let condition = parser::parse_assert(assert).map_err(OperationError::parse)?.condition;
+    let span = condition.span().range();
+    let size = simulator.check_expression(condition.clone()).map_err(OperationError::compile)?;
+    if size > 1 {
+        let message = format!("condition is {} bits wide, expected 1 bit", size);
+        return Err(OperationError::new(message, Some(span), false));
+    }
+
+    let value = simulator.evaluate(&assert[span.clone()]).context("Failed to evaluate assert")?;
+    if !value.is_zero() {
+        return Ok(());
+    }
 
-    // Run assert
-    let step_result = test_simulator.micro_step(false)?.unwrap();
-    match step_result.kind {
-        StepResultKind::Void => Ok(()),
-        StepResultKind::AssertError => Err(anyhow!("Assert failed")), // TODO: better error
-        StepResultKind::Condition { .. }
-        | StepResultKind::Pipe(..)
-        | StepResultKind::StatementEnd(..)
-        | StepResultKind::Breakpoint => unreachable!(),
+    // Show both sides of the comparisons
+    let mut error = OperationError::new("Assert failed".to_string(), Some(span.clone()), true);
+    let mut comparisons = Vec::new();
+    collect_comparisons(&condition, &mut comparisons);
+    for term in comparisons {
+        let evaluate = |expression: &ast::Expression<'_>| {
+            simulator.evaluate(&assert[expression.span().range()]).ok().map(|v| display_value(&v))
+        };
+        if let (Some(lhs), Some(rhs)) = (evaluate(&term.lhs), evaluate(&term.rhs)) {
+            let text = &assert[term.span.range()];
+            error.notes.push(format!("`{}`: left is {}, right is {}", text, lhs, rhs));
+        }
+    }
+    if error.notes.is_empty() {
+        error.notes.push(format!("`{}` is 0", &assert[span]));
     }
+
+    Err(error)
+}
+
+fn collect_comparisons<'a, 's>(
+    expression: &'a ast::Expression<'s>,
+    comparisons: &mut Vec<&'a ast::BinaryTerm<'s>>,
+) {
+    match expression {
+        ast::Expression::Atom(_) => (),
+        ast::Expression::BinaryTerm(term) => {
+            collect_comparisons(&term.lhs, comparisons);
+            use BinaryOperator::*;
+            if matches!(term.operator.node, Eq | Ne | Le | Lt | Ge | Gt) {
+                comparisons.push(term);
+            }
+            collect_comparisons(&term.rhs, comparisons);
+        }
+        ast::Expression::UnaryTerm(term) => collect_comparisons(&term.expression, comparisons),
+    }
+}
+
+/// Decimal, with the hexadecimal value for values above 9.
+fn display_value(value: &Value) -> String {
+    let dec = value.as_dec();
+    if dec.len() > 1 {
+        format!("{} (0x{})", dec, value.as_hex())
+    } else {
+        dec
+    }
+}
+
+fn exec_assert_memory(simulator: &Simulator, memory: &str, content: &str) -> Result<()> {
+    let actual = simulator.memory_file(&Ident(memory.to_string()))?;
+    let source = format!("H {} {}\n{}", actual.ar_size(), actual.dr_size(), content);
+    let expected = MemoryFile::parse(&source)
+        .map_err(|e| anyhow!("Invalid content of memory {}: {} \"{}\"", memory, e.kind, e.text))?;
+
+    let diff = expected.diff(&actual);
+    if diff.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Memory {} differs (expected -> actual):\n{}", memory, diff).into())
+    }
+}
+
+fn exec_assert_cycles(simulator: &Simulator, assert: &AssertCycles) -> Result<()> {
+    let AssertCycles { comparison, cycles } = *assert;
+    let actual = simulator.cycle_count();
+    let (is_success, operator) = match comparison {
+        Comparison::Eq => (actual == cycles, "="),
+        Comparison::Ne => (actual != cycles, "<>"),
+        Comparison::Lt => (actual < cycles, "<"),
+        Comparison::Le => (actual <= cycles, "<="),
+        Comparison::Gt => (actual > cycles, ">"),
+        Comparison::Ge => (actual >= cycles, ">="),
+    };
+    if is_success {
+        return Ok(());
+    }
+
+    let mut error = OperationError::new("Assert failed".to_string(), None, true);
+    let note = format!("`cycles {} {}`: left is {}, right is {}", operator, cycles, actual, cycles);
+    error.notes.push(note);
+    Err(error)
+}
+
+fn exec_expect(
+    simulator: &Simulator,
+    expect: &Expect,
+    assert_error: &mut Option<StepResult>,
+) -> Result<()> {
+    let message = match expect {
+        Expect::AssertError => match assert_error.take() {
+            Some(_) => return Ok(()),
+            None => "Expected an assert of the program to fail".to_string(),
+        },
+        Expect::Finished if simulator.is_finished() => return Ok(()),
+        Expect::Finished => "Expected the program to be finished".to_string(),
+        Expect::At(label) => {
+            let statement =
+                simulator.program().statements().iter().position(
+                    |statement| matches!(&statement.label, Some(l) if l.node.0 == label.0),
+                );
+            let statement = statement.ok_or_else(|| anyhow!("Label {} does not exist", label.0))?;
+            if simulator.statement() == Some(statement) {
+                return Ok(());
+            }
+            format!("Expected the simulator to be at label {}", label.0)
+        }
+    };
+
+    Err(OperationError::new(message, None, true))
 }
 
 fn setup_test_simulator(simulator: &Simulator, test_program: Program) -> Result<Simulator> {
@@ -126,6 +355,7 @@ fn build_test_program(
     operation: ast::Operation<'_>,
 ) -> Result<Program> {
     let ast = ast::Ast {
+        includes: Vec::new(),
         declarations: map_declarations(declarations),
         statements: vec![ast::Statement {
             label: None,
@@ -139,13 +369,11 @@ fn build_test_program(
             span_semicolon: Span::dummy(),
         }],
         trailing_label: None,
+        expansions: Vec::new(),
     };
 
     let backend = compiler_backend_simulator::BackendSimulator;
-    match compiler::compile(&backend, (), ast, &Default::default()) {
-        Ok(program) => Ok(program),
-        Err(_e) => Err(anyhow!("Failed to build test program")), // TODO: better error
-    }
+    compiler::compile(&backend, (), ast, &Default::default()).map_err(OperationError::compile)
 }
 
 fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
@@ -161,9 +389,10 @@ fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
                             registers: declare
                                 .registers
                                 .iter()
-                                .map(|register| ast::RegBus {
+                                .map(|register| ast::DeclareRegisterItem {
                                     ident: spanned_dummy(ast::Ident(&register.ident.0)),
-                                    range: register.range.map(spanned_dummy),
+                                    range: register.range.map(|range| spanned_dummy(range.into())),
+                                    init: None,
                                     span: Span::dummy(),
                                 })
                                 .collect(),
@@ -183,7 +412,7 @@ fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
                             .iter()
                             .map(|bus| ast::RegBus {
                                 ident: spanned_dummy(ast::Ident(&bus.ident.0)),
-                                range: bus.range.map(spanned_dummy),
+                                range: bus.range.map(|range| spanned_dummy(range.into())),
                                 span: Span::dummy(),
                             })
                             .collect(),
@@ -192,6 +421,22 @@ fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
                     })),
                 }
             }
+            Declaration::Constant(declare) => {
+                Some(ast::Declaration::Constant(ast::DeclareConstant {
+                    constants: declare
+                        .constants
+                        .iter()
+                        .map(|constant| ast::DeclareConstantItem {
+                            ident: spanned_dummy(ast::Ident(&constant.ident.0)),
+                            value: ast::Expression::Atom(ast::Atom::Number(spanned_dummy(
+                                Number { value: constant.value.clone(), kind: NumberKind::Decimal },
+                            ))),
+                            span: Span::dummy(),
+                        })
+                        .collect(),
+                    span: Span::dummy(),
+                }))
+            }
             Declaration::Memory(_) | Declaration::RegisterArray(_) => None,
         })
         .collect()
@@ -200,3 +445,89 @@ fn map_declarations(declarations: &[Declaration]) -> Vec<ast::Declaration<'_>> {
 fn spanned_dummy<T>(node: T) -> Spanned<T> {
     Spanned { node, span: Span::dummy() }
 }
+
+/// Error of an operation. Spans are relative to the text of the operation.
+#[derive(Debug)]
+struct OperationError {
+    message: String,
+    /// `None` for the whole operation.
+    span: Option<Range<usize>>,
+    notes: Vec<String>,
+    /// Errors that depend on the state of the simulator, which is added to the notes.
+    with_state: bool,
+}
+
+impl OperationError {
+    fn new(message: String, span: Option<Range<usize>>, with_state: bool) -> Self {
+        Self { message, span, notes: Vec::new(), with_state }
+    }
+
+    fn parse(error: toktok::Error<parser::Token>) -> Self {
+        let span = error.span().as_range().cloned();
+        Self::new(parser::error_message(&error), span, false)
+    }
+
+    fn compile(error: compiler::Error) -> Self {
+        // Only the first error is reported
+        let error = match error {
+            compiler::Error::Errors(errors) => errors.into_iter().min_by_key(|error| error.span),
+            error => return Self::new(error.pretty_print("", None, false), None, false),
+        };
+        let error = match error {
+            Some(error) => error,
+            None => return Self::new("Failed to compile the operation".to_string(), None, false),
+        };
+
+        let message = format!("{} [E{:03}]", error.kind, error.kind.code());
+        let mut operation_error = Self::new(message, Some(error.span.range()), false);
+        operation_error.notes.extend(error.kind.note().map(str::to_string));
+        operation_error.notes.extend(error.notes);
+        operation_error
+    }
+
+    /// An assert of the program failed without `expect assert_error`.
+    fn program_assert(simulator: &Simulator, step_result: &StepResult) -> Self {
+        let mut error = Self::new("Assert of the program failed".to_string(), None, false);
+        error.notes.push(format!(
+            "cycle {}, {}",
+            simulator.cycle_count(),
+            describe_statement(simulator, Some(step_result.statement))
+        ));
+        error.notes.push("add `expect assert_error` if the assert is expected to fail".to_string());
+        error
+    }
+
+    fn into_test_error(mut self, operation: Span, simulator: &Simulator) -> TestError {
+        let span = match self.span {
+            Some(span) => Span::from(operation.start + span.start..operation.start + span.end),
+            None => operation,
+        };
+        if self.with_state {
+            self.notes.push(describe_state(simulator));
+        }
+
+        TestError { message: self.message, span, notes: self.notes }
+    }
+}
+
+impl From<anyhow::Error> for OperationError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(format!("{:#}", error), None, true)
+    }
+}
+
+/// The cycle count and the statement the simulator is at.
+fn describe_state(simulator: &Simulator) -> String {
+    let statement = describe_statement(simulator, simulator.statement());
+    format!("cycle {}, {}", simulator.cycle_count(), statement)
+}
+
+fn describe_statement(simulator: &Simulator, statement: Option<usize>) -> String {
+    match statement {
+        Some(idx) => match &simulator.program().statements()[idx].label {
+            Some(label) => format!("at statement {} (label {})", idx, label.node.0),
+            None => format!("at statement {}", idx),
+        },
+        None => "at the end of the program".to_string(),
+    }
+}
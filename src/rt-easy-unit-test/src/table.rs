@@ -0,0 +1,76 @@
+use crate::unit_test::{TableRow, TableRows, UnitTest};
+use rtcore::common::Span;
+
+/// Error of a table file, located at the path of the file in the unit test.
+#[derive(Debug)]
+pub struct TableError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl UnitTest {
+    /// Replaces the tables of CSV files with their rows. The first row of a file names the columns,
+    /// columns that are not used by the test are ignored. `read_file` reads a file by the path
+    /// written in the unit test.
+    pub fn load_tables(
+        &mut self,
+        mut read_file: impl FnMut(&str) -> Option<String>,
+    ) -> Result<(), TableError> {
+        for table in self.tests.iter_mut().filter_map(|test| test.table.as_mut()) {
+            let (path, span) = match &table.rows {
+                TableRows::File { path, span } => (path, *span),
+                TableRows::Inline(_) => continue,
+            };
+
+            let error = |message: String| TableError { message, span };
+            let source =
+                read_file(path).ok_or_else(|| error(format!("failed to read {}", path)))?;
+            let rows = parse_csv(&source, &table.columns, span)
+                .map_err(|message| error(format!("invalid table {}: {}", path, message)))?;
+            table.rows = TableRows::Inline(rows);
+        }
+
+        Ok(())
+    }
+}
+
+impl TableError {
+    pub fn pretty_print(&self, source: &str, file_name: Option<&str>, ansi_colors: bool) -> String {
+        let mut error = pretty_error::Error::new(&self.message)
+            .with_source(source, pretty_error::Span::Range(self.span.range()))
+            .with_ansi_colors(ansi_colors);
+        if let Some(file_name) = file_name {
+            error = error.with_file_name(file_name);
+        }
+
+        error.to_string()
+    }
+}
+
+fn parse_csv(source: &str, columns: &[String], span: Span) -> Result<Vec<TableRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .comment(Some(b'#'))
+        .from_reader(source.as_bytes());
+
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let indices = columns
+        .iter()
+        .map(|column| match headers.iter().position(|header| header == column) {
+            Some(idx) => Ok(idx),
+            None => Err(format!("no column {}", column)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        let values = indices.iter().map(|idx| record[*idx].to_string()).collect();
+        rows.push(TableRow { values, span });
+    }
+    if rows.is_empty() {
+        return Err("the table has no rows".to_string());
+    }
+
+    Ok(rows)
+}
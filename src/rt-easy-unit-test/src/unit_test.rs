@@ -3,7 +3,48 @@ pub use rtcore::value::Value;
 
 #[derive(Debug)]
 pub struct UnitTest {
+    /// Runs before each test, after the simulator is reset.
+    pub setup: Option<Setup>,
+    pub tests: Vec<TestCase>,
+}
+
+#[derive(Debug)]
+pub struct Setup {
+    pub operations: Vec<Operation>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct TestCase {
+    /// `None` for the operations of a file without `test` blocks.
+    pub name: Option<String>,
+    /// The test runs once per row of the table.
+    pub table: Option<Table>,
     pub operations: Vec<Operation>,
+    pub span: Span,
+}
+
+/// `for A, B with { ... }` or `for A, B with "file.csv"`. The values of a row replace `{A}` and
+/// `{B}` in assignments and asserts.
+#[derive(Debug)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: TableRows,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum TableRows {
+    /// One value per column.
+    Inline(Vec<TableRow>),
+    /// A CSV file with a header row, see [`UnitTest::load_tables`].
+    File { path: String, span: Span },
+}
+
+#[derive(Debug)]
+pub struct TableRow {
+    pub values: Vec<String>,
+    pub span: Span,
 }
 
 #[derive(Debug)]
@@ -22,6 +63,9 @@ pub enum OperationKind {
     RemoveBreakpoint(RemoveBreakpoint),
     Assignment(Assignment),
     Assert(Assert),
+    AssertMemory(AssertMemory),
+    AssertCycles(AssertCycles),
+    Expect(Expect),
 }
 
 #[derive(Debug)]
@@ -50,6 +94,42 @@ pub struct Assert {
     pub assert: String,
 }
 
+/// Compares a memory with the content of a memory file, without the header.
+#[derive(Debug)]
+pub struct AssertMemory {
+    pub memory: String,
+    pub content: String,
+}
+
+/// Compares the number of cycles since the last reset, e.g. `assert cycles <= 100`.
+#[derive(Debug)]
+pub struct AssertCycles {
+    pub comparison: Comparison,
+    pub cycles: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Checks the outcome of the previous operations.
+#[derive(Debug)]
+pub enum Expect {
+    /// `expect assert_error`: an assert of the program failed in the previous operation. Without
+    /// it, a failed assert of the program fails the test.
+    AssertError,
+    /// `expect finished`
+    Finished,
+    /// `expect at LABEL`: the simulator is at the statement with the label.
+    At(Label),
+}
+
 #[derive(Debug)]
 pub struct SetBreakpoint {
     pub label: Label,
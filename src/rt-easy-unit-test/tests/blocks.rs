@@ -0,0 +1,101 @@
+mod util;
+
+const SOURCE: &str = r#"
+declare register A(7:0)
+declare input IN(7:0)
+declare output OUT(7:0)
+
+A <- IN;
+OUT <- A + 1;
+"#;
+
+#[test]
+fn tests() {
+    const SOURCE_UNIT_TEST: &str = r#"
+# Runs before each test
+setup {
+    IN <- 1
+    step
+}
+
+test "first" {
+    assert A = 1
+    IN <- 5
+    run
+    assert OUT = 2
+}
+
+test "reset between tests" {
+    assert A = 1 and OUT = 0
+}
+
+test "fails" {
+    assert A = 2
+    assert A = 1
+}
+
+test "empty" {
+}
+"#;
+
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+
+    let names = report.tests.iter().map(|test| test.name.as_deref().unwrap()).collect::<Vec<_>>();
+    assert_eq!(names, ["first", "reset between tests", "fails", "empty"]);
+    let failed = report.tests.iter().map(|test| test.error.is_some()).collect::<Vec<_>>();
+    assert_eq!(failed, [false, false, true, false]);
+    assert_eq!((report.passed(), report.failed()), (3, 1));
+
+    let error = report.tests[2].error.as_ref().unwrap();
+    assert_eq!(&SOURCE_UNIT_TEST[error.span.range()], "A = 2");
+}
+
+#[test]
+fn setup_failed() {
+    const SOURCE_UNIT_TEST: &str = "setup {\n    assert A = 1\n}\ntest \"a\" {\n}\ntest \"b\" {\n}";
+
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+
+    assert_eq!(report.failed(), 2);
+    let error = report.tests[1].error.as_ref().unwrap();
+    assert_eq!(&SOURCE_UNIT_TEST[error.span.range()], "A = 1");
+}
+
+#[test]
+fn without_blocks() {
+    let unit_test = util::compile_unit_test("IN <- 1\nstep\nassert A = 1");
+    assert!(unit_test.setup.is_none());
+    assert_eq!(unit_test.tests.len(), 1);
+    assert_eq!(unit_test.tests[0].name, None);
+    assert_eq!(unit_test.tests[0].operations.len(), 3);
+}
+
+#[test]
+fn invalid() {
+    util::compile_unit_test_err("test \"a\" {\n}\nstep");
+    util::compile_unit_test_err("setup {\n}\nsetup {\n}");
+    util::compile_unit_test_err("test a {\n}");
+    util::compile_unit_test_err("test \"a\" {\nstep");
+}
+
+#[test]
+fn pretty_print() {
+    const SOURCE_UNIT_TEST: &str = "test \"a\" {\n}\ntest \"b\" {\n    assert A = 1\n}";
+
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+    let printed = report.pretty_print(SOURCE_UNIT_TEST, Some("test.rtt"), false);
+
+    assert!(
+        printed.starts_with("test a ... ok\ntest b ... FAILED\n\n---- b ----\n"),
+        "{}",
+        printed
+    );
+    assert!(printed.contains("test.rtt:4:12"), "{}", printed);
+    assert!(printed.ends_with("test result: FAILED. 1 passed; 1 failed"), "{}", printed);
+}
@@ -0,0 +1,17 @@
+mod util;
+
+const SOURCE: &str = r#"
+declare constant WIDTH = 8, MSB = WIDTH - 1
+declare register A(MSB:0)
+declare input IN(MSB:0)
+
+A <- IN + WIDTH;
+"#;
+
+#[test]
+fn constant() {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test("IN <- MSB\nstep\nassert A = MSB + WIDTH");
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+    assert!(report.tests.iter().all(|test| test.error.is_none()));
+}
@@ -0,0 +1,63 @@
+mod util;
+
+const SOURCE: &str = r#"
+declare register A(7:0)
+declare input IN(7:0)
+declare output OUT(7:0)
+
+A <- IN;
+LOOP: OUT <- A + 1;
+"#;
+
+fn run_err(source_unit_test: &str) -> (rt_easy_unit_test::TestError, &str) {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(source_unit_test);
+    let error = util::run_err(program, unit_test);
+    let text = &source_unit_test[error.span.range()];
+    (error, text)
+}
+
+#[test]
+fn assert_values() {
+    let (error, text) = run_err("IN <- 12\nstep\nassert A = 12 and OUT = 3");
+    assert_eq!(error.message, "Assert failed");
+    assert_eq!(text, "A = 12 and OUT = 3");
+    assert_eq!(
+        error.notes,
+        [
+            "`A = 12`: left is 12 (0xC), right is 12 (0xC)",
+            "`OUT = 3`: left is 0, right is 3",
+            "cycle 1, at statement 1 (label LOOP)",
+        ]
+    );
+
+    let (error, _) = run_err("run\nassert A(0)");
+    assert_eq!(error.notes, ["`A(0)` is 0", "cycle 2, at the end of the program"]);
+}
+
+#[test]
+fn assert_invalid() {
+    let (error, text) = run_err("assert A + 1");
+    assert_eq!(error.message, "condition is 8 bits wide, expected 1 bit");
+    assert_eq!(text, "A + 1");
+    assert!(error.notes.is_empty());
+
+    let (error, text) = run_err("step\nassert   A = X");
+    assert_eq!(error.message, "no register or bus named \"X\" found [E005]");
+    assert_eq!(text, "X");
+
+    let (error, text) = run_err("\n\nassert A = = 1");
+    assert!(error.message.starts_with("found: \"=\""), "{}", error.message);
+    assert_eq!(text, "=");
+}
+
+#[test]
+fn assignment_invalid() {
+    let (error, text) = run_err("IN <- 1 +");
+    assert!(error.message.starts_with("found: <EOI>"), "{}", error.message);
+    assert_eq!(text, "IN <- 1 +");
+
+    let (error, text) = run_err("IN <- Y");
+    assert_eq!(error.message, "no register or bus named \"Y\" found [E005]");
+    assert_eq!(text, "Y");
+}
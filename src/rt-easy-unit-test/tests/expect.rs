@@ -0,0 +1,123 @@
+mod util;
+
+use rt_easy_unit_test::Options;
+
+const SOURCE: &str = r#"
+declare register A(7:0)
+declare input IN(7:0)
+
+A <- IN;
+CHECK: assert A <> 0;
+A <- A + 1;
+END: A <- A + 1;
+"#;
+
+fn run(source_unit_test: &str) -> Option<(rt_easy_unit_test::TestError, &str)> {
+    run_with(source_unit_test, &Default::default())
+}
+
+fn run_strict(source_unit_test: &str) -> Option<(rt_easy_unit_test::TestError, &str)> {
+    run_with(source_unit_test, &Options { fail_on_assert_error: true })
+}
+
+fn run_with<'a>(
+    source_unit_test: &'a str,
+    options: &Options,
+) -> Option<(rt_easy_unit_test::TestError, &'a str)> {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(source_unit_test);
+    let report = rt_easy_unit_test::run(program, unit_test, options);
+    let error = report.tests.into_iter().find_map(|test| test.error)?;
+    let text = &source_unit_test[error.span.range()];
+    Some((error, text))
+}
+
+#[test]
+fn expect_assert_error() {
+    assert!(run("IN <- 0\nrun\nexpect assert_error\nexpect finished").is_none());
+    assert!(run("IN <- 0\nstep 2\nexpect assert_error # Comment").is_none());
+    assert!(run("IN <- 1\nrun\nexpect finished").is_none());
+
+    let (error, text) = run_strict("IN <- 0\nstep\nstep\nstep").unwrap();
+    assert_eq!(error.message, "Assert of the program failed");
+    assert_eq!(text, "step");
+    assert_eq!(
+        error.notes,
+        [
+            "cycle 1, at statement 1 (label CHECK)",
+            "add `expect assert_error` if the assert is expected to fail"
+        ]
+    );
+
+    let (error, text) = run("IN <- 1\nrun\nexpect assert_error").unwrap();
+    assert_eq!(error.message, "Expected an assert of the program to fail");
+    assert_eq!(text, "expect assert_error");
+    assert_eq!(error.notes, ["cycle 4, at the end of the program"]);
+}
+
+#[test]
+fn unexpected_assert_error() {
+    // By default a failed assert of the program without `expect assert_error` is ignored
+    assert!(run("IN <- 0\nrun\nexpect finished").is_none());
+    assert!(run("IN <- 0\nstep\nstep\nstep").is_none());
+
+    // An ignored assert is not expected by a later `expect assert_error`
+    let (error, text) = run("IN <- 0\nstep 2\nstep\nexpect assert_error").unwrap();
+    assert_eq!(error.message, "Expected an assert of the program to fail");
+    assert_eq!(text, "expect assert_error");
+
+    // With `fail_on_assert_error` it fails the test, even if the program keeps running
+    assert!(run_strict("IN <- 0\nrun\nexpect assert_error\nexpect finished").is_none());
+    let (error, text) = run_strict("IN <- 0\nrun\nexpect finished").unwrap();
+    assert_eq!(error.message, "Assert of the program failed");
+    assert_eq!(text, "run");
+
+    // `expect assert_error` must directly follow the operation that failed the assert
+    let (error, text) = run_strict("IN <- 0\nstep 2\nassert A = 1\nexpect assert_error").unwrap();
+    assert_eq!(error.message, "Assert of the program failed");
+    assert_eq!(text, "step 2");
+}
+
+#[test]
+fn expect_state() {
+    assert!(run("IN <- 1\nset breakpoint END\nrun\nexpect at END").is_none());
+    assert!(run("IN <- 1\nstep\nexpect at CHECK").is_none());
+
+    let (error, _) = run("IN <- 1\nstep\nexpect at END").unwrap();
+    assert_eq!(error.message, "Expected the simulator to be at label END");
+    assert_eq!(error.notes, ["cycle 1, at statement 1 (label CHECK)"]);
+
+    let (error, _) = run("step\nexpect at FOO").unwrap();
+    assert_eq!(error.message, "Label FOO does not exist");
+
+    let (error, text) = run("IN <- 1\nstep 3\nexpect finished").unwrap();
+    assert_eq!(error.message, "Expected the program to be finished");
+    assert_eq!(text, "expect finished");
+}
+
+#[test]
+fn assert_cycles() {
+    assert!(run("IN <- 1\nrun\nassert cycles = 4").is_none());
+    assert!(run("IN <- 1\nrun\nassert cycles<=4\nassert cycles < 5\nassert cycles >= 4").is_none());
+    assert!(run("IN <- 1\nstep 2\nassert cycles <> 4\nassert cycles > 1").is_none());
+
+    let (error, text) = run("IN <- 1\nrun\nassert cycles <= 3").unwrap();
+    assert_eq!(error.message, "Assert failed");
+    assert_eq!(text, "assert cycles <= 3");
+    assert_eq!(
+        error.notes,
+        ["`cycles <= 3`: left is 4, right is 3", "cycle 4, at the end of the program"]
+    );
+}
+
+#[test]
+fn invalid() {
+    util::compile_unit_test_err("expect");
+    util::compile_unit_test_err("expect failure");
+    util::compile_unit_test_err("expect at");
+    util::compile_unit_test_err("expect at end");
+    util::compile_unit_test_err("expect finished END");
+    util::compile_unit_test_err("assert cycles");
+    util::compile_unit_test_err("assert cycles == 3");
+    util::compile_unit_test_err("assert cycles < -1");
+}
@@ -23,5 +23,27 @@ fn missing_input() {
     let program = util::compile(SOURCE);
     let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
 
-    assert!(rt_easy_unit_test::run(program, unit_test).is_err());
+    assert!(!rt_easy_unit_test::run(program, unit_test, &Default::default()).is_success());
+}
+
+#[test]
+fn assert_failed() {
+    const SOURCE: &str = r#"
+        declare register A(7:0)
+        declare output OUT(7:0)
+
+        A <- 3, OUT <- 1;
+    "#;
+
+    const SOURCE_UNIT_TEST: &str = r#"
+        run
+        assert OUT = 1
+        assert A = 3
+        assert A(1:0) + OUT = 0
+    "#;
+
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+
+    assert!(!rt_easy_unit_test::run(program, unit_test, &Default::default()).is_success());
 }
@@ -0,0 +1,47 @@
+mod util;
+
+const SOURCE: &str = r#"
+declare register AR(3:0), DR(7:0)
+declare memory MEM(AR, DR)
+
+AR <- 1, DR <- 0x2A;
+write MEM, AR <- 2, DR <- 0xFF;
+write MEM;
+"#;
+
+#[test]
+fn assert_memory() {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(
+        "assert memory MEM = 0: 0\nrun\nassert memory MEM = 1: 2A FF\nassert memory MEM = 'd 0 42 255",
+    );
+
+    assert!(rt_easy_unit_test::run(program, unit_test, &Default::default()).is_success());
+}
+
+#[test]
+fn assert_memory_differs() {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test("run\nassert memory MEM = 1: 2A 0 1");
+
+    let err = util::run_err(program, unit_test);
+    assert_eq!(
+        err.message,
+        "Memory MEM differs (expected -> actual):\n2: 00 -> FF\n3: 01 -> unset\n"
+    );
+}
+
+#[test]
+fn assert_memory_invalid() {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test("assert memory MEM = 1: 100");
+    let err = util::run_err(program, unit_test);
+    assert!(err.message.starts_with("Invalid content of memory MEM"), "{}", err.message);
+
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test("assert memory FOO = 0: 0");
+    let err = util::run_err(program, unit_test);
+    assert_eq!(err.message, "memory `FOO` does not exist");
+
+    assert!(rt_easy_unit_test::parser::parse("assert memory MEM 0: 0").is_err());
+}
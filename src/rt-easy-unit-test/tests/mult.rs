@@ -49,5 +49,5 @@ fn mult() {
     let program = util::compile(SOURCE);
     let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
 
-    assert!(rt_easy_unit_test::run(program, unit_test).is_ok());
+    assert!(rt_easy_unit_test::run(program, unit_test, &Default::default()).is_success());
 }
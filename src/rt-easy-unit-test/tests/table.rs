@@ -0,0 +1,111 @@
+mod util;
+
+use rt_easy_unit_test::unit_test::TableRows;
+
+const SOURCE: &str = r#"
+declare input A(7:0), B(7:0)
+declare output SUM(7:0)
+
+SUM <- A + B;
+"#;
+
+const SOURCE_UNIT_TEST: &str = r#"
+test "add" for X, Y, Z with {
+    1, 2, 3
+    $FF, 0b1, 0
+    -1, 1, 1
+}
+{
+    A <- {X}
+    B <- {Y}
+    run
+    assert SUM = {Z}
+}
+"#;
+
+#[test]
+fn table() {
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+
+    let rows = report
+        .tests
+        .iter()
+        .map(|test| {
+            assert_eq!(test.name.as_deref(), Some("add"));
+            let values =
+                test.parameters.iter().map(|(_, value)| value.as_str()).collect::<Vec<_>>();
+            (values, test.error.is_some())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rows,
+        [
+            (vec!["1", "2", "3"], false),
+            (vec!["$FF", "0b1", "0"], false),
+            (vec!["-1", "1", "1"], true),
+        ]
+    );
+    assert_eq!(report.tests[0].parameters[0], ("X".to_string(), "1".to_string()));
+
+    // The span of the substituted assert is the whole operation
+    let error = report.tests[2].error.as_ref().unwrap();
+    assert_eq!(&SOURCE_UNIT_TEST[error.span.range()], "assert SUM = {Z}");
+    assert_eq!(error.notes[0], "`SUM = 1`: left is 0, right is 1");
+}
+
+#[test]
+fn load_tables() {
+    const SOURCE_UNIT_TEST: &str = r#"
+test "add" for X, Z with "add.csv" {
+    A <- {X}
+    B <- 1
+    run
+    assert SUM = {Z}
+}
+"#;
+
+    let mut unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    unit_test
+        .load_tables(|path| {
+            assert_eq!(path, "add.csv");
+            Some("# Comment\nX, Y, Z\n1, 0, 2\n 7 ,0, 9\n".to_string())
+        })
+        .unwrap();
+    match &unit_test.tests[0].table.as_ref().unwrap().rows {
+        TableRows::Inline(rows) => assert_eq!(rows[1].values, ["7", "9"]),
+        TableRows::File { .. } => panic!("table not loaded"),
+    }
+
+    let program = util::compile(SOURCE);
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+    assert_eq!((report.passed(), report.failed()), (1, 1));
+
+    // Errors
+    let load_err = |csv: Option<&str>| {
+        let mut unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+        let error = unit_test.load_tables(|_| csv.map(str::to_string)).unwrap_err();
+        assert_eq!(&SOURCE_UNIT_TEST[error.span.range()], "\"add.csv\"");
+        error.message
+    };
+    assert_eq!(load_err(None), "failed to read add.csv");
+    assert_eq!(load_err(Some("X, Y\n1, 2")), "invalid table add.csv: no column Z");
+    assert_eq!(load_err(Some("X, Z\n")), "invalid table add.csv: the table has no rows");
+    assert!(load_err(Some("X, Z\n1, 2, 3")).starts_with("invalid table add.csv: CSV error"));
+
+    // Not loaded
+    let program = util::compile(SOURCE);
+    let unit_test = util::compile_unit_test(SOURCE_UNIT_TEST);
+    let error = util::run_err(program, unit_test);
+    assert_eq!(error.message, "the table add.csv is not loaded");
+}
+
+#[test]
+fn invalid() {
+    util::compile_unit_test_err("test \"a\" for X, Y with {\n1, 2\n3\n}\n{\n}");
+    util::compile_unit_test_err("test \"a\" for X, X with {\n1, 2\n}\n{\n}");
+    util::compile_unit_test_err("test \"a\" for X with {\n}\n{\n}");
+    util::compile_unit_test_err("test \"a\" for 1X with {\n1\n}\n{\n}");
+    util::compile_unit_test_err("test \"a\" for X with {\nA\n}\n{\n}");
+}
@@ -1,11 +1,12 @@
 use rt_easy_unit_test::unit_test::UnitTest;
+use rt_easy_unit_test::TestError;
 use rtprogram::Program;
 
 #[allow(dead_code)] // Not used by every test file
 pub fn compile(source: &str) -> Program {
     let ast = match parser::parse(source) {
         Ok(ast) => ast,
-        Err(e) => panic!("{}", parser::pretty_print_error(&e, source, None, false)),
+        Err(e) => panic!("{}", parser::pretty_print_errors(&e, source, None, false)),
     };
 
     let backend = compiler_backend_simulator::BackendSimulator;
@@ -32,3 +33,13 @@ pub fn compile_unit_test_err(source: &str) -> toktok::Error<rt_easy_unit_test::p
         Err(e) => e,
     }
 }
+
+/// The error of the first failed test.
+#[allow(dead_code)] // Not used by every test file
+pub fn run_err(program: Program, unit_test: UnitTest) -> TestError {
+    let report = rt_easy_unit_test::run(program, unit_test, &Default::default());
+    match report.tests.into_iter().find_map(|test| test.error) {
+        Some(error) => error,
+        None => panic!("all tests passed"),
+    }
+}
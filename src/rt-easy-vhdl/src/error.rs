@@ -15,4 +15,6 @@ pub enum RenderError {
         actual.1,
     )]
     InvalidMemorySize { name: Ident, expected: (usize, usize), actual: (usize, usize) },
+    #[error("invalid {format} memory file for `{name}`: {error}")]
+    InvalidMemoryFile { name: Ident, format: memory_file::Format, error: memory_file::ParseError },
 }
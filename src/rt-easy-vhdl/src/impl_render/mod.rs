@@ -53,14 +53,25 @@ struct VhdlTemplate<'a> {
 }
 
 impl<'a> VhdlTemplate<'a> {
+    fn register_init(init: &Option<rtcore::value::Value>) -> String {
+        match init {
+            Some(init) => format!("\"{}\"", init.as_bin(true)),
+            None => "(OTHERS => '0')".to_string(),
+        }
+    }
+
     fn any_port(&self) -> bool {
         self.declarations.buses.iter().any(|(_, _, kind)| *kind == BusKind::Input)
-            || self.declarations.registers.iter().any(|(_, _, kind)| *kind == RegisterKind::Output)
+            || self
+                .declarations
+                .registers
+                .iter()
+                .any(|(_, _, kind, _)| *kind == RegisterKind::Output)
     }
 
     fn ports_input(&self) -> impl Iterator<Item = (&'a Ident, BitRange, bool)> + '_ {
         let any_output =
-            self.declarations.registers.iter().any(|(_, _, kind)| *kind == RegisterKind::Output);
+            self.declarations.registers.iter().any(|(_, _, kind, _)| *kind == RegisterKind::Output);
         let inputs = self
             .declarations
             .buses
@@ -80,11 +91,11 @@ impl<'a> VhdlTemplate<'a> {
             .declarations
             .registers
             .iter()
-            .filter(|(_, _, kind)| *kind == RegisterKind::Output)
+            .filter(|(_, _, kind, _)| *kind == RegisterKind::Output)
             .collect::<Vec<_>>();
         let len = outputs.len();
 
-        outputs.into_iter().enumerate().map(move |(idx, (name, range, _))| {
+        outputs.into_iter().enumerate().map(move |(idx, (name, range, _, _))| {
             let is_last = idx == len - 1;
             (name, *range, is_last)
         })
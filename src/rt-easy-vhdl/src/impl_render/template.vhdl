@@ -24,6 +24,7 @@ PACKAGE HELPER_{{ module_name }} IS
     FUNCTION f_gt_s(in0 : unsigned; in1 : unsigned) RETURN unsigned;
     FUNCTION f_add(in0 : unsigned; in1 : unsigned) RETURN unsigned;
     FUNCTION f_sub(in0 : unsigned; in1 : unsigned) RETURN unsigned;
+    FUNCTION f_mul(in0 : unsigned; in1 : unsigned) RETURN unsigned;
     FUNCTION f_and(in0 : unsigned; in1 : unsigned) RETURN unsigned;
     FUNCTION f_nand(in0 : unsigned; in1 : unsigned) RETURN unsigned;
     FUNCTION f_or(in0 : unsigned; in1 : unsigned) RETURN unsigned;
@@ -108,6 +109,10 @@ PACKAGE BODY HELPER_{{ module_name }} IS
         RETURN in0 - in1;
     END FUNCTION;
 
+    FUNCTION f_mul(in0 : unsigned; in1 : unsigned) RETURN unsigned IS BEGIN
+        RETURN resize(in0 * in1, in0'LENGTH);
+    END FUNCTION;
+
     FUNCTION f_and(in0 : unsigned; in1 : unsigned) RETURN unsigned IS BEGIN
         RETURN in0 AND in1;
     END FUNCTION;
@@ -248,6 +253,7 @@ USE work.HELPER_{{ module_name }}.ALL;
 ENTITY EU_{{ module_name }} IS
     PORT (
         clock : IN STD_LOGIC;
+        reset : IN STD_LOGIC;
         c : IN STD_LOGIC_VECTOR({{ operations.len().checked_sub(1).unwrap_or(0) }} DOWNTO 0);
         k : OUT STD_LOGIC_VECTOR({{ criteria.len().checked_sub(1).unwrap_or(0) }} DOWNTO 0){% if self.any_port() %};{% endif %}
 
@@ -269,8 +275,8 @@ ARCHITECTURE Behavioral OF EU_{{ module_name }} IS
     ATTRIBUTE KEEP : STRING;
 
     -- Registers
-    {% for (name, range, _) in declarations.registers.iter() %}
-        SIGNAL register_{{ name }} : unsigned{{ RenderAsVhdl(*range) }} := (OTHERS => '0');
+    {% for (name, range, _, init) in declarations.registers.iter() %}
+        SIGNAL register_{{ name }} : unsigned{{ RenderAsVhdl(*range) }} := {{ Self::register_init(init) }};
         ATTRIBUTE KEEP OF register_{{ name }} : SIGNAL IS "TRUE";
     {% endfor %}
 
@@ -305,7 +311,7 @@ ARCHITECTURE Behavioral OF EU_{{ module_name }} IS
     {% endfor %}
 BEGIN
     -- Map registers to output
-    {% for (name, _, _) in declarations.registers.iter().filter(|(_, _, kind)| *kind == RegisterKind::Output) %}
+    {% for (name, _, _, _) in declarations.registers.iter().filter(|(_, _, kind, _)| *kind == RegisterKind::Output) %}
         output_{{ name }} <= register_{{ name }};
     {% endfor %}
 
@@ -330,12 +336,16 @@ BEGIN
     END PROCESS;
 
     -- Clocked operations
-    ClockedOp : PROCESS (clock)
+    ClockedOp : PROCESS (clock, reset)
         {% for (idx, range) in self.operations_tmp_var(true) %}
             VARIABLE tmp_c_{{ idx }} : unsigned{{ RenderAsVhdl(range) }};
         {% endfor %}
     BEGIN
-        IF rising_edge(clock) THEN
+        IF reset = '1' THEN
+            {% for (name, _, _, init) in declarations.registers.iter() %}
+                register_{{ name }} <= {{ Self::register_init(init) }};
+            {% endfor %}
+        ELSIF rising_edge(clock) THEN
             {% for (idx, operation) in self.operations(true) %}
 
                 -- control signal {{ idx }}: {{ RenderAsRt(operation) }}
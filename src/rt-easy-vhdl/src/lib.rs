@@ -43,6 +43,26 @@ impl Vhdl {
     ) -> Result<String, RenderError> {
         crate::impl_render::render(self, module_name, memories)
     }
+
+    /// Imports the memory file for the memory `name`, with the address and data register sizes
+    /// of its declaration.
+    pub fn import_memory_file(
+        &self,
+        name: &Ident,
+        format: memory_file::Format,
+        source: &[u8],
+        options: &memory_file::Options,
+    ) -> Result<memory_file::MemoryFile, RenderError> {
+        let (_, ar, dr) = self
+            .declarations
+            .memories
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .ok_or_else(|| RenderError::MemoryNotFound(name.clone()))?;
+
+        memory_file::MemoryFile::import(format, source, ar.1.size(), dr.1.size(), options)
+            .map_err(|error| RenderError::InvalidMemoryFile { name: name.clone(), format, error })
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -51,9 +71,10 @@ impl Vhdl {
 
 #[derive(Debug)]
 pub struct Declarations {
-    pub registers: Vec<(Ident, BitRange, RegisterKind)>, // (Name, Range, Kind)
-    pub buses: Vec<(Ident, BitRange, BusKind)>,          // (Name, Range, Kind)
-    pub register_arrays: Vec<(Ident, BitRange, usize)>,  // (Name, Range, Length)
+    /// (Name, Range, Kind, Initial value)
+    pub registers: Vec<(Ident, BitRange, RegisterKind, Option<rtcore::value::Value>)>,
+    pub buses: Vec<(Ident, BitRange, BusKind)>, // (Name, Range, Kind)
+    pub register_arrays: Vec<(Ident, BitRange, usize)>, // (Name, Range, Length)
     pub memories: Vec<(Ident, (Ident, BitRange, RegisterKind), (Ident, BitRange, RegisterKind))>, // (Name, AR, DR)
 }
 
@@ -123,6 +144,10 @@ impl Label {
         Self(format!("NAMED_{}", name))
     }
 
+    pub fn expanded(name: &str, expansion: usize) -> Self {
+        Self(format!("EXPANDED_{}_{}", expansion, name))
+    }
+
     pub fn unnamed(idx: usize) -> Self {
         Self(format!("UNNAMED_{}", idx))
     }
@@ -98,6 +98,7 @@ fn binary_operator(op: BinaryOperator) -> &'static str {
         BinaryOperator::Gt => "f_gt",
         BinaryOperator::Add => "f_add",
         BinaryOperator::Sub => "f_sub",
+        BinaryOperator::Mul => "f_mul",
         BinaryOperator::And => "f_and",
         BinaryOperator::Nand => "f_nand",
         BinaryOperator::Or => "f_or",
@@ -1,6 +1,6 @@
 mod util;
 
-use memory_file::MemoryFile;
+use memory_file::{Format, MemoryFile, ParseErrorKind};
 use rt_easy_vhdl::{error::RenderError, Ident};
 use std::collections::HashMap;
 
@@ -49,3 +49,32 @@ fn invalid_memory_size() {
         },
     }
 }
+
+#[test]
+fn invalid_memory_file() {
+    const SOURCE: &'static str = r#"
+        declare register X(3:0), Y(7:0)
+        declare memory MEM(X, Y)
+
+        X <- 1;
+    "#;
+
+    let vhdl = util::compile(SOURCE);
+    let name = Ident("MEM".to_string());
+
+    let mem = vhdl.import_memory_file(&name, Format::ReadMemH, b"@2 FF", &Default::default());
+    assert_eq!(mem.unwrap().data().len(), 1);
+
+    match vhdl.import_memory_file(&name, Format::ReadMemH, b"1FF", &Default::default()) {
+        Ok(_) => panic!("expected error"),
+        Err(err) => match err {
+            RenderError::InvalidMemoryFile { name, format, error } => {
+                assert_eq!(name.0, "MEM");
+                assert_eq!(format, Format::ReadMemH);
+                assert_eq!(error.kind, ParseErrorKind::ValueTooWide { dr_size: 8 });
+                assert_eq!((error.line, error.column), (1, 1));
+            }
+            _ => panic!("expected RenderError::InvalidMemoryFile"),
+        },
+    }
+}
@@ -4,7 +4,7 @@ use rt_easy_vhdl::Vhdl;
 pub fn compile(source: &str) -> Vhdl {
     let ast = match parser::parse(source) {
         Ok(ast) => ast,
-        Err(e) => panic!("{}", parser::pretty_print_error(&e, source, None, false)),
+        Err(e) => panic!("{}", parser::pretty_print_errors(&e, source, None, false)),
     };
 
     let backend = BackendVhdl;
@@ -0,0 +1,16 @@
+use wasm_bindgen::prelude::*;
+
+/// Assembles the program into a memory file, which can be loaded with
+/// `Simulator::load_memory_from_save`.
+#[wasm_bindgen]
+pub fn assemble(isa: String, code: String) -> Result<String, JsValue> {
+    let isa = match rt_easy::assembler::Isa::parse(&isa) {
+        Ok(isa) => isa,
+        Err(e) => return Err(JsValue::from_str(&e.pretty_print(&isa, Some("isa"), true))),
+    };
+
+    match rt_easy::assembler::assemble(&isa, &code) {
+        Ok(mem) => Ok(mem.to_string()),
+        Err(e) => Err(JsValue::from_str(&e.pretty_print(&code, None, true))),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Simulator;
+use crate::{Files, Simulator};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -6,9 +6,14 @@ pub fn check(code: String) -> Result<(), JsValue> {
     let ast = match rt_easy::parser::parse(&code) {
         Ok(ast) => ast,
         Err(e) => {
-            return Err(JsValue::from_str(&rt_easy::parser::pretty_print_error(
-                &e, &code, None, true,
-            )))
+            let mut message = rt_easy::parser::pretty_print_errors(&e, &code, None, true);
+            if let Some(partial) = e.partial {
+                if let Err(e) = rt_easy::compiler::check_partial(*partial, &Default::default()) {
+                    message += "\n\n";
+                    message += &e.pretty_print(&code, None, true);
+                }
+            }
+            return Err(JsValue::from_str(&message));
         }
     };
 
@@ -25,7 +30,7 @@ pub fn build(code: String) -> Result<Simulator, JsValue> {
     let ast = match rt_easy::parser::parse(&code) {
         Ok(ast) => ast,
         Err(e) => {
-            return Err(JsValue::from_str(&rt_easy::parser::pretty_print_error(
+            return Err(JsValue::from_str(&rt_easy::parser::pretty_print_errors(
                 &e, &code, None, true,
             )))
         }
@@ -39,3 +44,58 @@ pub fn build(code: String) -> Result<Simulator, JsValue> {
 
     Ok(Simulator(rt_easy::simulator::Simulator::init(program)))
 }
+
+#[wasm_bindgen]
+pub fn check_files(files: &Files, root: String) -> Result<(), JsValue> {
+    let files = &files.0;
+    let root = files.find(&root).ok_or_else(|| JsValue::from_str("root file not found"))?;
+
+    let ast = match rt_easy::parser::parse_files(files, root) {
+        Ok(ast) => ast,
+        Err(e) => {
+            let mut message = rt_easy::parser::pretty_print_file_errors(&e, files, true);
+            if let Some(partial) = e.partial {
+                if let Err(e) = rt_easy::compiler::check_partial(*partial, &Default::default()) {
+                    message += "\n\n";
+                    message += &e.pretty_print_files(files, true);
+                }
+            }
+            return Err(JsValue::from_str(&message));
+        }
+    };
+
+    match rt_easy::compiler::check(ast, &Default::default()) {
+        Ok(()) => (),
+        Err(e) => return Err(JsValue::from_str(&e.pretty_print_files(files, true))),
+    };
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn build_files(files: &Files, root: String) -> Result<Simulator, JsValue> {
+    let files = &files.0;
+    let root = files.find(&root).ok_or_else(|| JsValue::from_str("root file not found"))?;
+
+    let ast = match rt_easy::parser::parse_files(files, root) {
+        Ok(ast) => ast,
+        Err(e) => {
+            return Err(JsValue::from_str(&rt_easy::parser::pretty_print_file_errors(
+                &e, files, true,
+            )))
+        }
+    };
+
+    let backend = rt_easy::compiler_backend_simulator::BackendSimulator;
+    let program = match rt_easy::compiler::compile(&backend, (), ast, &Default::default()) {
+        Ok(program) => program,
+        Err(e) => return Err(JsValue::from_str(&e.pretty_print_files(files, true))),
+    };
+
+    Ok(Simulator(rt_easy::simulator::Simulator::init(program)))
+}
+
+#[wasm_bindgen]
+pub fn explain(code: usize) -> Option<String> {
+    rt_easy::compiler::explain(code)
+}
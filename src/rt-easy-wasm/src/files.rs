@@ -0,0 +1,19 @@
+use wasm_bindgen::prelude::*;
+
+/// A virtual file map, used to resolve `include "..."` without a file system.
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct Files(pub(crate) rt_easy::rtcore::files::Files);
+
+#[wasm_bindgen]
+impl Files {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the file `name`.
+    pub fn add(&mut self, name: String, source: String) {
+        self.0.add(name, source);
+    }
+}
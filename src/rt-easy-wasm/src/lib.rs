@@ -1,6 +1,8 @@
 #![deny(rust_2018_idioms)]
 
+mod assembler;
 mod compiler;
+mod files;
 mod signals;
 mod simulator;
 mod span;
@@ -9,7 +11,9 @@ mod util;
 
 use wasm_bindgen::prelude::*;
 
-pub use self::{compiler::*, signals::*, simulator::*, span::*, step_result::*};
+pub use self::{
+    assembler::*, compiler::*, files::*, signals::*, simulator::*, span::*, step_result::*,
+};
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc<'_> = wee_alloc::WeeAlloc::INIT;
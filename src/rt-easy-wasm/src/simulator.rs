@@ -2,6 +2,7 @@ use crate::{
     util::{JsError, *},
     Signals, Span, StepResult,
 };
+use rt_easy::memory_file::ParseError;
 use rt_easy::rtcore::{
     common::{BusKind, RegisterKind},
     value::{SignedValue, Value},
@@ -62,6 +63,10 @@ impl Simulator {
         })
     }
 
+    pub fn evaluate(&self, expr: &str, base: &str) -> Result<String> {
+        map_err(move || Ok(self.0.evaluate(expr)?.as_base(base)?))
+    }
+
     pub fn registers(&self, kind: &str) -> Result<Vec<JsValue>> {
         let kind = match kind {
             "Intern" => RegisterKind::Intern,
@@ -275,7 +280,16 @@ impl Simulator {
 
     pub fn load_memory_from_save(&mut self, name: String, save: &str) -> Result<()> {
         map_err(move || {
-            self.0.load_memory_from_save(&Ident(name), save.as_bytes())?;
+            self.0.load_memory_from_save(&Ident(name), save.as_bytes()).map_err(|e| {
+                match e.downcast_ref::<ParseError>() {
+                    Some(error) => JsError::from_str(&format!(
+                        "{}\n{}",
+                        e,
+                        error.pretty_print(save.as_bytes(), None, true)
+                    )),
+                    None => JsError::from(e),
+                }
+            })?;
             Ok(())
         })
     }
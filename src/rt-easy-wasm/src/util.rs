@@ -1,4 +1,4 @@
-use rt_easy::rtcore::value::{SignedValue, ValueSlice};
+use rt_easy::rtcore::value::{SignedValue, Value, ValueSlice};
 use rt_easy::simulator::Error as SimulatorError;
 use wasm_bindgen::prelude::*;
 
@@ -31,7 +31,13 @@ pub trait ValueSliceExt {
     fn as_base(&self, base: &str) -> Result<String, JsError>;
 }
 
-impl ValueSliceExt for ValueSlice {
+impl ValueSliceExt for Value {
+    fn as_base(&self, base: &str) -> Result<String, JsError> {
+        self.as_slice().as_base(base)
+    }
+}
+
+impl ValueSliceExt for ValueSlice<'_> {
     fn as_base(&self, base: &str) -> Result<String, JsError> {
         match base {
             "BIN" => Ok(self.as_bin(true)),
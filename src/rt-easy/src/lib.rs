@@ -1,4 +1,4 @@
 pub use {
-    compiler, compiler_backend_simulator, compiler_backend_vhdl, parser, rtast, rtcore, rtprogram,
-    simulator,
+    assembler, compiler, compiler_backend_simulator, compiler_backend_vhdl, memory_file, parser,
+    rtast, rtcore, rtprogram, simulator,
 };
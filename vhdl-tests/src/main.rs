@@ -172,7 +172,7 @@ impl Tb {
         let rt_code = fs::read_to_string(self.src_dir.join(self.rt_file_name()))?;
         let ast = match parser::parse(&rt_code) {
             Ok(ast) => ast,
-            Err(e) => bail!("{}", parser::pretty_print_error(&e, &rt_code, None, false)),
+            Err(e) => bail!("{}", parser::pretty_print_errors(&e, &rt_code, None, false)),
         };
         let vhdl = match compiler::compile(&BackendVhdl, (), ast, &Default::default()) {
             Ok(vhdl) => vhdl,
@@ -195,7 +195,7 @@ impl Tb {
                     .to_owned();
                 let source = fs::read_to_string(path)?;
                 let memory_file = MemoryFile::parse(&source)
-                    .map_err(|()| anyhow!("failed to parse memory `{}`", name))?;
+                    .map_err(|e| anyhow!("failed to parse memory `{}`: {}", name, e))?;
                 memories.insert(rtvhdl::Ident(name), memory_file);
             }
         }
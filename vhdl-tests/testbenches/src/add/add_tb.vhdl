@@ -22,6 +22,7 @@ BEGIN
     -- Connect ports
     MAP_EU : ENTITY work.EU_add PORT MAP(
         clock => clock_p,
+        reset => reset,
         c => c,
         k => k,
         input_A => input_A,
@@ -29,6 +29,7 @@ BEGIN
     -- Connect ports
     MAP_EU : ENTITY work.EU_bit_range PORT MAP(
         clock => clock_p,
+        reset => reset,
         c => c,
         k => k,
         input_IN_A => input_IN_A,
@@ -20,6 +20,7 @@ BEGIN
     -- Connect ports
     MAP_EU : ENTITY work.EU_concat_lvalue PORT MAP(
         clock => clock_p,
+        reset => reset,
         c => c,
         k => k,
         output_OUTPUT => output_OUTPUT
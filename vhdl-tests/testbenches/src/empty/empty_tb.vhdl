@@ -18,6 +18,7 @@ BEGIN
     -- Connect ports
     MAP_EU : ENTITY work.EU_empty PORT MAP(
         clock => clock_p,
+        reset => reset,
         c => c,
         k => k
         );
@@ -21,6 +21,7 @@ BEGIN
     -- Connect ports
     MAP_EU : ENTITY work.EU_if PORT MAP(
         clock => clock_p,
+        reset => reset,
         c => c,
         k => k,
         input_IN => input_IN,
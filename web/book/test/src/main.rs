@@ -259,7 +259,7 @@ fn check_code(source: &str) -> Result<(), CheckCodeError> {
         Ok(ast) => ast,
         Err(e) => {
             return Err(CheckCodeError {
-                error: anyhow!(parser::pretty_print_error(&e, &source, None, true)),
+                error: anyhow!(parser::pretty_print_errors(&e, &source, None, true)),
                 codes: HashSet::new(),
             })
         }
@@ -288,7 +288,7 @@ fn run_code(source: &str) -> Result<()> {
     let program = {
         let ast = match parser::parse(&source) {
             Ok(ast) => ast,
-            Err(e) => bail!(parser::pretty_print_error(&e, &source, None, true)),
+            Err(e) => bail!(parser::pretty_print_errors(&e, &source, None, true)),
         };
 
         let backend = compiler_backend_simulator::BackendSimulator;
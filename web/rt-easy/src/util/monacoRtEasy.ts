@@ -18,8 +18,10 @@ export function setUpRtEasyLang(monaco: Monaco) {
       "case",
       "default",
       "assert",
+      "include",
+      "define",
     ],
-    typeKeywords: ["input", "output", "register", "bus", "memory", "array"],
+    typeKeywords: ["input", "output", "register", "bus", "memory", "array", "constant"],
     operators: [
       "=",
       "<>",
@@ -29,6 +31,7 @@ export function setUpRtEasyLang(monaco: Monaco) {
       ">",
       "+",
       "-",
+      "*",
       "and",
       "nand",
       "or",
@@ -155,6 +158,9 @@ export function setUpRtEasyLang(monaco: Monaco) {
         completionKeyWord("bus"),
         completionKeyWord("memory"),
         completionKeyWord("register array"),
+        completionKeyWord("constant"),
+        completionKeyWord("include"),
+        completionKeyWord("define"),
 
         // Other keywords
         completionKeyWord("goto"),